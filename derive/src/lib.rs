@@ -0,0 +1,235 @@
+use std::path::Path;
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr, Type};
+
+// `#[derive(UiconfModel)]` for data models, should be used together with `#[derive(Reflect)]`,
+// or replaced with `#[uiconf_model]` which adds both.
+//
+// Generates `UiconfModel` implementation, and `<Name>Bindings` struct with a constant
+// for each field, so binding names can be checked by the compiler:
+//
+//     assert_eq!(PlayerUiBindings::health, "@health");
+//
+#[proc_macro_derive(UiconfModel)]
+pub fn derive_uiconf_model(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+// `#[uiconf_model]` is a shorthand for `#[derive(Reflect, UiconfModel)]`:
+//
+//     #[uiconf_model]
+//     struct PlayerUi { health: f32, r#type: String }
+//
+// Derive macros can't add other derives to the same struct, so this has to be an attribute.
+#[proc_macro_attribute]
+pub fn uiconf_model(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        let attr = proc_macro2::TokenStream::from(attr);
+        return syn::Error::new_spanned(attr, "uiconf_model doesn't take arguments").to_compile_error().into();
+    }
+    let item = proc_macro2::TokenStream::from(item);
+    quote! {
+        #[derive(::bevy::reflect::Reflect, ::bevy_uiconf_egui::UiconfModel)]
+        #item
+    }.into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(input, "UiconfModel can only be derived for structs"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(input, "UiconfModel can only be derived for structs with named fields"));
+    };
+
+    // fields with `#[reflect(ignore)]` are not visible to bindings
+    let fields = fields.named.iter().filter(|field| !is_reflect_ignored(field)).collect::<Vec<_>>();
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let bindings = format_ident!("{}Bindings", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // reflection uses unprefixed names, `r#type` is bound as `@type`
+    let field_names = fields.iter().map(|field| field.ident.as_ref().unwrap().unraw().to_string()).collect::<Vec<_>>();
+    let field_idents = fields.iter().map(|field| field.ident.as_ref().unwrap()).collect::<Vec<_>>();
+    let field_refs = field_names.iter().map(|name| format!("@{}", name)).collect::<Vec<_>>();
+    let field_types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let triggers = fields.iter()
+        .filter(|field| is_trigger(&field.ty))
+        .map(|field| field.ident.as_ref().unwrap().unraw().to_string())
+        .collect::<Vec<_>>();
+
+    let doc = format!("Binding names of [`{}`] fields.", name);
+
+    Ok(quote! {
+        impl #impl_generics ::bevy_uiconf_egui::UiconfModel for #name #ty_generics #where_clause {
+            const FIELDS: &'static [&'static str] = &[#(#field_names),*];
+            const TRIGGERS: &'static [&'static str] = &[#(#triggers),*];
+
+            fn register_types(registry: &mut ::bevy::reflect::TypeRegistry) {
+                registry.register::<Self>();
+                #(registry.register::<#field_types>();)*
+            }
+        }
+
+        #[doc = #doc]
+        #vis struct #bindings;
+
+        #[allow(non_upper_case_globals)]
+        impl #bindings {
+            #(pub const #field_idents: &'static str = #field_refs;)*
+        }
+    })
+}
+
+// `#[derive(LabelToId)]` for enums with unit variants, used to query responses of widgets:
+//
+//     #[derive(LabelToId)]
+//     enum MenuLabel { StartButton, Quit }
+//
+//     window.response(ctx, MenuLabel::StartButton); // widget with `id = start_button`
+//
+#[proc_macro_derive(LabelToId)]
+pub fn derive_label_to_id(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_label(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_label(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(input, "LabelToId can only be derived for enums"));
+    };
+    if let Some(variant) = data.variants.iter().find(|variant| !matches!(variant.fields, Fields::Unit)) {
+        return Err(syn::Error::new_spanned(variant, "LabelToId can only be derived for enums with unit variants"));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants = data.variants.iter().map(|variant| &variant.ident).collect::<Vec<_>>();
+    let ids = variants.iter().map(|variant| to_snake_case(&variant.unraw().to_string())).collect::<Vec<_>>();
+
+    Ok(quote! {
+        impl #impl_generics ::bevy_uiconf_egui::LabelToId for #name #ty_generics #where_clause {
+            fn to_id(&self) -> ::std::borrow::Cow<'static, str> {
+                match self {
+                    #(Self::#variants => ::std::borrow::Cow::Borrowed(#ids),)*
+                }
+            }
+        }
+    })
+}
+
+// `include_uiconf!("assets/gui/menu.gui")`, documented in the main crate where it's re-exported.
+//
+// File is embedded with `include_bytes!`, so that cargo rebuilds the crate whenever it changes.
+// Only syntax is checked here: widgets can't be read without custom widgets and data models,
+// which are only known at runtime.
+#[proc_macro]
+pub fn include_uiconf(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    match expand_include(&path) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_include(path: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let file = path.value();
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| syn::Error::new_spanned(path, "CARGO_MANIFEST_DIR is not set"))?;
+    let full_path = Path::new(&manifest_dir).join(&file);
+    let data = std::fs::read(&full_path)
+        .map_err(|err| syn::Error::new_spanned(path, format!("failed to read {}: {}", file, err)))?;
+    check_syntax(&data, &file).map_err(|message| syn::Error::new_spanned(path, message))?;
+
+    let full_path = full_path.to_string_lossy();
+    Ok(quote! {{
+        static WINDOW: ::std::sync::OnceLock<::bevy_uiconf_egui::UiconfWindow> = ::std::sync::OnceLock::new();
+        WINDOW.get_or_init(|| {
+            let data: &'static [u8] = include_bytes!(#full_path);
+            ::bevy_uiconf_egui::UiconfWindow::from_embedded(data, #file).unwrap_or_else(|errors| {
+                let errors = errors.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+                panic!("{}", errors.join("\n\n"))
+            })
+        })
+    }})
+}
+
+// same formats as the loader, selected by file extension
+fn check_syntax(data: &[u8], file: &str) -> Result<(), String> {
+    if file.ends_with(".ron") {
+        return ron::de::from_bytes::<serde::de::IgnoredAny>(data).map(|_| ()).map_err(|err| {
+            format!("failed to parse {}:{}:{} as ron: {}", file, err.position.line, err.position.col, err.code)
+        });
+    }
+    if file.ends_with(".json") {
+        return serde_json::from_slice::<serde::de::IgnoredAny>(data).map(|_| ()).map_err(|err| {
+            format!("failed to parse {}:{}:{} as json: {}", file, err.line(), err.column(), err)
+        });
+    }
+
+    let tape = jomini::TextTape::from_slice(data).map_err(|err| {
+        // unexpected end of file is reported at the end
+        let (line, column) = line_column(data, err.offset().unwrap_or(data.len()));
+        format!("syntax error: {} (at {}:{}:{})", err, file, line, column)
+    })?;
+
+    if !tape.utf8_reader().fields().any(|(key, _, _)| key.read_str() == "window") {
+        return Err(format!("missing field `window` (at {})", file));
+    }
+    Ok(())
+}
+
+// 1-based, same as in errors reported at runtime
+fn line_column(data: &[u8], offset: usize) -> (usize, usize) {
+    let before = &data[..offset.min(data.len())];
+    let line_start = before.iter().rposition(|c| *c == b'\n').map_or(0, |idx| idx + 1);
+    let line = before.iter().filter(|c| **c == b'\n').count() + 1;
+    (line, String::from_utf8_lossy(&before[line_start..]).chars().count() + 1)
+}
+
+// `StartButton` => `start_button`, `HUDPanel` => `hud_panel`
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut result = String::new();
+    for (idx, ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() && idx > 0 {
+            let prev_lower = chars[idx - 1].is_lowercase() || chars[idx - 1].is_ascii_digit();
+            let next_lower = chars.get(idx + 1).is_some_and(|next| next.is_lowercase());
+            if prev_lower || (chars[idx - 1].is_uppercase() && next_lower) {
+                result.push('_');
+            }
+        }
+        result.extend(ch.to_lowercase());
+    }
+    result
+}
+
+fn is_reflect_ignored(field: &syn::Field) -> bool {
+    field.attrs.iter().any(|attr| {
+        if !attr.path().is_ident("reflect") { return false; }
+        let mut ignored = false;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("ignore") { ignored = true; }
+            Ok(())
+        });
+        ignored
+    })
+}
+
+// matched by name, same as users would write it
+fn is_trigger(ty: &Type) -> bool {
+    let Type::Path(path) = ty else { return false; };
+    path.path.segments.last().is_some_and(|segment| segment.ident == "Trigger")
+}
@@ -5,7 +5,7 @@ use bevy::prelude::*;
 use bevy::winit::{UpdateMode, WinitSettings};
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use bevy_uiconf_egui::reader::data_model::Trigger;
-use bevy_uiconf_egui::{AssetServerExt, UiconfPlugin, UiconfWindow};
+use bevy_uiconf_egui::{AssetServerExt, UiconfInspectorPlugin, UiconfPlugin, UiconfWindow};
 
 #[derive(Resource, Default)]
 struct MyWindow {
@@ -33,7 +33,8 @@ fn main() {
             DefaultPlugins,
             WorldInspectorPlugin::new()
                 .run_if(input_toggle_active(false, KeyCode::F12)),
-            UiconfPlugin,
+            UiconfPlugin { show_load_errors: true, ..default() },
+            UiconfInspectorPlugin::default(),
         ))
         .register_type::<DataModel>()
         .insert_resource(WinitSettings {
@@ -56,7 +57,7 @@ fn main() {
 }
 
 fn initialize_uiconf_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let handle = asset_server.load_uiconf("gui/window.gui");
+    let handle = asset_server.load_uiconf_with_schema::<DataModel>("gui/window.gui");
     commands.insert_resource(MyWindow { handle });
 }
 
@@ -0,0 +1,61 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::shared::EguiShared;
+
+type PlaySound = dyn Fn(&mut Commands, &AssetServer, &str) + Send + Sync;
+
+// Sounds requested by `clicked_sound = "sfx/click.ogg"` and `hovered_sound = ...`,
+// insert it as a resource to enable them:
+//
+//     app.insert_resource(UiconfAudio::default());
+//
+// By default sounds are loaded from asset server and played once, custom hook can be used
+// to route them through your own audio system:
+//
+//     app.insert_resource(UiconfAudio::new(|commands, asset_server, path| { ... }));
+//
+#[derive(Resource, Clone)]
+pub struct UiconfAudio {
+    queue: Arc<Mutex<Vec<String>>>,
+    play: Arc<PlaySound>,
+}
+
+impl Default for UiconfAudio {
+    fn default() -> Self {
+        Self::new(|commands, asset_server, path| {
+            commands.spawn(AudioBundle {
+                source: asset_server.load(path.to_owned()),
+                settings: PlaybackSettings::DESPAWN,
+            });
+        })
+    }
+}
+
+impl UiconfAudio {
+    pub fn new(play: impl Fn(&mut Commands, &AssetServer, &str) + Send + Sync + 'static) -> Self {
+        Self { queue: Default::default(), play: Arc::new(play) }
+    }
+
+    // sound is played at the end of the frame, same sound is only played once per frame
+    pub fn queue(&self, path: &str) {
+        let mut queue = self.queue.lock().unwrap();
+        if !queue.iter().any(|queued| queued == path) {
+            queue.push(path.to_owned());
+        }
+    }
+}
+
+impl EguiShared for UiconfAudio {
+    const ID: &'static str = "bevy_uiconf_egui::audio";
+}
+
+pub fn play_queued_sounds(audio: Option<Res<UiconfAudio>>, asset_server: Res<AssetServer>, mut commands: Commands) {
+    let Some(audio) = audio else { return; };
+
+    let queue = std::mem::take(&mut *audio.queue.lock().unwrap());
+    for path in queue.iter() {
+        (audio.play)(&mut commands, &asset_server, path);
+    }
+}
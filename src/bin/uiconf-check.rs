@@ -0,0 +1,27 @@
+use std::process::ExitCode;
+
+use bevy_uiconf_egui::validate::validate_file;
+
+fn main() -> ExitCode {
+    let files = std::env::args().skip(1).collect::<Vec<_>>();
+    if files.is_empty() {
+        eprintln!("usage: uiconf-check <file.gui | file.gui.ron | file.gui.json>...");
+        return ExitCode::FAILURE;
+    }
+
+    let mut failed = false;
+    for file in files.iter() {
+        if let Err(errors) = validate_file(file, None) {
+            failed = true;
+            for error in errors {
+                eprintln!("error: {}\n", error);
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
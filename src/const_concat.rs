@@ -1,31 +1,21 @@
-// this heinous hackery is released under Unlicense
-// https://github.com/eira-fransham/const-concat
+// Concatenation of `&'static [&'static str]` slices in const context,
+// used to combine FIELDS lists of nested properties.
 
-pub const unsafe fn transmute<From, To>(from: From) -> To {
-    union Transmute<From, To> {
-        from: std::mem::ManuallyDrop<From>,
-        to: std::mem::ManuallyDrop<To>,
-    }
-
-    std::mem::ManuallyDrop::into_inner(Transmute { from: std::mem::ManuallyDrop::new(from) }.to)
-}
-
-pub const unsafe fn concat<First, Second, Out>(a: &'static [&'static str], b: &'static [&'static str]) -> Out
-where
-    First: Copy,
-    Second: Copy,
-    Out: Copy,
-{
-    #[repr(C)]
-    #[derive(Copy, Clone)]
-    struct Both<A, B>(A, B);
+pub const fn concat<const N: usize>(a: &[&'static str], b: &[&'static str]) -> [&'static str; N] {
+    assert!(a.len() + b.len() == N, "output length should be equal to the sum of input lengths");
 
-    let arr: Both<First, Second> = Both(
-        *transmute::<_, *const First>(a.as_ptr()),
-        *transmute::<_, *const Second>(b.as_ptr()),
-    );
-
-    transmute(arr)
+    let mut result = [""; N];
+    let mut i = 0;
+    while i < a.len() {
+        result[i] = a[i];
+        i += 1;
+    }
+    let mut j = 0;
+    while j < b.len() {
+        result[i + j] = b[j];
+        j += 1;
+    }
+    result
 }
 
 #[macro_export]
@@ -37,15 +27,9 @@ macro_rules! const_concat {
         $a
     };
     ($a:expr, $b:expr) => {{
-        let ret: &'static [&'static str] = unsafe {
-            &$crate::const_concat::concat::<
-                [&'static str; $a.len()],
-                [&'static str; $b.len()],
-                [&'static str; $a.len() + $b.len()],
-            >($a, $b)
-        };
-
-        unsafe { $crate::const_concat::transmute::<_, &'static [&'static str]>(ret) }
+        const LEN: usize = $a.len() + $b.len();
+        const RESULT: [&'static str; LEN] = $crate::const_concat::concat::<LEN>($a, $b);
+        &RESULT
     }};
     ($a:expr, $($rest:expr),*) => {{
         const TAIL: &'static [&'static str] = const_concat!($($rest),*);
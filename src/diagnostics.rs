@@ -0,0 +1,110 @@
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::egui;
+use crate::loader::EguiAsset;
+use crate::shared::EguiShared;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticKind {
+    // field or namespace doesn't exist in data model
+    UnresolvedBinding,
+    // field exists, but has a type widget can't use
+    TypeMismatch,
+    // formatter is unknown, or doesn't accept bound value
+    Formatter,
+    UnknownLocalizationKey,
+    // `theme` isn't registered in `UiconfThemes`
+    UnknownTheme,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    // path of the asset the problem was found in
+    pub asset: String,
+    pub message: String,
+}
+
+// Problems found while windows are shown, each one is reported once per binding and asset version.
+// Enabled by default, render them in a debug overlay or check them in tests:
+//
+//     fn check_ui(diagnostics: Res<UiconfDiagnostics>) {
+//         assert!(diagnostics.is_empty(), "{:#?}", diagnostics.entries());
+//     }
+//
+// Diagnostics of an asset are cleared when it's reloaded.
+#[derive(Resource, Clone, Default)]
+pub struct UiconfDiagnostics {
+    entries: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+impl UiconfDiagnostics {
+    pub fn entries(&self) -> Vec<Diagnostic> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    pub fn for_asset(&self, asset: &str) -> Vec<Diagnostic> {
+        self.entries.lock().unwrap().iter().filter(|entry| entry.asset == asset).cloned().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().unwrap().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    pub fn clear_asset(&self, asset: &str) {
+        self.entries.lock().unwrap().retain(|entry| entry.asset != asset);
+    }
+
+    pub(crate) fn install(&self, ctx: &egui::Context) {
+        ctx.data_mut(|d| d.insert_temp(Self::egui_id(), self.clone()));
+    }
+}
+
+thread_local! {
+    // bindings don't know which asset they belong to, so it's set for the duration of `EguiAsset::show`
+    static CURRENT: RefCell<Option<(UiconfDiagnostics, String)>> = const { RefCell::new(None) };
+}
+
+pub(crate) fn scope<R>(diagnostics: Option<UiconfDiagnostics>, asset: &str, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.with(|current| {
+        current.replace(diagnostics.map(|diagnostics| (diagnostics, asset.to_owned())))
+    });
+    let result = f();
+    CURRENT.with(|current| current.replace(previous));
+    result
+}
+
+// logs a warning, and records it if shown inside `scope`; callers make sure it's only reported once
+pub(crate) fn report(kind: DiagnosticKind, message: String) {
+    bevy::log::warn!("{}", message);
+    CURRENT.with(|current| {
+        let Some((diagnostics, asset)) = &*current.borrow() else { return; };
+        diagnostics.entries.lock().unwrap().push(Diagnostic { kind, asset: asset.clone(), message });
+    });
+}
+
+impl EguiShared for UiconfDiagnostics {
+    const ID: &'static str = "bevy_uiconf_egui::diagnostics";
+}
+
+pub fn clear_reloaded_diagnostics(
+    diagnostics: Option<Res<UiconfDiagnostics>>,
+    mut events: EventReader<AssetEvent<EguiAsset>>,
+    assets: Res<Assets<EguiAsset>>,
+) {
+    let Some(diagnostics) = diagnostics else { return; };
+
+    for event in events.read() {
+        let AssetEvent::Modified { id } = event else { continue; };
+        if let Some(asset) = assets.get(*id) {
+            diagnostics.clear_asset(&asset.path);
+        }
+    }
+}
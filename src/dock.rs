@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+#[cfg(feature = "egui_dock")]
+use crate::egui;
+use crate::model::Dock;
+use crate::shared::EguiShared;
+
+// Current arrangement of windows with `dock` property: which side each window is docked to,
+// and the size of its panel. Windows are keyed by their explicit `id`, windows without one
+// always stay where the file puts them.
+//
+// Arrangement can be saved and restored between runs, e.g. with `ron`:
+//
+//     let saved = ron::to_string(&dock.arrangement())?;
+//     dock.restore(ron::from_str(&saved)?);
+//
+// With `egui_dock` feature, docked windows are tabs of a single dock area instead of panels.
+// Tabs can be dragged onto each other or to the edges of other tabs to split them, and windows
+// docked to the same side start out as tabs of the same node. Arrangement of tabs is saved
+// with `UiconfPersistence`, see `UiconfPersistence::dock_path`.
+#[derive(Resource, Clone, Default)]
+pub struct UiconfDock {
+    windows: Arc<Mutex<HashMap<String, DockedWindow>>>,
+    #[cfg(feature = "egui_dock")]
+    tabs: Arc<Mutex<DockTabs>>,
+}
+
+// Tabs are keyed the same way as saved window layouts, see `UiconfLayouts::window`.
+#[cfg(feature = "egui_dock")]
+#[derive(Default)]
+struct DockTabs {
+    // `None` until the first window is docked
+    state: Option<egui_dock::DockState<String>>,
+    // side each tab was docked to, windows docked to the same side are put together
+    sides: HashMap<String, Dock>,
+    titles: HashMap<String, String>,
+    // layer, content rect and clip rect of tabs shown this frame, the dock area is shown before
+    // any window, and windows draw their content into the rect of their tab
+    shown: HashMap<String, (egui::LayerId, egui::Rect, egui::Rect)>,
+    saved: Option<String>,
+}
+
+#[cfg(feature = "egui_dock")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SavedTabs {
+    state: egui_dock::DockState<String>,
+    sides: HashMap<String, Dock>,
+}
+
+#[cfg(feature = "egui_dock")]
+impl DockTabs {
+    fn remove(&mut self, key: &str) {
+        if self.sides.remove(key).is_none() { return; }
+        let key = key.to_owned();
+        if let Some(state) = &mut self.state {
+            if let Some(tab) = state.find_tab(&key) {
+                state.remove_tab(tab);
+            }
+        }
+    }
+
+    fn add(&mut self, key: &str, dock: Dock) {
+        let key = key.to_owned();
+        let same_side = self.sides.iter()
+            .filter(|(other, side)| **side == dock && **other != key)
+            .find_map(|(other, _)| self.state.as_ref()?.find_tab(other));
+        let first = self.sides.is_empty();
+        self.sides.insert(key.clone(), dock);
+
+        let state = match &mut self.state {
+            Some(state) if !first => state,
+            _ => {
+                self.state = Some(egui_dock::DockState::new(vec![key]));
+                return;
+            }
+        };
+        if let Some((surface, node, _)) = same_side {
+            state.set_focused_node_and_surface((surface, node));
+            state.push_to_focused_leaf(key);
+            return;
+        }
+
+        // new node takes a quarter of the area
+        let tree = state.main_surface_mut();
+        let root = egui_dock::NodeIndex::root();
+        match dock {
+            Dock::Left   => { tree.split_left(root, 0.25, vec![key]); }
+            Dock::Right  => { tree.split_right(root, 0.75, vec![key]); }
+            Dock::Top    => { tree.split_above(root, 0.25, vec![key]); }
+            Dock::Bottom => { tree.split_below(root, 0.75, vec![key]); }
+            Dock::Float  => {}
+        }
+    }
+}
+
+// content is drawn by the window itself, when it's shown later in the frame
+#[cfg(feature = "egui_dock")]
+struct TabRects<'a> {
+    titles: &'a HashMap<String, String>,
+    shown: &'a mut HashMap<String, (egui::LayerId, egui::Rect, egui::Rect)>,
+}
+
+#[cfg(feature = "egui_dock")]
+impl egui_dock::TabViewer for TabRects<'_> {
+    type Tab = String;
+
+    fn title(&mut self, tab: &mut String) -> egui::WidgetText {
+        self.titles.get(tab).unwrap_or(tab).as_str().into()
+    }
+
+    fn id(&mut self, tab: &mut String) -> egui::Id {
+        egui::Id::new(("bevy_uiconf_egui::dock_tab", &*tab))
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, tab: &mut String) {
+        self.shown.insert(tab.clone(), (ui.layer_id(), ui.max_rect(), ui.clip_rect()));
+    }
+
+    // windows are shown by game code, so they can't be closed from here
+    fn closeable(&mut self, _tab: &mut String) -> bool {
+        false
+    }
+
+    // docked windows have scroll area of their own
+    fn scroll_bars(&self, _tab: &String) -> [bool; 2] {
+        [false, false]
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct DockedWindow {
+    pub dock: Dock,
+    // width of left and right panels, height of top and bottom ones
+    pub size: Option<f32>,
+}
+
+impl UiconfDock {
+    pub fn window(&self, id: &str) -> Option<DockedWindow> {
+        self.windows.lock().unwrap().get(id).copied()
+    }
+
+    // moves window to another side, panel size is reset
+    pub fn set_dock(&self, id: &str, dock: Dock) {
+        self.windows.lock().unwrap().insert(id.to_owned(), DockedWindow { dock, size: None });
+    }
+
+    pub(crate) fn set_size(&self, id: &str, dock: Dock, size: f32) {
+        self.windows.lock().unwrap().insert(id.to_owned(), DockedWindow { dock, size: Some(size) });
+    }
+
+    pub fn arrangement(&self) -> HashMap<String, DockedWindow> {
+        self.windows.lock().unwrap().clone()
+    }
+
+    pub fn restore(&self, arrangement: HashMap<String, DockedWindow>) {
+        *self.windows.lock().unwrap() = arrangement;
+    }
+
+    // Rect to draw content of a docked window in, the window is added as a tab if it isn't docked yet.
+    // `None` if the tab isn't shown, e.g. another tab of the same node is active.
+    #[cfg(feature = "egui_dock")]
+    pub(crate) fn tab_rect(&self, key: &str, dock: Dock, title: String) -> Option<(egui::LayerId, egui::Rect, egui::Rect)> {
+        let mut tabs = self.tabs.lock().unwrap();
+        tabs.titles.insert(key.to_owned(), title);
+        // moved to another side with `set_dock`, tabs dragged by user keep their side
+        if tabs.sides.get(key) != Some(&dock) {
+            tabs.remove(key);
+            tabs.add(key, dock);
+        }
+        tabs.shown.get(key).copied()
+    }
+
+    // window was made floating with `set_dock`
+    #[cfg(feature = "egui_dock")]
+    pub(crate) fn remove_tab(&self, key: &str) {
+        self.tabs.lock().unwrap().remove(key);
+    }
+
+    #[cfg(feature = "egui_dock")]
+    fn show_tabs(&self, ctx: &egui::Context) {
+        let mut tabs = self.tabs.lock().unwrap();
+        let DockTabs { state, titles, shown, sides, .. } = &mut *tabs;
+        shown.clear();
+        // dock area takes the whole central panel, so it's only shown when something is docked
+        let Some(state) = state.as_mut().filter(|_| !sides.is_empty()) else { return; };
+        egui_dock::DockArea::new(state).show(ctx, &mut TabRects { titles, shown });
+    }
+
+    // `None` if the arrangement didn't change since the last call
+    #[cfg(feature = "egui_dock")]
+    pub(crate) fn changed_tabs(&self) -> Option<String> {
+        let mut tabs = self.tabs.lock().unwrap();
+        let saved = SavedTabs { state: tabs.state.clone()?, sides: tabs.sides.clone() };
+        let text = ron::to_string(&saved).map_err(|err| bevy::log::warn!("failed to serialize dock tabs: {}", err)).ok()?;
+        if tabs.saved.as_ref() == Some(&text) { return None; }
+        tabs.saved = Some(text.clone());
+        Some(text)
+    }
+
+    #[cfg(feature = "egui_dock")]
+    pub(crate) fn restore_tabs(&self, text: &str) {
+        let saved = match ron::from_str::<SavedTabs>(text) {
+            Ok(saved) => saved,
+            Err(err) => {
+                bevy::log::warn!("failed to read dock tabs: {}", err);
+                return;
+            }
+        };
+        let mut tabs = self.tabs.lock().unwrap();
+        tabs.state = Some(saved.state);
+        tabs.sides = saved.sides;
+        tabs.saved = Some(text.to_owned());
+    }
+}
+
+// shown before game systems, so that windows know where their tabs are when they are shown
+#[cfg(feature = "egui_dock")]
+pub fn show_dock_area(dock: Res<UiconfDock>, mut egui_contexts: bevy_egui::EguiContexts) {
+    dock.show_tabs(egui_contexts.ctx_mut());
+}
+
+impl EguiShared for UiconfDock {
+    const ID: &'static str = "bevy_uiconf_egui::dock";
+}
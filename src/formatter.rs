@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy::reflect::Reflect;
+
+use crate::reader::data_model::reflect_as_f64;
+use crate::shared::EguiShared;
+
+// Formats bound value for display, `text = "@gold | thousands"`, `arg` is what follows
+// the colon in `percent:1`. Returns `None` if value type is not supported.
+pub trait Formatter: Send + Sync + 'static {
+    fn format(&self, value: &dyn Reflect, arg: Option<&str>) -> Option<String>;
+}
+
+impl<F> Formatter for F
+where
+    F: Fn(&dyn Reflect, Option<&str>) -> Option<String> + Send + Sync + 'static,
+{
+    fn format(&self, value: &dyn Reflect, arg: Option<&str>) -> Option<String> {
+        self(value, arg)
+    }
+}
+
+// Formatters available to all windows, built-in ones are `thousands`, `percent` and `duration`.
+// Add your own by modifying the resource:
+//
+//     app.world.resource_mut::<UiconfFormatters>().add("stars", |value: &dyn Reflect, _: Option<&str>| {
+//         Some("*".repeat(*value.downcast_ref::<u32>()? as usize))
+//     });
+//
+#[derive(Resource, Clone)]
+pub struct UiconfFormatters(Arc<HashMap<String, Arc<dyn Formatter>>>);
+
+impl Default for UiconfFormatters {
+    fn default() -> Self {
+        let mut result = Self(Default::default());
+        result.add("thousands", thousands);
+        result.add("percent", percent);
+        result.add("duration", duration);
+        result
+    }
+}
+
+impl UiconfFormatters {
+    // replaces existing formatter with the same name
+    pub fn add(&mut self, name: impl Into<String>, formatter: impl Formatter) {
+        Arc::make_mut(&mut self.0).insert(name.into(), Arc::new(formatter));
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    pub fn format(&self, name: &str, value: &dyn Reflect, arg: Option<&str>) -> Option<String> {
+        self.0.get(name)?.format(value, arg)
+    }
+}
+
+impl EguiShared for UiconfFormatters {
+    const ID: &'static str = "bevy_uiconf_egui::formatters";
+}
+
+// `arg` is the number of decimal places, defaults to 0
+fn decimals(arg: Option<&str>) -> Option<usize> {
+    arg.map_or(Some(0), |arg| arg.trim().parse().ok())
+}
+
+// `1234567.5 | thousands:1` => `1,234,567.5`
+fn thousands(value: &dyn Reflect, arg: Option<&str>) -> Option<String> {
+    let value = reflect_as_f64(value)?;
+    let formatted = format!("{:.*}", decimals(arg)?, value.abs());
+    let (int, frac) = formatted.split_at(formatted.find('.').unwrap_or(formatted.len()));
+
+    let mut result = String::new();
+    if value < 0. && formatted.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+        result.push('-');
+    }
+    for (idx, ch) in int.chars().enumerate() {
+        if idx > 0 && (int.len() - idx) % 3 == 0 {
+            result.push(',');
+        }
+        result.push(ch);
+    }
+    result.push_str(frac);
+    Some(result)
+}
+
+// `0.125 | percent:1` => `12.5%`
+fn percent(value: &dyn Reflect, arg: Option<&str>) -> Option<String> {
+    let value = reflect_as_f64(value)?;
+    Some(format!("{:.*}%", decimals(arg)?, value * 100.))
+}
+
+// seconds or `Duration` => `m:ss` or `h:mm:ss`
+fn duration(value: &dyn Reflect, _arg: Option<&str>) -> Option<String> {
+    let seconds = if let Some(value) = value.downcast_ref::<std::time::Duration>() {
+        value.as_secs_f64()
+    } else {
+        reflect_as_f64(value)?
+    };
+
+    let sign = if seconds < 0. { "-" } else { "" };
+    let seconds = seconds.abs() as u64;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        Some(format!("{}{}:{:02}:{:02}", sign, hours, minutes, seconds))
+    } else {
+        Some(format!("{}{}:{:02}", sign, minutes, seconds))
+    }
+}
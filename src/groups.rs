@@ -0,0 +1,46 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::shared::EguiShared;
+
+// Named groups of widgets that are hidden together across all windows, e.g. debug overlays
+// or elements only shown to spectators. Widgets are tagged with `groups = { debug economy }`,
+// and are hidden if any of their groups is hidden:
+//
+//     fn toggle_debug(groups: Res<UiconfGroups>, keys: Res<Input<KeyCode>>) {
+//         if keys.just_pressed(KeyCode::F3) {
+//             groups.set_group_visible("debug", !groups.is_group_visible("debug"));
+//         }
+//     }
+//
+// All groups are visible by default.
+#[derive(Resource, Clone, Default)]
+pub struct UiconfGroups {
+    hidden: Arc<Mutex<HashSet<String>>>,
+}
+
+impl UiconfGroups {
+    pub fn set_group_visible(&self, group: &str, visible: bool) {
+        let mut hidden = self.hidden.lock().unwrap();
+        if visible {
+            hidden.remove(group);
+        } else {
+            hidden.insert(group.to_owned());
+        }
+    }
+
+    pub fn is_group_visible(&self, group: &str) -> bool {
+        !self.hidden.lock().unwrap().contains(group)
+    }
+
+    pub(crate) fn are_visible(&self, groups: &[String]) -> bool {
+        let hidden = self.hidden.lock().unwrap();
+        groups.iter().all(|group| !hidden.contains(group))
+    }
+}
+
+impl EguiShared for UiconfGroups {
+    const ID: &'static str = "bevy_uiconf_egui::groups";
+}
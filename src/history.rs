@@ -0,0 +1,233 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+use bevy::reflect::{ReflectMut, ReflectRef};
+use bevy::utils::HashMap;
+
+use crate::egui;
+use crate::reader::data_model::{DataSource, Trigger};
+use crate::shared::EguiShared;
+
+// Undo history of data model fields changed through widgets, e.g. for in-game editors.
+// Insert it as a resource to enable, and bind buttons to `undo` and `redo` actions:
+//
+//     app.insert_resource(UiconfHistory::new(100));
+//
+//     button = { text = "Undo" clicked = undo }
+//
+// Changes made by the app itself aren't recorded. Changes of the same field that follow each other
+// closely are merged, so that dragging a slider is undone at once.
+#[derive(Resource, Clone)]
+pub struct UiconfHistory {
+    inner: Arc<Mutex<HistoryInner>>,
+    // oldest edits are forgotten when there are more than this
+    limit: usize,
+}
+
+#[derive(Default)]
+struct HistoryInner {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+    pending: Vec<HistoryAction>,
+    // frame number each window was last shown in
+    shown: HashMap<egui::Id, u64>,
+}
+
+struct Edit {
+    // window the field was changed in, edits are reverted on the same data
+    window: egui::Id,
+    name: String,
+    old: Box<dyn Reflect>,
+    new: Box<dyn Reflect>,
+    time: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum HistoryAction {
+    Undo,
+    Redo,
+}
+
+impl UiconfHistory {
+    // changes closer than this (in seconds) are merged into one edit
+    const MERGE_INTERVAL: f64 = 0.5;
+
+    pub fn new(limit: usize) -> Self {
+        Self { inner: Default::default(), limit }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.inner.lock().unwrap().undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.inner.lock().unwrap().redo.is_empty()
+    }
+
+    // edits are reverted when their window is shown next time, because only the window has its data;
+    // edits of windows that are no longer shown can't be reverted, and are forgotten instead
+    pub fn undo(&self) {
+        self.inner.lock().unwrap().pending.push(HistoryAction::Undo);
+    }
+
+    pub fn redo(&self) {
+        self.inner.lock().unwrap().pending.push(HistoryAction::Redo);
+    }
+
+    pub fn clear(&self) {
+        *self.inner.lock().unwrap() = HistoryInner::default();
+    }
+
+    // Fields are copied before widgets get mutable access to them, and compared after the window is shown.
+    pub(crate) fn record(&self, window: egui::Id, data: &mut dyn DataSource, ctx: &egui::Context, show: impl FnOnce(&mut dyn DataSource)) {
+        self.run_pending(window, data, ctx.frame_nr());
+
+        let mut source = RecordingSource { data, snapshots: vec![] };
+        show(&mut source);
+        let RecordingSource { data, snapshots } = source;
+
+        let time = ctx.input(|i| i.time);
+        let mut inner = self.inner.lock().unwrap();
+
+        for (name, old) in snapshots {
+            let Some(new) = field(data, &name) else { continue; };
+            if new.reflect_partial_eq(&*old).unwrap_or(false) { continue; }
+            let new = new.clone_value();
+            inner.redo.clear();
+
+            if let Some(last) = inner.undo.last_mut() {
+                if last.window == window && last.name == name && time - last.time < Self::MERGE_INTERVAL {
+                    last.new = new;
+                    last.time = time;
+                    continue;
+                }
+            }
+
+            inner.undo.push(Edit { window, name, old, new, time });
+            if inner.undo.len() > self.limit {
+                inner.undo.remove(0);
+            }
+        }
+    }
+
+    // actions for edits made in other windows are left until those windows are shown,
+    // if they weren't shown in the previous frame either, they're hidden or closed
+    fn run_pending(&self, window: egui::Id, data: &mut dyn DataSource, frame: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        let inner = &mut *inner;
+        inner.shown.insert(window, frame);
+
+        while let Some(&action) = inner.pending.first() {
+            let (from, to) = match action {
+                HistoryAction::Undo => (&mut inner.undo, &mut inner.redo),
+                HistoryAction::Redo => (&mut inner.redo, &mut inner.undo),
+            };
+            match from.last() {
+                Some(edit) if edit.window != window => {
+                    let is_shown = inner.shown.get(&edit.window).is_some_and(|shown| shown + 1 >= frame);
+                    if is_shown { break; }
+                    from.pop();
+                    continue;
+                }
+                Some(_) => {
+                    let edit = from.pop().unwrap();
+                    let value = match action {
+                        HistoryAction::Undo => &*edit.old,
+                        HistoryAction::Redo => &*edit.new,
+                    };
+                    if let Some(field) = field_mut(data, &edit.name) {
+                        field.apply(value);
+                    }
+                    to.push(edit);
+                }
+                None => {}
+            }
+            inner.pending.remove(0);
+        }
+    }
+}
+
+fn field<'a>(data: &'a dyn DataSource, name: &str) -> Option<&'a dyn Reflect> {
+    let (source, field) = data.source(name)?;
+    let ReflectRef::Struct(source) = source.reflect_ref() else { return None; };
+    source.field(field)
+}
+
+fn field_mut<'a>(data: &'a mut dyn DataSource, name: &str) -> Option<&'a mut dyn Reflect> {
+    let (source, field) = data.source_mut(name)?;
+    let ReflectMut::Struct(source) = source.reflect_mut() else { return None; };
+    source.field_mut(field)
+}
+
+// Keeps a copy of every field as it was before widgets got mutable access to it.
+// Triggers are events rather than state, so they aren't recorded.
+struct RecordingSource<'a> {
+    data: &'a mut dyn DataSource,
+    snapshots: Vec<(String, Box<dyn Reflect>)>,
+}
+
+impl DataSource for RecordingSource<'_> {
+    fn source<'n>(&self, name: &'n str) -> Option<(&dyn Reflect, &'n str)> {
+        self.data.source(name)
+    }
+
+    fn source_mut<'n>(&mut self, name: &'n str) -> Option<(&mut dyn Reflect, &'n str)> {
+        if !self.snapshots.iter().any(|(recorded, _)| recorded == name) {
+            if let Some(value) = field(&*self.data, name).filter(|value| !value.is::<Trigger>()) {
+                self.snapshots.push((name.to_owned(), value.clone_value()));
+            }
+        }
+        self.data.source_mut(name)
+    }
+
+    fn data_ptr(&self) -> *const () {
+        self.data.data_ptr()
+    }
+}
+
+impl EguiShared for UiconfHistory {
+    const ID: &'static str = "bevy_uiconf_egui::history";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Reflect, Default)]
+    struct Settings {
+        volume: f32,
+    }
+
+    #[test]
+    fn close_edits_merged() {
+        let ctx = egui::Context::default();
+        let history = UiconfHistory::new(10);
+        let window = egui::Id::new("window");
+        let mut settings = Settings::default();
+
+        let show = |settings: &mut Settings, time: f64, volume: Option<f32>| {
+            ctx.begin_frame(egui::RawInput { time: Some(time), ..Default::default() });
+            history.record(window, settings, &ctx, |data| {
+                if let (Some(volume), Some(field)) = (volume, field_mut(data, "volume")) {
+                    field.apply(&volume);
+                }
+            });
+            let _ = ctx.end_frame();
+        };
+
+        // second edit is within the interval from the first one, third isn't
+        show(&mut settings, 0.0, Some(1.));
+        show(&mut settings, 0.3, Some(2.));
+        show(&mut settings, 1.0, Some(3.));
+        assert_eq!(history.inner.lock().unwrap().undo.len(), 2);
+
+        history.undo();
+        show(&mut settings, 1.1, None);
+        assert_eq!(settings.volume, 2.);
+        history.undo();
+        show(&mut settings, 1.2, None);
+        assert_eq!(settings.volume, 0.);
+        assert!(!history.can_undo());
+        assert!(history.can_redo());
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::egui;
+use crate::shared::EguiShared;
+
+// Textures of images shown by widgets, e.g. item icons in `slot_grid`. Paths usually come from data model,
+// so images are loaded from asset server when they're first shown, and nothing is drawn until they're ready.
+// Loaded images are kept for the lifetime of the app.
+#[derive(Resource, Clone, Default)]
+pub struct UiconfImages {
+    inner: Arc<Mutex<ImagesInner>>,
+}
+
+#[derive(Default)]
+struct ImagesInner {
+    requested: Vec<String>,
+    loaded: HashMap<String, LoadedImage>,
+}
+
+struct LoadedImage {
+    handle: Handle<Image>,
+    texture: egui::TextureId,
+    // known once the image is loaded
+    size: Option<egui::Vec2>,
+}
+
+impl UiconfImages {
+    // texture of an image at `path`, `None` if it's requested for the first time
+    pub fn texture(&self, path: &str) -> Option<egui::TextureId> {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(image) = inner.loaded.get(path) {
+            return Some(image.texture);
+        }
+        if !inner.requested.iter().any(|requested| requested == path) {
+            inner.requested.push(path.to_owned());
+        }
+        None
+    }
+
+    // size of an image in pixels, `None` until it's loaded
+    pub fn size(&self, path: &str) -> Option<egui::Vec2> {
+        self.inner.lock().unwrap().loaded.get(path)?.size
+    }
+}
+
+impl EguiShared for UiconfImages {
+    const ID: &'static str = "bevy_uiconf_egui::images";
+}
+
+// textures are registered right away, bevy_egui skips them until the image is loaded
+pub fn load_requested_images(
+    images: Option<Res<UiconfImages>>,
+    asset_server: Res<AssetServer>,
+    assets: Res<Assets<Image>>,
+    mut egui_contexts: bevy_egui::EguiContexts,
+) {
+    let Some(images) = images else { return; };
+
+    let mut inner = images.inner.lock().unwrap();
+    for path in std::mem::take(&mut inner.requested) {
+        let handle: Handle<Image> = asset_server.load(path.clone());
+        let texture = egui_contexts.add_image(handle.clone());
+        inner.loaded.insert(path, LoadedImage { handle, texture, size: None });
+    }
+
+    for image in inner.loaded.values_mut().filter(|image| image.size.is_none()) {
+        if let Some(asset) = assets.get(&image.handle) {
+            let size = asset.size_f32();
+            image.size = Some(egui::vec2(size.x, size.y));
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+
+use crate::egui;
+use crate::loader::{EguiAsset, UiconfLoadErrors};
+use crate::writer::writer::Writer;
+use crate::writer::ToUiconf;
+use crate::UiconfDiagnostics;
+
+// Debug window listing all loaded .gui assets with their diagnostics and load errors,
+// add it after `UiconfPlugin`, and toggle with F10:
+//
+//     app.add_plugins(UiconfInspectorPlugin::default());
+//
+pub struct UiconfInspectorPlugin {
+    pub toggle_key: KeyCode,
+    // whether inspector is shown on startup
+    pub visible: bool,
+}
+
+impl Default for UiconfInspectorPlugin {
+    fn default() -> Self {
+        Self { toggle_key: KeyCode::F10, visible: false }
+    }
+}
+
+impl Plugin for UiconfInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(UiconfInspector { toggle_key: self.toggle_key, visible: self.visible });
+        app.add_systems(Update, (toggle_inspector, show_inspector).chain());
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct UiconfInspector {
+    pub toggle_key: KeyCode,
+    pub visible: bool,
+}
+
+pub fn toggle_inspector(mut inspector: ResMut<UiconfInspector>, keys: Res<Input<KeyCode>>) {
+    if keys.just_pressed(inspector.toggle_key) {
+        inspector.visible = !inspector.visible;
+    }
+}
+
+pub fn show_inspector(
+    mut inspector: ResMut<UiconfInspector>,
+    assets: Res<Assets<EguiAsset>>,
+    asset_server: Res<AssetServer>,
+    diagnostics: Option<Res<UiconfDiagnostics>>,
+    errors: Option<Res<UiconfLoadErrors>>,
+    mut egui_contexts: bevy_egui::EguiContexts,
+) {
+    if !inspector.visible { return; }
+
+    // assets that failed on the first load don't exist yet, so they are only known by their errors
+    let mut entries = BTreeMap::<String, (Option<&EguiAsset>, Option<String>)>::new();
+    for (_, asset) in assets.iter() {
+        entries.entry(asset.path.clone()).or_default().0 = Some(asset);
+    }
+    for (path, error) in errors.map(|errors| errors.all()).unwrap_or_default() {
+        entries.entry(path).or_default().1 = Some(error);
+    }
+
+    let mut visible = inspector.visible;
+    egui::Window::new("uiconf inspector")
+        .id(egui::Id::new("bevy_uiconf_egui::inspector"))
+        .open(&mut visible)
+        .default_width(400.)
+        .vscroll(true)
+        .show(egui_contexts.ctx_mut(), |ui| {
+            if entries.is_empty() {
+                ui.weak("no assets loaded");
+            }
+
+            for (path, (asset, error)) in entries.iter() {
+                let diagnostics = diagnostics.as_ref().map(|diagnostics| diagnostics.for_asset(path)).unwrap_or_default();
+                let mut header = egui::RichText::new(path);
+                if error.is_some() {
+                    header = header.color(ui.visuals().error_fg_color);
+                } else if !diagnostics.is_empty() {
+                    header = header.color(ui.visuals().warn_fg_color);
+                }
+
+                egui::CollapsingHeader::new(header).id_source(path).show(ui, |ui| {
+                    if let Some(asset) = asset {
+                        if let Some(title) = &asset.window.title {
+                            let mut writer = Writer::new();
+                            title.write_uiconf(&mut writer);
+                            ui.label(format!("window: {}", writer.finish().trim()));
+                        }
+                        ui.label(format!("fonts: {}, dependencies: {}", asset.fonts.len(), asset.dependencies.len()));
+                    }
+
+                    if diagnostics.is_empty() {
+                        ui.weak("no runtime problems");
+                    }
+                    for diagnostic in diagnostics.iter() {
+                        ui.colored_label(ui.visuals().warn_fg_color, format!("{:?}: {}", diagnostic.kind, diagnostic.message));
+                    }
+
+                    if let Some(error) = error {
+                        ui.colored_label(ui.visuals().error_fg_color, egui::RichText::new(error).monospace());
+                    }
+
+                    if ui.button("Reload").clicked() {
+                        asset_server.reload(path.clone());
+                    }
+                });
+            }
+        });
+
+    if visible != inspector.visible {
+        inspector.visible = visible;
+    }
+}
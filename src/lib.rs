@@ -1,24 +1,155 @@
-use std::sync::atomic::{AtomicU32, Ordering};
-
 use bevy::asset::AssetPath;
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 
 use self::loader::{EguiAsset, EguiAssetLoader, EguiAssetLoaderSettings};
 use self::reader::data_model::Trigger;
+use self::reader::ReadUiconf;
+use self::response::WidgetState;
+pub use self::audio::UiconfAudio;
+pub use self::diagnostics::{Diagnostic, DiagnosticKind, UiconfDiagnostics};
+pub use self::dock::UiconfDock;
+pub use self::formatter::{Formatter, UiconfFormatters};
+pub use self::groups::UiconfGroups;
+pub use self::history::UiconfHistory;
+pub use self::images::UiconfImages;
+pub use self::inspector::UiconfInspectorPlugin;
+pub use self::loader::UiconfLoadErrors;
+pub use self::localize::{Localize, UiconfLocalizer};
+pub use self::navigation::{NavAction, UiconfNavigation};
+pub use self::persistence::{UiconfLayouts, UiconfPersistence};
+pub use self::pointer::{uiconf_keyboard_free, uiconf_not_typing, uiconf_pointer_free, UiconfPointerState};
+pub use self::reader::data_model::UiconfModel;
+pub use self::reader::reader::LoadingMode;
+pub use self::registry::{CustomProperty, CustomWidget, UiconfWidgetRegistry};
+pub use self::response::{LabelToId, WidgetResponse};
+pub use self::safe_area::UiconfSafeArea;
+pub use self::scale::UiconfScale;
+pub use self::shared::{sync_to_egui, EguiShared};
+pub use self::state::UiconfStates;
+pub use self::theme::UiconfThemes;
+pub use bevy_uiconf_egui_derive::{uiconf_model, LabelToId, UiconfModel};
 
+pub mod audio;
 mod const_concat;
+pub mod diagnostics;
+pub mod dock;
+pub mod formatter;
+pub mod groups;
+pub mod history;
+pub mod images;
+pub mod inspector;
 pub mod loader;
+pub mod localize;
 pub mod model;
+pub mod navigation;
+pub mod persistence;
+pub mod pointer;
 pub mod reader;
+pub mod registry;
+pub mod response;
+pub mod safe_area;
+pub mod scale;
+pub mod shared;
+#[cfg(feature = "image")]
+pub mod snapshot;
+pub mod state;
+pub mod theme;
+pub mod uiconf_test;
+pub mod validate;
+pub mod writer;
 
 #[derive(Default)]
-pub struct UiconfPlugin;
+pub struct UiconfPlugin {
+    // default loading mode, can be overridden per asset with `EguiAssetLoaderSettings`
+    pub mode: LoadingMode,
+    // data model to validate bindings against, can be overridden per asset with `EguiAssetLoaderSettings`
+    pub schema: Option<&'static str>,
+    // keep window position, size and collapsed state when asset is hot-reloaded,
+    // by default all state of the window is reset whenever the file changes;
+    // widget state (scroll offsets, collapsing headers, etc.) is kept only for widgets that didn't move
+    // in the file, state left under old ids of moved widgets is removed, see `clear_egui_state_on_reload`
+    pub keep_window_state: bool,
+    // show a small window with the error of every asset that failed to load, handy with hot-reloading;
+    // the last good version of a reloaded asset is shown either way
+    pub show_load_errors: bool,
+    // save window positions and sizes to a file, and restore them on startup
+    pub persistence: Option<UiconfPersistence>,
+    // initial `UiconfSafeArea`, for windows with `respect_safe_area = yes`
+    pub safe_area: egui::Margin,
+}
+
+impl UiconfPlugin {
+    // Validate bindings of all loaded assets against `T`, type must be registered in `AppTypeRegistry`.
+    pub fn with_schema<T: TypePath>(mut self) -> Self {
+        self.schema = Some(T::type_path());
+        self
+    }
+}
 
 impl Plugin for UiconfPlugin {
     fn build(&self, app: &mut App) {
+        let widgets = app.world.get_resource::<UiconfWidgetRegistry>().cloned().unwrap_or_default();
+        app.insert_resource(widgets.clone());
+        let errors = UiconfLoadErrors::default();
+        app.insert_resource(errors.clone());
         app.init_asset::<EguiAsset>();
-        app.init_asset_loader::<EguiAssetLoader>();
+        app.register_asset_loader(EguiAssetLoader {
+            mode: self.mode,
+            schema: self.schema,
+            keep_window_state: self.keep_window_state,
+            type_registry: app.world.get_resource::<AppTypeRegistry>().cloned().unwrap_or_default(),
+            widgets,
+            errors,
+        });
         app.register_type::<Trigger>();
+        app.add_event::<UiconfCommand>();
+        app.init_resource::<formatter::UiconfFormatters>();
+        app.init_resource::<UiconfDiagnostics>();
+        app.init_resource::<UiconfImages>();
+        app.init_resource::<UiconfDock>();
+        app.init_resource::<UiconfGroups>();
+        app.init_resource::<UiconfScale>();
+        app.init_resource::<UiconfThemes>();
+        app.init_resource::<UiconfPointerState>();
+        if !app.world.contains_resource::<UiconfSafeArea>() {
+            app.insert_resource(UiconfSafeArea::new(self.safe_area));
+        }
+        app.insert_resource(UiconfStates::new(app.world.get_resource::<AppTypeRegistry>().cloned().unwrap_or_default()));
+        app.add_systems(PreUpdate, (
+            sync_to_egui::<UiconfLocalizer>,
+            sync_to_egui::<UiconfFormatters>,
+            sync_to_egui::<UiconfAudio>,
+            sync_to_egui::<UiconfDiagnostics>,
+            diagnostics::clear_reloaded_diagnostics,
+            sync_to_egui::<UiconfImages>,
+            sync_to_egui::<UiconfDock>,
+            sync_to_egui::<UiconfGroups>,
+            sync_to_egui::<UiconfStates>,
+            sync_to_egui::<UiconfHistory>,
+            sync_to_egui::<UiconfSafeArea>,
+            sync_to_egui::<UiconfScale>,
+            sync_to_egui::<UiconfThemes>,
+        ));
+        app.add_systems(PreUpdate, navigation::navigate.after(bevy_egui::EguiSet::BeginFrame));
+        #[cfg(feature = "egui_dock")]
+        app.add_systems(PreUpdate, dock::show_dock_area.after(bevy_egui::EguiSet::BeginFrame).after(sync_to_egui::<UiconfDock>));
+        app.add_systems(PostUpdate, (audio::play_queued_sounds, images::load_requested_images));
+        app.add_systems(PostUpdate, pointer::update_pointer_state.before(bevy_egui::EguiSet::ProcessOutput));
+
+        if let Some(persistence) = &self.persistence {
+            app.insert_resource(persistence::UiconfLayouts::load(persistence.clone()));
+            #[cfg(feature = "egui_dock")]
+            if let Some(text) = persistence::read_file(&persistence.dock_path()) {
+                app.world.resource::<UiconfDock>().restore_tabs(&text);
+            }
+            app.add_systems(PreUpdate, sync_to_egui::<UiconfLayouts>);
+            app.add_systems(Last, persistence::save_layouts);
+        }
+
+        if self.show_load_errors {
+            app.add_systems(Update, show_load_errors);
+        }
     }
 }
 
@@ -28,25 +159,259 @@ pub use loader::EguiAsset as UiconfWindow;
 pub use bevy_egui::egui;
 pub use bevy_egui::EguiContexts;
 
+// Embeds .gui file into the binary, path is relative to the crate root, and the crate is rebuilt
+// whenever the file changes. Only syntax is checked at compile time. Widgets are read on first use,
+// because custom widgets and data models are only known at runtime, and the program panics
+// if they're invalid. Check the file in tests with `validate_file` to catch that before running:
+//
+//     let window: &'static UiconfWindow = include_uiconf!("assets/gui/menu.gui");
+//     window.show(&mut data, egui_contexts.ctx_mut());
+//
+pub use bevy_uiconf_egui_derive::include_uiconf;
+
+// Plain `asset_server.load` works as well, these are kept for convenience.
+// Loading the same path again returns the same handle, and settings of the first load are used.
 pub trait AssetServerExt {
     fn load_uiconf<'a>(&self, path: impl Into<AssetPath<'a>>) -> Handle<EguiAsset>;
+    fn load_uiconf_with_schema<'a, T: TypePath>(&self, path: impl Into<AssetPath<'a>>) -> Handle<EguiAsset>;
 }
 
 impl AssetServerExt for AssetServer {
     fn load_uiconf<'a>(&self, path: impl Into<AssetPath<'a>>) -> Handle<EguiAsset> {
-        let counter = AtomicU32::new(1);
-        self.load_with_settings(path, move |settings: &mut EguiAssetLoaderSettings| {
-            settings.version = counter.fetch_add(1, Ordering::Relaxed);
+        self.load(path)
+    }
+
+    // same as `load_uiconf`, but validates all bindings against data model `T`
+    fn load_uiconf_with_schema<'a, T: TypePath>(&self, path: impl Into<AssetPath<'a>>) -> Handle<EguiAsset> {
+        self.load_with_settings(path, |settings: &mut EguiAssetLoaderSettings| {
+            settings.schema = Some(T::type_path().to_owned());
         })
     }
 }
 
+pub trait AppExt {
+    // registers data model type and types of its fields, so it can be used as schema
+    fn register_uiconf_model<T: UiconfModel>(&mut self) -> &mut Self;
+    // renders windows of all entities with `UiconfWindowSpawner` and data model `D`
+    fn add_uiconf_windows<D: Component + Reflect>(&mut self) -> &mut Self;
+    // renders a window above every entity with `UiconfNameplate` and data model `D`
+    fn add_uiconf_nameplates<D: Component + Reflect>(&mut self) -> &mut Self;
+    // makes `tag = { ... }` available in all .gui files loaded afterwards
+    fn register_uiconf_widget<W: CustomWidget + ReadUiconf>(&mut self, tag: impl Into<String>) -> &mut Self;
+    // makes `name = ...` available on built-in `widget` (`button` or `label`) in all .gui files loaded afterwards
+    fn register_uiconf_property<P: CustomProperty + ReadUiconf>(&mut self, widget: impl Into<String>, name: impl Into<String>) -> &mut Self;
+}
+
+impl AppExt for App {
+    fn register_uiconf_model<T: UiconfModel>(&mut self) -> &mut Self {
+        T::register_types(&mut self.world.resource::<AppTypeRegistry>().write());
+        self
+    }
+
+    fn add_uiconf_windows<D: Component + Reflect>(&mut self) -> &mut Self {
+        self.add_systems(Update, show_uiconf_windows::<D>.in_set(UiconfSystems::ShowWindows))
+    }
+
+    fn add_uiconf_nameplates<D: Component + Reflect>(&mut self) -> &mut Self {
+        self.add_systems(Update, show_uiconf_nameplates::<D>.in_set(UiconfSystems::ShowWindows))
+    }
+
+    fn register_uiconf_widget<W: CustomWidget + ReadUiconf>(&mut self, tag: impl Into<String>) -> &mut Self {
+        self.world.get_resource_or_insert_with(UiconfWidgetRegistry::default).register::<W>(tag);
+        self
+    }
+
+    fn register_uiconf_property<P: CustomProperty + ReadUiconf>(&mut self, widget: impl Into<String>, name: impl Into<String>) -> &mut Self {
+        self.world.get_resource_or_insert_with(UiconfWidgetRegistry::default).register_property::<P>(widget, name);
+        self
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UiconfSystems {
+    // all systems added with `add_uiconf_windows`
+    ShowWindows,
+}
+
+// Window shown automatically, data model is a component on the same entity:
+//
+//     commands.spawn((UiconfWindowSpawner::new(asset_server.load_uiconf("gui/menu.gui")), MenuModel::default()));
+//     app.add_uiconf_windows::<MenuModel>();
+//
+#[derive(Component, Debug, Clone)]
+pub struct UiconfWindowSpawner {
+    pub handle: Handle<UiconfWindow>,
+}
+
+impl UiconfWindowSpawner {
+    pub fn new(handle: Handle<UiconfWindow>) -> Self {
+        Self { handle }
+    }
+}
+
+// Secondary bevy window that uiconf windows with `viewport = tools` are shown on:
+//
+//     commands.spawn((Window { title: "Tools".into(), ..default() }, UiconfViewport::new("tools")));
+//
+// Windows are not shown while there is no viewport with their name, e.g. after it's closed.
+#[derive(Component, Debug, Clone)]
+pub struct UiconfViewport(pub String);
+
+impl UiconfViewport {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
+
+pub fn show_uiconf_windows<D: Component + Reflect>(
+    assets: Res<Assets<UiconfWindow>>,
+    mut windows: Query<(Entity, &UiconfWindowSpawner, &mut D)>,
+    viewports: Query<(Entity, &UiconfViewport)>,
+    mut egui_contexts: bevy_egui::EguiContexts,
+    mut commands: EventWriter<UiconfCommand>,
+) {
+    for (entity, spawner, mut data) in windows.iter_mut() {
+        let Some(window) = assets.get(&spawner.handle) else { continue; };
+
+        let ctx = match window.viewport() {
+            Some(name) => {
+                let Some((viewport, _)) = viewports.iter().find(|(_, viewport)| viewport.0 == name) else { continue; };
+                let Some(ctx) = egui_contexts.try_ctx_for_window_mut(viewport) else { continue; };
+                ctx
+            }
+            None => egui_contexts.ctx_mut(),
+        };
+
+        window.show(data.as_reflect_mut(), ctx);
+        commands.send_batch(UiconfCommand::drain(ctx, entity));
+    }
+}
+
+// Window shown above an entity, e.g. health bar of a unit, data model is a component on the same entity:
+//
+//     commands.spawn((unit, UnitStats::default(), UiconfNameplate::new(nameplate.clone(), Vec3::Y * 2.)));
+//     app.add_uiconf_nameplates::<UnitStats>();
+//
+// All entities share the same parsed asset, and each one gets a window of its own.
+// Windows follow the camera marked with `UiconfCamera`, or the first active camera if there is none.
+#[derive(Component, Debug, Clone)]
+pub struct UiconfNameplate {
+    pub handle: Handle<UiconfWindow>,
+    // added to entity translation, so that the window isn't drawn over the entity itself
+    pub offset: Vec3,
+}
+
+impl UiconfNameplate {
+    pub fn new(handle: Handle<UiconfWindow>, offset: Vec3) -> Self {
+        Self { handle, offset }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct UiconfCamera;
+
+pub fn show_uiconf_nameplates<D: Component + Reflect>(
+    assets: Res<Assets<UiconfWindow>>,
+    mut nameplates: Query<(Entity, &UiconfNameplate, &GlobalTransform, &mut D)>,
+    cameras: Query<(&Camera, &GlobalTransform, Has<UiconfCamera>)>,
+    mut egui_contexts: bevy_egui::EguiContexts,
+    mut commands: EventWriter<UiconfCommand>,
+) {
+    let camera = cameras.iter().find(|(camera, _, marked)| camera.is_active && *marked)
+        .or_else(|| cameras.iter().find(|(camera, _, _)| camera.is_active));
+    let Some((camera, camera_transform, _)) = camera else { return; };
+    let ctx = egui_contexts.ctx_mut();
+
+    for (entity, nameplate, transform, mut data) in nameplates.iter_mut() {
+        let Some(window) = assets.get(&nameplate.handle) else { continue; };
+        let world_pos = transform.translation() + nameplate.offset;
+        window.show_instance_at_world(entity, data.as_reflect_mut(), ctx, world_pos, camera, camera_transform);
+        commands.send_batch(UiconfCommand::drain(ctx, entity));
+    }
+}
+
+// Sent by `clicked = emit(QuitGame)` and other response events, for windows shown with `add_uiconf_windows`:
+//
+//     fn handle_commands(mut commands: EventReader<UiconfCommand>) {
+//         for command in commands.read() {
+//             if command.name == "QuitGame" { ... }
+//         }
+//     }
+//
+// Windows shown manually should call `UiconfCommand::drain` after `show` to collect them.
+#[derive(Event, Debug, Clone)]
+pub struct UiconfCommand {
+    pub name: String,
+    // entity with `UiconfWindowSpawner` or `UiconfNameplate`
+    pub window: Entity,
+    // path of the widget in config file, e.g. `window.layout.button`
+    pub widget: String,
+}
+
+impl UiconfCommand {
+    fn egui_id() -> egui::Id {
+        egui::Id::new("bevy_uiconf_egui::commands")
+    }
+
+    // widgets only have access to egui context, so commands are queued there until the window is shown
+    pub(crate) fn queue(ctx: &egui::Context, name: &str, widget: &str) {
+        ctx.data_mut(|d| {
+            d.get_temp_mut_or_default::<Vec<(String, String)>>(Self::egui_id()).push((name.to_owned(), widget.to_owned()));
+        });
+    }
+
+    // returns all commands emitted since the last call, attributed to `window`
+    pub fn drain(ctx: &egui::Context, window: Entity) -> Vec<Self> {
+        let queued = ctx.data_mut(|d| std::mem::take(d.get_temp_mut_or_default::<Vec<(String, String)>>(Self::egui_id())));
+        queued.into_iter().map(|(name, widget)| Self { name, window, widget }).collect()
+    }
+}
+
+// Window ids depend on the contents of the file, so reloaded windows get their state reset
+// automatically. This system cleans up state left behind by the previous version of the asset.
+// With `UiconfPlugin::keep_window_state` window ids don't change on reload, and only state of
+// widgets that moved or were removed is cleaned up, the next time the window is shown.
 pub fn clear_egui_state_on_reload(
     mut events: EventReader<AssetEvent<EguiAsset>>,
+    assets: Res<Assets<EguiAsset>>,
     mut egui_contexts: bevy_egui::EguiContexts,
+    mut known_ids: Local<HashMap<AssetId<EguiAsset>, egui::Id>>,
 ) {
-    if !events.is_empty() {
-        egui_contexts.ctx_mut().memory_mut(|mem| *mem = Default::default());
+    for event in events.read() {
+        let old_window_id = match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                let Some(asset) = assets.get(*id) else { continue; };
+                let window_id = asset.window_id();
+                let old_window_id = known_ids.insert(*id, window_id);
+                if matches!(event, AssetEvent::Modified { .. }) && old_window_id == Some(window_id) {
+                    WidgetState::reloaded(egui_contexts.ctx_mut(), window_id);
+                }
+                old_window_id.filter(|old_id| *old_id != window_id)
+            }
+            AssetEvent::Removed { id } => {
+                known_ids.remove(id)
+            }
+            _ => None,
+        };
+
+        if let Some(old_window_id) = old_window_id {
+            EguiAsset::clear_memory(old_window_id, egui_contexts.ctx_mut());
+        }
+    }
+}
+
+// Asset server keeps the previous version of an asset if its reload fails, so windows don't vanish
+// while the file is being edited. This system shows what went wrong next to them.
+pub fn show_load_errors(errors: Res<UiconfLoadErrors>, mut egui_contexts: bevy_egui::EguiContexts) {
+    let ctx = egui_contexts.ctx_mut();
+
+    for (path, error) in errors.all() {
+        egui::Window::new(format!("failed to load {}", path))
+            .id(egui::Id::new(("bevy_uiconf_egui::load_error", &path)))
+            .default_width(400.)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.colored_label(ui.visuals().error_fg_color, egui::RichText::new(error).monospace());
+            });
     }
-    events.clear();
 }
@@ -1,21 +1,379 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
 use bevy::asset::{AssetLoader, AsyncReadExt};
 use bevy::prelude::*;
 
 use crate::egui;
+use crate::reader::data_model::{DataSource, ReflectSource};
+use crate::reader::error::{Error, Location};
+use crate::reader::format::Format;
+use crate::reader::reader::LoadingMode;
+use crate::reader::schema::Schema;
+use crate::response::{LabelToId, WidgetResponse, WidgetState};
+use crate::registry::UiconfWidgetRegistry;
+use crate::shared::EguiShared;
+use crate::validate::read_validated;
 
 #[derive(Asset, TypePath, Debug)]
 pub struct EguiAsset {
     pub window: crate::model::Window,
+    pub fonts: Vec<LoadedFont>,
+    pub text_styles: Vec<(egui::TextStyle, egui::FontId)>,
+    // hash of asset path and its contents, used as a base for all egui ids,
+    // so that changing the file resets egui state of its windows only
+    // (contents aren't hashed if `UiconfPlugin::keep_window_state` is set)
+    pub hash: egui::Id,
+    // sounds and other assets referenced by widgets, kept loaded while the window exists
+    pub dependencies: Vec<Handle<bevy::asset::LoadedUntypedAsset>>,
+    // asset path, or file name for embedded windows, used to attribute `UiconfDiagnostics`
+    pub path: String,
 }
 
 impl EguiAsset {
     pub fn show(&self, data: &mut dyn Reflect, ctx: &mut egui::Context) {
-        self.window.show(data, ctx);
+        self.show_with_context(&mut ReflectSource(data), ctx);
+    }
+
+    // same as `show`, but bindings are resolved against several data models, see `UiconfContext`
+    pub fn show_with_context(&self, data: &mut dyn DataSource, ctx: &mut egui::Context) {
+        self.show_at(data, ctx, self.hash, None);
+    }
+
+    fn show_at(&self, data: &mut dyn DataSource, ctx: &mut egui::Context, hash: egui::Id, pos: Option<egui::Pos2>) {
+        if !self.install_fonts(ctx) {
+            // fonts are applied at the start of the next frame,
+            // and egui panics if text uses a font family it doesn't know about
+            return;
+        }
+        self.install_text_styles(ctx);
+
+        let diagnostics = crate::UiconfDiagnostics::get(ctx);
+        crate::diagnostics::scope(diagnostics, &self.path, || self.window.show_at(hash, &self.path, data, ctx, pos));
+    }
+
+    // Shows the window at a point in the world, e.g. `Transform` of an entity for nameplates and prompts,
+    // use `pivot = center_bottom` in the file to place it above the point. Not shown if the point is
+    // behind the camera. Should be called every frame, so the window follows the camera.
+    pub fn show_at_world(
+        &self,
+        data: &mut dyn Reflect,
+        ctx: &mut egui::Context,
+        world_pos: Vec3,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) {
+        self.show_at_world_with_hash(self.hash, data, ctx, world_pos, camera, camera_transform);
+    }
+
+    // Same as `show_at_world`, but window id is combined with `instance`, so that the same asset
+    // can be shown many times at once, e.g. with an entity for every nameplate.
+    pub fn show_instance_at_world(
+        &self,
+        instance: impl std::hash::Hash,
+        data: &mut dyn Reflect,
+        ctx: &mut egui::Context,
+        world_pos: Vec3,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) {
+        self.show_at_world_with_hash(self.hash.with(instance), data, ctx, world_pos, camera, camera_transform);
+    }
+
+    fn show_at_world_with_hash(
+        &self,
+        hash: egui::Id,
+        data: &mut dyn Reflect,
+        ctx: &mut egui::Context,
+        world_pos: Vec3,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) {
+        let Some(pos) = world_to_egui(ctx, world_pos, camera, camera_transform) else {
+            // responses from the last time window was shown are forgotten
+            WidgetResponse::store(ctx, self.window.id(hash));
+            return;
+        };
+        self.show_at(&mut ReflectSource(data), ctx, hash, Some(pos));
+    }
+
+    // Reads a file without asset server, see `include_uiconf!` and `uiconf_test::Harness`. Fonts can't be used,
+    // because they are loaded through asset server.
+    pub fn from_embedded(data: &[u8], file: &str) -> Result<Self, Vec<Error>> {
+        let root = read_validated(data, file, Format::from_path(std::path::Path::new(file)), LoadingMode::Strict, None, &UiconfWidgetRegistry::default())?;
+
+        if !root.fonts.0.is_empty() {
+            return Err(vec![Error::Custom {
+                message: "fonts can only be used in files loaded by asset server".to_owned(),
+                at: Location::new("fonts".to_owned(), file.to_owned(), data, None),
+            }]);
+        }
+
+        Ok(EguiAsset {
+            window: root.window,
+            fonts: vec![],
+            text_styles: root.text_styles.0.into_iter()
+                .map(|style| (egui::TextStyle::Name(style.name.into()), style.font))
+                .collect(),
+            hash: egui::Id::new(("embedded", file)),
+            dependencies: vec![],
+            path: file.to_owned(),
+        })
+    }
+
+    // `viewport = tools` in the file, see `UiconfViewport`
+    pub fn viewport(&self) -> Option<&str> {
+        self.window.viewport()
+    }
+
+    pub fn window_id(&self) -> egui::Id {
+        self.window.id(self.hash)
     }
+
+    // id of the window shown with `show_instance_at_world`
+    pub fn instance_window_id(&self, instance: impl std::hash::Hash) -> egui::Id {
+        self.window.id(self.hash.with(instance))
+    }
+
+    // response of a widget with `id = start_button` in the last frame, `None` if it wasn't shown
+    pub fn response(&self, ctx: &egui::Context, label: impl LabelToId) -> Option<WidgetResponse> {
+        WidgetResponse::get(ctx, self.window_id(), &label.to_id())
+    }
+
+    // screen rect of a widget with explicit `id`, e.g. for tutorials pointing at a button:
+    //
+    //     if let Some(rect) = window.widget_rect(ctx, MenuLabel::StartButton) {
+    //         ctx.layer_painter(egui::LayerId::debug()).rect_stroke(rect.expand(4.), 4., (2., egui::Color32::YELLOW));
+    //     }
+    //
+    // Only the visible part is returned, `None` if widget wasn't shown in the last frame or is scrolled out of view.
+    pub fn widget_rect(&self, ctx: &egui::Context, label: impl LabelToId) -> Option<egui::Rect> {
+        self.response(ctx, label)?.visible_rect()
+    }
+
+    // Clean up whatever egui lets us remove for the old window id. Size (`resize::State` under
+    // `id.with("resize")`) stays behind, because its type is private in egui 0.24 and state
+    // can only be removed by type. It's a few bytes and never shown again.
+    pub fn clear_memory(window_id: egui::Id, ctx: &egui::Context) {
+        if let Some(state) = egui::collapsing_header::CollapsingState::load(ctx, window_id.with("collapsing")) {
+            state.remove(ctx);
+        }
+        WidgetState::clear(ctx, window_id);
+        Self::remove_area(window_id, ctx);
+    }
+
+    // egui 0.24 has no API to remove a single area, so all of them are round-tripped through serde
+    // without the ones of the window
+    fn remove_area(window_id: egui::Id, ctx: &egui::Context) {
+        fn without(value: serde_value::Value, id: &serde_value::Value) -> serde_value::Value {
+            use serde_value::Value;
+            // `LayerId` of the window, in draw order and visibility sets
+            let is_layer = |value: &Value| matches!(value, Value::Map(layer) if layer.get(&Value::String("id".to_owned())) == Some(id));
+            match value {
+                Value::Map(map) => Value::Map(map.into_iter()
+                    .filter(|(key, _)| key != id)
+                    .map(|(key, value)| (key, without(value, id)))
+                    .collect()),
+                Value::Seq(seq) => Value::Seq(seq.into_iter()
+                    .filter(|item| !is_layer(item))
+                    .map(|item| without(item, id))
+                    .collect()),
+                value => value,
+            }
+        }
+
+        let Ok(id) = serde_value::to_value(window_id) else { return; };
+        ctx.memory_mut(|mem| {
+            let areas = mem.areas_mut();
+            let Ok(value) = serde_value::to_value(&*areas) else { return; };
+            if let Ok(value) = without(value, &id).deserialize_into() {
+                *areas = value;
+            }
+        });
+    }
+
+    // styles are only replaced if changed, so hot-reloaded assets can update them
+    fn install_text_styles(&self, ctx: &egui::Context) {
+        let style = ctx.style();
+        if self.text_styles.iter().all(|(name, font)| style.text_styles.get(name) == Some(font)) { return; }
+
+        ctx.style_mut(|style| {
+            for (name, font) in self.text_styles.iter() {
+                style.text_styles.insert(name.clone(), font.clone());
+            }
+        });
+    }
+
+    // returns `true` if all fonts declared in this asset are already available
+    fn install_fonts(&self, ctx: &egui::Context) -> bool {
+        if self.fonts.is_empty() { return true; }
+
+        // keep track of everything we've installed, because egui doesn't let us read font definitions back
+        let id = egui::Id::new("bevy_uiconf_egui::fonts");
+        let mut definitions = ctx.data(|d| d.get_temp::<egui::FontDefinitions>(id)).unwrap_or_default();
+        let mut changed = false;
+
+        for (idx, font) in self.fonts.iter().enumerate() {
+            let fallback = definitions.families.get(&egui::FontFamily::Proportional).cloned().unwrap_or_default();
+            let family = definitions.families.entry(font.family.clone()).or_insert(fallback);
+
+            if !family.contains(&font.name) {
+                // fonts declared first take precedence, built-in fonts are used as a fallback
+                let position = self.fonts[..idx].iter().filter(|f| f.family == font.family).count();
+                family.insert(position.min(family.len()), font.name.clone());
+                definitions.font_data.insert(font.name.clone(), font.data.clone());
+                changed = true;
+            }
+        }
+
+        if changed {
+            ctx.set_fonts(definitions.clone());
+            ctx.data_mut(|d| d.insert_temp(id, definitions));
+            ctx.request_repaint();
+        }
+
+        !changed
+    }
+}
+
+// egui works in points of its own, which may be scaled differently than bevy logical pixels
+fn world_to_egui(ctx: &egui::Context, world_pos: Vec3, camera: &Camera, camera_transform: &GlobalTransform) -> Option<egui::Pos2> {
+    let ndc = camera.world_to_ndc(camera_transform, world_pos)?;
+    if !(0. ..=1.).contains(&ndc.z) { return None; }
+
+    let viewport = camera.physical_viewport_rect()?;
+    let size = (viewport.max - viewport.min).as_vec2();
+    let physical = viewport.min.as_vec2() + Vec2::new(ndc.x + 1., 1. - ndc.y) / 2. * size;
+    Some(egui::pos2(physical.x, physical.y) / ctx.pixels_per_point())
+}
+
+#[derive(Debug)]
+pub struct LoadedFont {
+    pub family: egui::FontFamily,
+    // unique name of this font, includes content hash so hot-reloaded fonts get re-installed
+    pub name: String,
+    pub data: egui::FontData,
 }
 
 #[derive(Default)]
-pub struct EguiAssetLoader;
+pub struct EguiAssetLoader {
+    // used for assets that don't specify loading mode in their settings
+    pub mode: LoadingMode,
+    // type path of the data model used for assets that don't specify schema in their settings
+    pub schema: Option<&'static str>,
+    // see `UiconfPlugin::keep_window_state`
+    pub keep_window_state: bool,
+    pub type_registry: AppTypeRegistry,
+    pub widgets: UiconfWidgetRegistry,
+    pub errors: UiconfLoadErrors,
+}
+
+// Last error of every asset that failed to load, removed once the asset loads successfully.
+// Shared with asset loader, so it's always up to date.
+#[derive(Resource, Clone, Default)]
+pub struct UiconfLoadErrors(Arc<Mutex<HashMap<String, String>>>);
+
+impl UiconfLoadErrors {
+    // keyed by asset path, same as `EguiAsset::path`
+    pub fn get(&self, path: &str) -> Option<String> {
+        self.0.lock().unwrap().get(path).cloned()
+    }
+
+    // sorted by asset path
+    pub fn all(&self) -> Vec<(String, String)> {
+        let mut errors = self.0.lock().unwrap().iter()
+            .map(|(path, error)| (path.clone(), error.clone()))
+            .collect::<Vec<_>>();
+        errors.sort();
+        errors
+    }
+
+    fn record(&self, path: &str, error: Option<&anyhow::Error>) {
+        let mut errors = self.0.lock().unwrap();
+        match error {
+            Some(error) => { errors.insert(path.to_owned(), error.to_string()); }
+            None => { errors.remove(path); }
+        }
+    }
+}
+
+impl EguiAssetLoader {
+    async fn load_asset(
+        &self,
+        reader: &mut bevy::asset::io::Reader<'_>,
+        settings: &EguiAssetLoaderSettings,
+        load_context: &mut bevy::asset::LoadContext<'_>,
+    ) -> anyhow::Result<EguiAsset> {
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer).await?;
+
+        let root = {
+            let registry = self.type_registry.read();
+            let type_id = |type_path: &str| match registry.get_with_type_path(type_path) {
+                Some(registration) => Ok(registration.type_id()),
+                None => Err(anyhow::anyhow!("data model type `{}` is not registered", type_path)),
+            };
+            let namespaces = settings.context.iter()
+                .map(|(namespace, type_path)| Ok((namespace.as_str(), type_id(type_path)?)))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let schema = if !namespaces.is_empty() {
+                Some((&*registry, Schema::Context(&namespaces)))
+            } else if let Some(schema) = settings.schema.as_deref().or(self.schema) {
+                Some((&*registry, Schema::Model(type_id(schema)?)))
+            } else {
+                None
+            };
+
+            read_validated(
+                &buffer,
+                &load_context.path().to_string_lossy(),
+                Format::from_path(load_context.path()),
+                settings.mode.unwrap_or(self.mode),
+                schema,
+                &self.widgets,
+            ).map_err(|errors| {
+                let errors = errors.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+                anyhow::anyhow!("{}", errors.join("\n\n"))
+            })?
+        };
+
+        // fonts are read directly, which registers them as loader dependencies,
+        // so changing a font file reloads all windows using it
+        let mut fonts = vec![];
+        for family in root.fonts.0.iter() {
+            for path in family.paths.iter() {
+                let bytes = load_context.read_asset_bytes(path.clone()).await?;
+                fonts.push(LoadedFont {
+                    family: family.family.clone(),
+                    name: format!("{}#{:016x}", path, egui::util::hash(&bytes)),
+                    data: egui::FontData::from_owned(bytes),
+                });
+            }
+        }
+
+        // widgets are identified by their position in the file, so with stable window id
+        // only the state of widgets that were moved around is lost after reload
+        let hash = if self.keep_window_state {
+            egui::Id::new(load_context.asset_path())
+        } else {
+            egui::Id::new((load_context.asset_path(), egui::util::hash(&buffer)))
+        };
+
+        // asset isn't considered fully loaded until these are, so sounds are ready on first click
+        let dependencies = root.assets.into_iter().map(|path| load_context.load_untyped(path)).collect();
+
+        Ok(EguiAsset {
+            window: root.window,
+            fonts,
+            text_styles: root.text_styles.0.into_iter()
+                .map(|style| (egui::TextStyle::Name(style.name.into()), style.font))
+                .collect(),
+            hash,
+            dependencies,
+            path: load_context.path().to_string_lossy().into_owned(),
+        })
+    }
+}
 
 impl AssetLoader for EguiAssetLoader {
     type Asset = EguiAsset;
@@ -26,32 +384,51 @@ impl AssetLoader for EguiAssetLoader {
         &'a self,
         reader: &'a mut bevy::asset::io::Reader,
         settings: &'a Self::Settings,
-        _load_context: &'a mut bevy::asset::LoadContext,
+        load_context: &'a mut bevy::asset::LoadContext,
     ) -> bevy::utils::BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
         Box::pin(async move {
-            if settings.version == 0 {
-                return Err(anyhow::anyhow!("
-Please use `asset_server.load_uiconf` instead of `asset_server.load`.
-
-Add `use bevy_uiconf_egui::AssetServerExt;` to access it."));
-            }
-
-            let mut buffer = Vec::new();
-            reader.read_to_end(&mut buffer).await?;
-
-            Ok(EguiAsset {
-                window: crate::model::Root::read(&buffer)?,
-                //hash: egui::Id::new((load_context.asset_path(), /*settings.version*/)),
-            })
+            let path = load_context.path().to_string_lossy().into_owned();
+            let result = self.load_asset(reader, settings, load_context).await;
+            self.errors.record(&path, result.as_ref().err());
+            result
         })
     }
 
     fn extensions(&self) -> &[&str] {
-        &["gui"]
+        Format::EXTENSIONS
     }
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Default, Debug)]
 pub struct EguiAssetLoaderSettings {
-    pub version: u32,
+    // overrides loading mode set in `UiconfPlugin`
+    pub mode: Option<LoadingMode>,
+    // type path of the data model to validate bindings against, overrides schema set in `UiconfPlugin`
+    pub schema: Option<String>,
+    // for windows shown with `UiconfContext`: namespaces and type paths of their data models,
+    // bindings are validated against them instead of `schema`
+    pub context: Vec<(String, String)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EguiAsset;
+    use crate::egui;
+
+    #[test]
+    fn clear_memory_keeps_other_windows() {
+        let ctx = egui::Context::default();
+        let (reloaded, other) = (egui::Id::new("reloaded"), egui::Id::new("other"));
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            for id in [reloaded, other] {
+                egui::Window::new("window").id(id).show(ctx, |ui| ui.label("window"));
+            }
+        });
+        let other_rect = ctx.memory(|mem| mem.area_rect(other));
+        assert!(other_rect.is_some());
+
+        EguiAsset::clear_memory(reloaded, &ctx);
+        assert_eq!(ctx.memory(|mem| mem.area_rect(reloaded)), None);
+        assert_eq!(ctx.memory(|mem| mem.area_rect(other)), other_rect);
+    }
 }
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy::reflect::Reflect;
+
+use crate::reader::data_model::reflect_as_f64;
+use crate::shared::EguiShared;
+
+// Argument passed to localizer, taken from data model field bound in `args = { ... }`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LocArg<'a> {
+    String(&'a str),
+    Number(f64),
+}
+
+impl<'a> LocArg<'a> {
+    // strings and numbers are passed as is, enums are passed as their variant name
+    pub fn from_reflect(field: &'a dyn Reflect) -> Option<Self> {
+        if let Some(value) = reflect_as_f64(field) {
+            Some(Self::Number(value))
+        } else if let Some(value) = field.downcast_ref::<String>() {
+            Some(Self::String(value))
+        } else if let bevy::reflect::ReflectRef::Enum(value) = field.reflect_ref() {
+            Some(Self::String(value.variant_name()))
+        } else {
+            None
+        }
+    }
+}
+
+// Translates `loc(key)` texts, returns `None` if key is unknown (key itself is shown then).
+pub trait Localize: Send + Sync + 'static {
+    fn localize(&self, key: &str, args: &[(&str, LocArg)]) -> Option<String>;
+}
+
+// Localizer used by all windows, insert it as a resource:
+//
+//     app.insert_resource(UiconfLocalizer::new(FluentLocalizer::new(bundle)));
+//
+#[derive(Resource, Clone)]
+pub struct UiconfLocalizer(pub Arc<dyn Localize>);
+
+impl UiconfLocalizer {
+    pub fn new(localizer: impl Localize) -> Self {
+        Self(Arc::new(localizer))
+    }
+}
+
+impl EguiShared for UiconfLocalizer {
+    const ID: &'static str = "bevy_uiconf_egui::localizer";
+}
+
+// Default localizer based on fluent, resources are added to the bundle by user:
+//
+//     let mut bundle = fluent::concurrent::FluentBundle::new_concurrent(vec![langid!("en-US")]);
+//     bundle.add_resource(FluentResource::try_new(ftl_string).unwrap()).unwrap();
+//
+#[cfg(feature = "fluent")]
+pub struct FluentLocalizer(fluent::concurrent::FluentBundle<fluent::FluentResource>);
+
+#[cfg(feature = "fluent")]
+impl FluentLocalizer {
+    pub fn new(mut bundle: fluent::concurrent::FluentBundle<fluent::FluentResource>) -> Self {
+        // unicode isolation marks are rendered by egui as unknown characters
+        bundle.set_use_isolating(false);
+        Self(bundle)
+    }
+}
+
+#[cfg(feature = "fluent")]
+impl Localize for FluentLocalizer {
+    fn localize(&self, key: &str, args: &[(&str, LocArg)]) -> Option<String> {
+        let pattern = self.0.get_message(key)?.value()?;
+
+        let mut fluent_args = fluent::FluentArgs::new();
+        for (name, value) in args.iter() {
+            match *value {
+                LocArg::String(value) => fluent_args.set(*name, value),
+                LocArg::Number(value) => fluent_args.set(*name, value),
+            }
+        }
+
+        // errors are rendered inline by fluent, e.g. missing arguments are shown as `{$name}`
+        let mut errors = vec![];
+        Some(self.0.format_pattern(pattern, Some(&fluent_args), &mut errors).into_owned())
+    }
+}
@@ -1,16 +1,40 @@
+use std::borrow::Cow;
 use std::fmt::Debug;
+use std::rc::Rc;
 use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 use std::vec;
 
 use bevy::reflect::Reflect;
 use jomini::{TextTape, TextToken};
 use strum::{Display, EnumString, EnumVariantNames, VariantNames};
 
-use crate::reader::binding::{Binding, BindingRef};
-use crate::reader::data_model::{ResolveBinding, ResolveBindingRef, Trigger};
-use crate::reader::error::Error;
-use crate::reader::reader::Reader;
+use crate::audio::UiconfAudio;
+use crate::dock::{DockedWindow, UiconfDock};
+use crate::formatter::UiconfFormatters;
+use crate::groups::UiconfGroups;
+use crate::history::UiconfHistory;
+use crate::images::UiconfImages;
+use crate::localize::{LocArg, UiconfLocalizer};
+use crate::persistence::{UiconfLayouts, WindowLayout};
+use crate::pointer::{FocusedTextInput, ShownAreas};
+use crate::reader::binding::{is_option, Binding, BindingRef, NoneValue};
+use crate::reader::data_model::{reflect_as_f64, reflect_set_f64, DataSource, ReflectSource, ResolveBinding, ResolveBindingRef, Trigger};
+use crate::reader::error::{Error, Location};
+use crate::reader::format::SourceMap;
+use crate::reader::reader::{LoadingMode, ReadContext, Reader, ReaderPath};
+use crate::reader::schema::BindingRecord;
 use crate::reader::ReadUiconf;
+use crate::registry::{CustomProperty, CustomWidget, UiconfWidgetRegistry};
+use crate::response::{WidgetResponse, WidgetState};
+use crate::safe_area::UiconfSafeArea;
+use crate::scale::UiconfScale;
+use crate::shared::EguiShared;
+use crate::state::{apply_staged, StagedCopies, StagingSource, UiconfStates};
+use crate::theme::UiconfThemes;
+use crate::writer::writer::Writer;
+use crate::writer::ToUiconf;
 use crate::{const_concat, egui};
 
 //
@@ -21,50 +45,293 @@ use crate::{const_concat, egui};
 pub struct Root {
     //pub windows: Vec<Window>,
     pub window: Window,
+    pub fonts: Fonts,
+    pub text_styles: TextStyles,
+    pub bindings: Vec<BindingRecord>,
+    // paths of sounds and other assets referenced by widgets
+    pub assets: Vec<String>,
 }
 
 impl Root {
-    const FIELDS: &'static [&'static str] = &["window"];
+    const FIELDS: &'static [&'static str] = &["classes", "fonts", "icons", "palette", "text_styles", "window"];
 
-    pub fn read(data: &[u8]) -> Result<Window, Error> {
-        let tape = TextTape::from_slice(data).unwrap();
+    pub fn read(data: &[u8], file: &str, mode: LoadingMode, widgets: &UiconfWidgetRegistry) -> Result<Root, Error> {
+        Self::read_mapped(data, None, file, mode, widgets)
+    }
+
+    // `data` converted from another format, see `Format::to_gui`
+    pub fn read_mapped<'d>(
+        data: &'d [u8],
+        source_map: Option<&'d SourceMap<'d>>,
+        file: &str,
+        mode: LoadingMode,
+        widgets: &UiconfWidgetRegistry,
+    ) -> Result<Root, Error> {
+        let tape = TextTape::from_slice(data).map_err(|error| {
+            // unexpected end of file is reported at the end
+            let offset = error.offset().or(Some(data.len()));
+            Error::SyntaxError { at: Location::new(String::new(), file.to_owned(), data, offset), error }
+        })?;
         let reader = tape.utf8_reader();
         let mut window = None;
+        let mut fonts = None;
+        let mut text_styles = None;
+
+        // classes, text style and font family names need to be known before anything else is read
+        let mut context = ReadContext::new(data, file, mode, widgets.clone()).with_source_map(source_map);
+        let mut has_classes = false;
+        for (key, _, value) in reader.fields() {
+            let key_str = key.read_str();
+            let value = Reader::new(value, ReaderPath::root().join(key_str.clone(), 0), Rc::new(ReadContext::new(data, file, mode, widgets.clone()).with_source_map(source_map)))
+                .with_key_offset(key.read_scalar());
+            match &*key_str {
+                "classes" => {
+                    if has_classes {
+                        return Err(Error::duplicate_field(&value, "classes"));
+                    }
+                    context.read_classes(&value)?;
+                    has_classes = true;
+                }
+                "text_styles" => context.read_text_style_names(&value)?,
+                "fonts"       => context.read_font_family_names(&value)?,
+                _ => {}
+            }
+        }
+        let context = Rc::new(context);
+
+        // palette and icons are read before everything else, so that classes and widgets can use them
+        let mut has_palette = false;
+        for (key, _, value) in reader.fields() {
+            let value = Reader::new(value, ReaderPath::root().join(key.read_str(), 0), context.clone())
+                .with_key_offset(key.read_scalar());
+            if key.read_str() == "palette" {
+                if has_palette {
+                    return Err(Error::duplicate_field(&value, "palette"));
+                }
+                context.read_palette(&value)?;
+                has_palette = true;
+            } else if key.read_str() == "icons" {
+                context.read_icons(&value)?;
+            }
+        }
 
         for (key, op, value) in reader.fields() {
-            let value = Reader::new(value, vec![(key.read_str().into(), 0)]);
+            let value = Reader::new(value, ReaderPath::root().join(key.read_str(), 0), context.clone())
+                .with_key_offset(key.read_scalar());
             let key = key.read_str();
-            if key == "window" {
-                if let Some(op) = op {
-                    return Err(Error::unexpected_operator(&value, op));
-                }
+            if let Some(op) = op {
+                return Err(Error::unexpected_operator(&value, op));
+            }
+            if key == "classes" || key == "palette" || key == "icons" {
+                // already processed above
+            } else if key == "window" {
                 if window.is_some() {
                     return Err(Error::duplicate_field(&value, "window"));
                 }
                 window = Some(value.read()?);
+            } else if key == "fonts" {
+                if fonts.is_some() {
+                    return Err(Error::duplicate_field(&value, "fonts"));
+                }
+                fonts = Some(value.read()?);
+            } else if key == "text_styles" {
+                // font families are checked after the loop, once all fonts are known
+                if text_styles.is_some() {
+                    return Err(Error::duplicate_field(&value, "text_styles"));
+                }
+                text_styles = Some(value);
             } else {
-                return Err(Error::unknown_field(&value, &key, Root::FIELDS));
+                value.skip_unknown_field(&key, Root::FIELDS)?;
             }
         }
 
+        let fonts: Fonts = fonts.unwrap_or_default();
+        let text_styles = match text_styles {
+            Some(value) => TextStyles::read(&value)?,
+            None => TextStyles::default(),
+        };
+
         if let Some(window) = window {
-            Ok(window)
+            Ok(Root {
+                window,
+                fonts,
+                text_styles,
+                bindings: context.take_bindings(),
+                assets: context.take_assets(),
+            })
         } else {
             let tape = TextTape::from_slice(b"a=b").unwrap();
             let reader = tape.utf8_reader();
-            let dummy_value = Reader::new(reader.fields().next().unwrap().2, vec![]);
+            let dummy_value = Reader::new(reader.fields().next().unwrap().2, ReaderPath::root(), context);
             Err(Error::missing_field(&dummy_value, "window"))
         }
     }
 }
 
+impl ToUiconf for Root {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        if !self.fonts.0.is_empty() {
+            writer.field("fonts", &self.fonts);
+        }
+        if !self.text_styles.0.is_empty() {
+            writer.field("text_styles", &self.text_styles);
+        }
+        writer.field("window", &self.window);
+    }
+}
+
+impl Root {
+    // Writes file back in canonical form, classes are written expanded.
+    pub fn write(&self) -> String {
+        let mut writer = Writer::new();
+        self.write_uiconf(&mut writer);
+        writer.finish()
+    }
+}
+
+//
+// Fonts
+//
+
+#[derive(Debug, Default)]
+pub struct Fonts(pub Vec<FontFamily>);
+
+#[derive(Debug)]
+pub struct FontFamily {
+    pub family: egui::FontFamily,
+    pub paths: Vec<String>,
+}
+
+impl ReadUiconf for Fonts {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut families: Vec<FontFamily> = vec![];
+
+        for (key, value) in value.read_object()? {
+            let family = font_family_from_name(&key);
+            if families.iter().any(|f| f.family == family) {
+                return Err(Error::duplicate_field(&value, &key));
+            }
+
+            // either `name = "path.ttf"` or `name = { "regular.ttf" "fallback.ttf" }`
+            let paths = if value.is_scalar() {
+                vec![value.read_string()?]
+            } else {
+                value.read()?
+            };
+
+            families.push(FontFamily { family, paths });
+        }
+
+        Ok(Fonts(families))
+    }
+}
+
+impl ToUiconf for Fonts {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            for family in self.0.iter() {
+                writer.field(font_family_name(&family.family), &family.paths);
+            }
+        });
+    }
+}
+
+fn font_family_from_name(name: &str) -> egui::FontFamily {
+    match name {
+        "proportional" => egui::FontFamily::Proportional,
+        "monospace"    => egui::FontFamily::Monospace,
+        name           => egui::FontFamily::Name(name.into()),
+    }
+}
+
+// egui panics on unknown font families, so only built-in and declared families are allowed
+fn read_font_family(value: &Reader) -> Result<egui::FontFamily, Error> {
+    let family = font_family_from_name(&value.read_string()?);
+    if let egui::FontFamily::Name(name) = &family {
+        if !value.is_font_family_declared(name) {
+            return Err(Error::custom(value, format!("font family `{}` should be declared in `fonts`", name)));
+        }
+    }
+    Ok(family)
+}
+
+fn font_family_name(family: &egui::FontFamily) -> &str {
+    match family {
+        egui::FontFamily::Proportional => "proportional",
+        egui::FontFamily::Monospace    => "monospace",
+        egui::FontFamily::Name(name)   => name,
+    }
+}
+
+//
+// TextStyles
+//
+
+// `text_styles = { title = { size = 24  family = serif } }`, used as `style = title` in rich text
+#[derive(Debug, Default)]
+pub struct TextStyles(pub Vec<TextStyle>);
+
+#[derive(Debug)]
+pub struct TextStyle {
+    pub name: String,
+    pub font: egui::FontId,
+}
+
+impl TextStyle {
+    const FIELDS: &'static [&'static str] = &["size", "family"];
+}
+
+impl TextStyles {
+    fn read(value: &Reader) -> Result<Self, Error> {
+        let mut styles: Vec<TextStyle> = vec![];
+
+        for (key, value) in value.read_object()? {
+            if styles.iter().any(|style| style.name == *key) {
+                return Err(Error::duplicate_field(&value, &key));
+            }
+
+            let mut size = None;
+            let mut family = egui::FontFamily::Proportional;
+
+            for (field, value) in value.read_object()? {
+                match &*field {
+                    "size" => { size = Some(value.read()?); }
+                    "family" => { family = read_font_family(&value)?; }
+                    str => { value.skip_unknown_field(str, TextStyle::FIELDS)?; }
+                }
+            }
+
+            let size = size.ok_or_else(|| Error::missing_field(&value, "size"))?;
+            styles.push(TextStyle { name: key.to_string(), font: egui::FontId::new(size, family) });
+        }
+
+        Ok(TextStyles(styles))
+    }
+}
+
+impl ToUiconf for TextStyles {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            for style in self.0.iter() {
+                writer.field_with(&style.name, |writer| writer.object(|writer| {
+                    writer.field("size", &style.font.size);
+                    if style.font.family != egui::FontFamily::Proportional {
+                        writer.field_with("family", |w| w.scalar(font_family_name(&style.font.family)));
+                    }
+                }));
+            }
+        });
+    }
+}
+
 //
 // Window
 //
 
 #[derive(Debug)]
 pub struct Window {
-    pub title: RichText,
+    // only optional if title bar is hidden or window has explicit `id`
+    pub title: Option<RichText>,
     pub props: Vec<WindowProperty>,
     pub content: Content,
 }
@@ -76,15 +343,143 @@ impl Window {
         ContentWidget::FIELDS,
     );
 
-    pub fn show(&self, data: &mut dyn Reflect, ctx: &egui::Context) {
-        let title = self.title.resolve(data).ok().unwrap_or_default();
-        let mut window = egui::Window::new(title);
+    // explicit `id` is still combined with asset hash, so that reloading the file resets window state
+    pub fn id(&self, hash: egui::Id) -> egui::Id {
+        match self.explicit_id() {
+            Some(id) => hash.with("window").with(&id.0),
+            None     => hash.with("window"),
+        }
+    }
+
+    // egui ids differ between platforms and change with the file, saved layouts use asset path and explicit `id`
+    fn layout_key(&self, asset: &str) -> String {
+        match self.explicit_id() {
+            Some(id) => format!("{}#{}", asset, id.0),
+            None     => asset.to_owned(),
+        }
+    }
+
+    fn explicit_id(&self) -> Option<&WidgetId> {
+        self.props.iter().find_map(|prop| match prop {
+            WindowProperty::Id(id) => Some(id),
+            _ => None,
+        })
+    }
+
+    pub fn viewport(&self) -> Option<&str> {
+        self.props.iter().find_map(|prop| match prop {
+            WindowProperty::Viewport(viewport) => Some(viewport.as_str()),
+            _ => None,
+        })
+    }
+
+    // same id as used by `egui::Window` internally
+    pub fn collapsing_id(&self, hash: egui::Id) -> egui::Id {
+        self.id(hash).with("collapsing")
+    }
+
+    // only windows with `dock` property are docked, side chosen by user wins over the one in the file
+    fn docked(&self, ctx: &egui::Context) -> Option<DockedWindow> {
+        let declared = self.props.iter().find_map(|prop| match prop {
+            WindowProperty::Dock(dock) => Some(*dock),
+            _ => None,
+        })?;
+        let arranged = self.explicit_id().zip(UiconfDock::get(ctx)).and_then(|(id, dock)| dock.window(&id.0));
+        Some(arranged.unwrap_or(DockedWindow { dock: declared, size: None }))
+    }
+
+    // checked before anything else, so hidden windows cost almost nothing
+    fn is_shown(&self, data: &dyn DataSource) -> bool {
+        self.props.iter().all(|prop| match prop {
+            WindowProperty::ShowWhen(condition) => condition.resolve(data).unwrap_or(true),
+            _ => true,
+        })
+    }
+
+    // `asset` is the path of the file, used to save window layout, see `UiconfPersistence`
+    pub fn show(&self, hash: egui::Id, asset: &str, data: &mut dyn DataSource, ctx: &egui::Context) {
+        self.show_at(hash, asset, data, ctx, None);
+    }
+
+    // `pos` overrides position set in the file, it's where window `pivot` is placed
+    pub fn show_at(&self, hash: egui::Id, asset: &str, data: &mut dyn DataSource, ctx: &egui::Context, pos: Option<egui::Pos2>) {
+        let staging = self.props.iter().find_map(|prop| match prop {
+            WindowProperty::Staging(staging) => Some(staging),
+            _ => None,
+        });
+        // history is recorded inside of staging, so that undo reverts staged changes
+        let show = |data: &mut dyn DataSource| match UiconfHistory::get(ctx) {
+            Some(history) => history.record(self.id(hash), data, ctx, |data| self.show_window(hash, asset, data, ctx, pos)),
+            None          => self.show_window(hash, asset, data, ctx, pos),
+        };
+        match staging {
+            Some(staging) => staging.show(self.id(hash), data, ctx, show),
+            None          => show(data),
+        }
+    }
+
+    fn show_window(&self, hash: egui::Id, asset: &str, data: &mut dyn DataSource, ctx: &egui::Context, pos: Option<egui::Pos2>) {
+        if !self.is_shown(data) {
+            // responses from the last time window was shown are forgotten
+            WidgetResponse::store(ctx, self.id(hash));
+            return;
+        }
+
+        let global_scale = UiconfScale::get(ctx).map_or(1., |scale| scale.scale);
+        let scale = self.props.iter().fold(global_scale, |scale, prop| match prop {
+            WindowProperty::Scale(value) => value.resolve(data).map_or(scale, |value| scale * value),
+            _ => scale,
+        });
+        let theme = self.props.iter().rev().find_map(|prop| match prop {
+            WindowProperty::Theme(theme) => theme.resolve_ref(data).ok().cloned(),
+            _ => None,
+        });
+        // theme replaces the whole style, so it goes first, and scale is applied on top of it
+        UiconfThemes::scoped(ctx, theme.as_deref(), || {
+            UiconfScale::scoped(ctx, scale, || self.show_styled(hash, asset, data, ctx, pos))
+        });
+    }
+
+    fn show_styled(&self, hash: egui::Id, asset: &str, data: &mut dyn DataSource, ctx: &egui::Context, pos: Option<egui::Pos2>) {
+        let docked = self.docked(ctx);
+        if let Some(docked) = docked.filter(|docked| docked.dock != Dock::Float) {
+            #[cfg(feature = "egui_dock")]
+            self.show_tab(hash, asset, data, ctx, docked.dock);
+            #[cfg(not(feature = "egui_dock"))]
+            self.show_docked(hash, data, ctx, docked);
+            return;
+        }
+        #[cfg(feature = "egui_dock")]
+        if let (Some(_), Some(dock)) = (docked, UiconfDock::get(ctx)) {
+            dock.remove_tab(&self.layout_key(asset));
+        }
+
+        let title = self.title.as_ref().map(|title| title.resolve(data, ctx)).unwrap_or_default();
+        let mut window = egui::Window::new(title).id(self.id(hash));
+        let mut frame = None;
+        let mut opacity = 1.;
+
+        // applied first, so that explicit `constrain_to` wins
+        let safe_area = self.props.iter()
+            .any(|prop| matches!(prop, WindowProperty::RespectSafeArea(true)))
+            .then(|| UiconfSafeArea::get(ctx))
+            .flatten();
+        if let Some(safe_area) = &safe_area {
+            window = window.constrain_to(safe_area.rect(ctx));
+        }
 
         for prop in self.props.iter() {
             use WindowProperty as P;
             match prop {
+                P::Id(_) | P::ShowWhen(_) | P::Viewport(_) | P::Dock(_) | P::Staging(_) | P::Fullscreen(_) | P::RespectSafeArea(_) | P::Scale(_) | P::Theme(_) => {
+                    // already applied above, applied below, or handled by whoever shows the window
+                }
                 P::Anchor(anchor) => {
-                    window = window.anchor(anchor.align, anchor.offset);
+                    let offset = match &safe_area {
+                        Some(safe_area) => safe_area.anchor_offset(anchor.align, anchor.offset),
+                        None            => anchor.offset,
+                    };
+                    window = window.anchor(anchor.align, offset);
                 }
                 P::TitleBar(title_bar) => {
                     if let Ok(title_bar) = title_bar.resolve(data) {
@@ -92,6 +487,45 @@ impl Window {
                     }
                 }
 
+                // everything related to positioning
+                P::DefaultPos(pos) => {
+                    window = window.default_pos(*pos);
+                }
+                P::CurrentPos(pos) => {
+                    if let Ok(pos) = pos.resolve(data) {
+                        window = window.current_pos(egui::pos2(pos.x, pos.y));
+                    }
+                }
+                P::Pivot(pivot) => {
+                    window = window.pivot(*pivot);
+                }
+                P::Constrain(constrain) => {
+                    window = window.constrain(*constrain);
+                }
+                P::ConstrainTo(rect) => {
+                    window = window.constrain_to(*rect);
+                }
+
+                // scrolling and styling
+                P::VScroll(vscroll) => {
+                    window = window.vscroll(*vscroll);
+                }
+                P::HScroll(hscroll) => {
+                    window = window.hscroll(*hscroll);
+                }
+                P::Scroll2(scroll) => {
+                    window = window.scroll2(*scroll);
+                }
+                P::Frame(props) => {
+                    // properties are applied on top of default window frame
+                    frame = Some(FrameProperty::apply(props, egui::Frame::window(&ctx.style()), data));
+                }
+                P::Opacity(value) => {
+                    if let Ok(value) = value.resolve(data) {
+                        opacity = value.clamp(0., 1.);
+                    }
+                }
+
                 // everything related to resizing
                 P::DefaultSize(size) => {
                     window = window.default_size(*size);
@@ -107,8 +541,10 @@ impl Window {
                 P::FixedSize(size) => {
                     window = window.fixed_size(*size);
                 }
-                P::AutoSized => {
-                    window = window.auto_sized();
+                P::AutoSized(auto_sized) => {
+                    if *auto_sized {
+                        window = window.auto_sized();
+                    }
                 }
                 P::Resizable(resizable) => {
                     if let Ok(resizable) = resizable.resolve(data) {
@@ -137,12 +573,224 @@ impl Window {
                         window = window.collapsible(collapsible);
                     }
                 }
+                P::Collapsed(Binding::Value(collapsed)) => {
+                    window = window.default_open(!collapsed);
+                }
+                P::Collapsed(collapsed) => {
+                    // bound value always wins over whatever egui remembers
+                    if let Ok(collapsed) = collapsed.resolve(data) {
+                        let id = self.collapsing_id(hash);
+                        let mut state = egui::collapsing_header::CollapsingState::load_with_default_open(ctx, id, !collapsed);
+                        state.set_open(!collapsed);
+                        state.store(ctx);
+                    }
+                }
+            }
+        }
+
+        // saved layout is only used while egui doesn't know the window yet, i.e. after startup
+        let layouts = UiconfLayouts::get(ctx).filter(|_| pos.is_none()).map(|layouts| (layouts, self.layout_key(asset)));
+        let saved = layouts.as_ref().and_then(|(layouts, key)| layouts.window(key));
+        if let Some(saved) = saved {
+            window = window.default_pos(egui::pos2(saved.pos[0], saved.pos[1])).default_open(!saved.collapsed);
+            if let Some(size) = saved.size {
+                window = window.default_size(egui::vec2(size[0], size[1]));
             }
         }
 
-        window.show(ctx, |ui| {
+        if let Some(pos) = pos {
+            window = window.current_pos(pos).movable(false);
+        }
+
+        // overrides everything related to placement and decorations, whatever order properties are in
+        let fullscreen = self.props.iter().any(|prop| match prop {
+            WindowProperty::Fullscreen(fullscreen) => fullscreen.resolve(data).unwrap_or(false),
+            _ => false,
+        });
+        if fullscreen {
+            let mut window_frame = frame.unwrap_or_else(|| egui::Frame::window(&ctx.style()));
+            window_frame.rounding = egui::Rounding::ZERO;
+            window_frame.stroke = egui::Stroke::NONE;
+            window_frame.shadow = egui::epaint::Shadow::NONE;
+            frame = Some(window_frame);
+
+            // fixed size is the size of content, without frame margins
+            let rect = safe_area.map_or_else(|| ctx.available_rect(), |safe_area| safe_area.rect(ctx));
+            window = window
+                .fixed_pos(rect.min)
+                .fixed_size(rect.size() - window_frame.inner_margin.sum())
+                .title_bar(false)
+                .collapsible(false)
+                .movable(false);
+        }
+
+        // window frame and title bar are painted with context style, so it is faded for the duration
+        // of this window, content colors set explicitly (e.g. text `color`) aren't affected
+        let style = ctx.style();
+        if opacity < 1. {
+            let mut frame = frame.unwrap_or_else(|| egui::Frame::window(&style));
+            frame.fill = frame.fill.gamma_multiply(opacity);
+            frame.stroke.color = frame.stroke.color.gamma_multiply(opacity);
+            frame.shadow.color = frame.shadow.color.gamma_multiply(opacity);
+            window = window.frame(frame);
+
+            let mut faded = (*style).clone();
+            fade_visuals(&mut faded.visuals, opacity);
+            ctx.set_style(faded);
+        } else if let Some(frame) = frame {
+            window = window.frame(frame);
+        }
+
+        let layer = egui::LayerId::new(egui::Order::Middle, self.id(hash));
+
+        // window frame is painted inside of `show`, so background has to be placed in its layer beforehand
+        let background = self.props.iter().rev().find_map(|prop| match prop {
+            WindowProperty::Frame(props) => FrameProperty::background(props),
+            _ => None,
+        }).map(|background| {
+            let painter = ctx.layer_painter(layer);
+            let idx = Background::placeholder(&painter);
+            (background, painter, idx)
+        });
+
+        // Tab moves focus between widgets with `focus_order` within this window
+        let focused_before = ctx.memory(|mem| mem.focus());
+        FocusOrder::begin(ctx, layer);
+
+        let mut content_size = None;
+        let response = window.show(ctx, |ui| {
+            content_size = Some(ui.max_rect().size());
+            Place::set_window_rect(ui);
             self.content.show(data, ui);
         });
+        WidgetResponse::store(ctx, self.id(hash));
+        WidgetState::store(ctx, self.id(hash));
+        FocusOrder::end(ctx, layer, focused_before);
+        if let Some(response) = &response {
+            ShownAreas::record(ctx, response.response.layer_id, response.response.rect);
+        }
+
+        if let (Some((layouts, key)), Some(response)) = (&layouts, &response) {
+            // position is saved the same way it's restored, as position of the pivot
+            let pivot = self.props.iter().rev().find_map(|prop| match prop {
+                WindowProperty::Pivot(pivot) => Some(*pivot),
+                _ => None,
+            }).unwrap_or(egui::Align2::LEFT_TOP);
+            let pos = pivot.pos_in_rect(&response.response.rect);
+            // collapsed window doesn't show its content, so size from the last time is kept
+            let size = content_size.map(|size| [size.x, size.y]).or(saved.and_then(|saved| saved.size));
+            let collapsed = egui::collapsing_header::CollapsingState::load(ctx, self.collapsing_id(hash)).is_some_and(|state| !state.is_open());
+            layouts.record(key, WindowLayout { pos: [pos.x, pos.y], size, collapsed });
+        }
+
+        if let (Some(docked), Some(response)) = (docked, &response) {
+            self.dock_menu(&response.response, docked.dock);
+        }
+
+        if let (Some((background, painter, idx)), Some(response)) = (background, response) {
+            background.paint(data, &painter, idx, response.response.rect);
+        }
+
+        if opacity < 1. {
+            ctx.set_style(style);
+        }
+
+        // write back collapse state, in case user toggled it
+        for prop in self.props.iter() {
+            if let WindowProperty::Collapsed(Binding::Ref(collapsed)) = prop {
+                let state = egui::collapsing_header::CollapsingState::load(ctx, self.collapsing_id(hash));
+                if let (Some(state), Ok(collapsed)) = (state, collapsed.resolve_mut(data)) {
+                    *collapsed = !state.is_open();
+                }
+            }
+        }
+    }
+}
+
+impl Window {
+    // content is drawn into the rect of its tab, the tab itself is shown by `dock::show_dock_area`
+    #[cfg(feature = "egui_dock")]
+    fn show_tab(&self, hash: egui::Id, asset: &str, data: &mut dyn DataSource, ctx: &egui::Context, dock: Dock) {
+        let Some(tabs) = UiconfDock::get(ctx) else { return; };
+        let key = self.layout_key(asset);
+        let title = self.title.as_ref().map_or_else(|| key.clone(), |title| title.resolve(data, ctx).text().to_owned());
+        let id = self.id(hash);
+        let Some((layer, rect, clip_rect)) = tabs.tab_rect(&key, dock, title) else {
+            // responses from the last time window was shown are forgotten
+            WidgetResponse::store(ctx, id);
+            return;
+        };
+
+        let mut ui = egui::Ui::new(ctx.clone(), layer, id, rect, clip_rect.intersect(rect));
+        Place::set_window_rect(&ui);
+        egui::ScrollArea::both().auto_shrink([false; 2]).show(&mut ui, |ui| self.content.show(data, ui));
+        WidgetResponse::store(ctx, id);
+        WidgetState::store(ctx, id);
+        ShownAreas::record(ctx, layer, rect);
+    }
+
+    // docked windows are egui panels, so they have to be shown before `CentralPanel`
+    #[cfg_attr(feature = "egui_dock", allow(dead_code))]
+    fn show_docked(&self, hash: egui::Id, data: &mut dyn DataSource, ctx: &egui::Context, docked: DockedWindow) {
+        let id = self.id(hash);
+        let mut frame = egui::Frame::side_top_panel(&ctx.style());
+        for prop in self.props.iter() {
+            if let WindowProperty::Frame(props) = prop {
+                frame = FrameProperty::apply(props, frame, data);
+            }
+        }
+
+        let show_content = |ui: &mut egui::Ui| {
+            if let Some(title) = &self.title {
+                let title = title.resolve(data, ui.ctx()).fallback_text_style(egui::TextStyle::Heading);
+                let header = ui.add(egui::Label::new(title).sense(egui::Sense::click()));
+                self.dock_menu(&header, docked.dock);
+                ui.separator();
+            }
+            Place::set_window_rect(ui);
+            egui::ScrollArea::both().auto_shrink([false; 2]).show(ui, |ui| self.content.show(data, ui));
+        };
+
+        let response = match docked.dock {
+            Dock::Left | Dock::Right => {
+                let mut panel = if docked.dock == Dock::Left { egui::SidePanel::left(id) } else { egui::SidePanel::right(id) };
+                if let Some(size) = docked.size { panel = panel.default_width(size); }
+                panel.frame(frame).show(ctx, show_content).response
+            }
+            Dock::Top | Dock::Bottom => {
+                let mut panel = if docked.dock == Dock::Top { egui::TopBottomPanel::top(id) } else { egui::TopBottomPanel::bottom(id) };
+                if let Some(size) = docked.size { panel = panel.default_height(size); }
+                panel.resizable(true).frame(frame).show(ctx, show_content).response
+            }
+            Dock::Float => return,
+        };
+        let size = match docked.dock {
+            Dock::Left | Dock::Right => response.rect.width(),
+            _                        => response.rect.height(),
+        };
+        WidgetResponse::store(ctx, id);
+        WidgetState::store(ctx, id);
+        ShownAreas::record(ctx, response.layer_id, response.rect);
+
+        // panel size is remembered, so that it's saved together with the arrangement
+        if let (Some(window_id), Some(dock)) = (self.explicit_id(), UiconfDock::get(ctx)) {
+            if docked.size != Some(size) {
+                dock.set_size(&window_id.0, docked.dock, size);
+            }
+        }
+    }
+
+    // windows with explicit `id` can be moved to another side from the context menu of their title
+    fn dock_menu(&self, response: &egui::Response, current: Dock) {
+        let (Some(id), Some(dock)) = (self.explicit_id(), UiconfDock::get(&response.ctx)) else { return; };
+        response.clone().context_menu(|ui| {
+            for side in [Dock::Left, Dock::Right, Dock::Top, Dock::Bottom, Dock::Float] {
+                if ui.selectable_label(side == current, side.to_string()).clicked() {
+                    dock.set_dock(&id.0, side);
+                    ui.close_menu();
+                }
+            }
+        });
     }
 }
 
@@ -161,16 +809,20 @@ impl ReadUiconf for Window {
                 title = Some(value.read()?);
                 should_be_on_top = true;
             } else if WindowProperty::FIELDS.contains(&&*key) {
+                if key == "id" && props.iter().any(|prop| matches!(prop, WindowProperty::Id(_))) {
+                    return Err(Error::duplicate_field(&value, "id"));
+                }
                 props.push(WindowProperty::read_map_value(&key, &value)?);
                 should_be_on_top = true;
-            } else if ContentWidget::FIELDS.contains(&&*key) {
+            } else if ContentWidget::is_field(&value, &key) {
                 content.push(ContentWidget::read_map_value(&key, &value)?);
                 last_content = Some(key.to_string());
             } else {
-                return Err(Error::unknown_field(&value, &key, Window::FIELDS));
+                value.skip_unknown_field(&key, Window::FIELDS)?;
             }
 
-            if should_be_on_top && last_content.is_some() {
+            // bare flags can only be placed at the end of the object
+            if should_be_on_top && last_content.is_some() && !value.is_flag() {
                 return Err(Error::custom(&value, format!(
                     "all window properties should be above content, but `{}` is located after `{}`",
                     key, last_content.unwrap(),
@@ -178,7 +830,13 @@ impl ReadUiconf for Window {
             }
         }
 
-        let title = title.ok_or_else(|| Error::missing_field(value, "title"))?;
+        // window id is derived from asset path, so title is only needed for the title bar
+        let title_optional = props.iter().any(|prop| matches!(prop,
+            WindowProperty::Id(_) | WindowProperty::TitleBar(Binding::Value(false))
+        ));
+        if title.is_none() && !title_optional {
+            return Err(Error::missing_field(value, "title"));
+        }
 
         Ok(Window {
             title,
@@ -188,21 +846,66 @@ impl ReadUiconf for Window {
     }
 }
 
+impl ToUiconf for Window {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(title) = &self.title {
+                writer.field("title", title);
+            }
+            for prop in self.props.iter() {
+                prop.write_map_value(writer);
+            }
+            self.content.write_map_values(writer);
+        });
+    }
+}
+
 //
 // WindowProperty
 //
 
 #[derive(Debug)]
 pub enum WindowProperty {
+    Id(WidgetId),
+    // `show_when = { @state == InGame }`, window isn't shown at all otherwise
+    ShowWhen(Condition),
+    // name of `UiconfViewport` to show the window on, primary window by default
+    Viewport(String),
+    // `dock = left`, shown as a side panel instead of a floating window
+    Dock(Dock),
+    // `staging = { apply = @apply revert = @revert }`, changes are only written when applied
+    Staging(Staging),
     Anchor(Anchor),
     TitleBar(Binding<bool>),
+    // `fullscreen = @in_menu`, covers space not taken by panels, without title bar and decorations
+    Fullscreen(Binding<bool>),
+    // kept inside of `UiconfSafeArea`, anchor offsets are counted from its edges
+    RespectSafeArea(bool),
+
+    // everything related to positioning
+    DefaultPos(egui::Pos2),
+    CurrentPos(Binding<bevy::math::Vec2>),
+    Pivot(egui::Align2),
+    Constrain(bool),
+    ConstrainTo(egui::Rect),
+
+    // scrolling and styling
+    VScroll(bool),
+    HScroll(bool),
+    Scroll2([bool; 2]),
+    Frame(Vec<FrameProperty>),
+    Opacity(Binding<f32>),
+    // `scale = @settings.ui_scale`, multiplies text sizes and spacing, on top of `UiconfScale`
+    Scale(Binding<f32>),
+    // `theme = @settings.theme`, name of a style from `UiconfThemes`, current theme by default
+    Theme(Binding<String>),
 
     // everything related to resizing
     DefaultSize(egui::Vec2),
     MinSize(egui::Vec2),
     MaxSize(egui::Vec2),
     FixedSize(egui::Vec2),
-    AutoSized,
+    AutoSized(bool),
     Resizable(Binding<bool>),
 
     // other flags
@@ -210,106 +913,680 @@ pub enum WindowProperty {
     Interactable(Binding<bool>),
     Movable(Binding<bool>),
     Collapsible(Binding<bool>),
+    Collapsed(Binding<bool>),
 }
 
 impl WindowProperty {
     const FIELDS: &'static [&'static str] = &[
-        "id", "anchor", "title_bar",
+        "id", "show_when", "viewport", "dock", "staging", "anchor", "title_bar", "fullscreen", "respect_safe_area",
+        "default_pos", "current_pos", "pivot", "constrain", "constrain_to",
+        "vscroll", "hscroll", "scroll2", "frame", "opacity", "scale", "theme",
         "default_size", "min_size", "max_size", "fixed_size", "auto_sized", "resizable",
-        "enabled", "interactable", "movable", "collapsible",
+        "enabled", "interactable", "movable", "collapsible", "collapsed",
     ];
 
     fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
         match tag {
+            "id"           => Ok(Self::Id           (value.read()?)),
+            "show_when"    => Ok(Self::ShowWhen     (value.read()?)),
+            "viewport"     => Ok(Self::Viewport     (value.read()?)),
+            "dock"         => Ok(Self::Dock         (value.read()?)),
+            "staging"      => Ok(Self::Staging      (value.read()?)),
             "anchor"       => Ok(Self::Anchor       (value.read()?)),
             "title_bar"    => Ok(Self::TitleBar     (value.read()?)),
+            "fullscreen"   => Ok(Self::Fullscreen   (value.read()?)),
+            "respect_safe_area" => Ok(Self::RespectSafeArea(value.read()?)),
+            "default_pos"  => Ok(Self::DefaultPos   (value.read::<Size<{ SIZE_ANY_DISALLOWED }>>()?.0.to_pos2())),
+            "current_pos"  => Ok(Self::CurrentPos   (value.read()?)),
+            "pivot"        => Ok(Self::Pivot        (value.read::<Align2>()?.0)),
+            "constrain"    => Ok(Self::Constrain    (value.read()?)),
+            "constrain_to" => Ok(Self::ConstrainTo  (value.read::<Rect>()?.0)),
+            "vscroll"      => Ok(Self::VScroll      (value.read()?)),
+            "hscroll"      => Ok(Self::HScroll      (value.read()?)),
+            "scroll2"      => Ok(Self::Scroll2      (Self::read_scroll2(value)?)),
+            "frame"        => Ok(Self::Frame        (FrameProperty::read_block(value)?)),
+            "opacity"      => Ok(Self::Opacity      (value.read()?)),
+            "scale"        => Ok(Self::Scale        (value.read()?)),
+            "theme"        => Ok(Self::Theme        (value.read()?)),
             "default_size" => Ok(Self::DefaultSize  (value.read::<Size<{ SIZE_ANY_DISALLOWED }>>()?.0)),
             "min_size"     => Ok(Self::MinSize      (value.read::<Size<{ SIZE_ANY_IS_ZERO    }>>()?.0)),
             "max_size"     => Ok(Self::MaxSize      (value.read::<Size<{ SIZE_ANY_IS_INF     }>>()?.0)),
             "fixed_size"   => Ok(Self::FixedSize    (value.read::<Size<{ SIZE_ANY_DISALLOWED }>>()?.0)),
-            "auto_sized"   => { value.read::<Empty>()?; Ok(Self::AutoSized) },
+            "auto_sized"   => Ok(Self::AutoSized    (read_flag(value)?)),
             "resizable"    => Ok(Self::Resizable    (value.read()?)),
             "enabled"      => Ok(Self::Enabled      (value.read()?)),
             "interactable" => Ok(Self::Interactable (value.read()?)),
             "movable"      => Ok(Self::Movable      (value.read()?)),
             "collapsible"  => Ok(Self::Collapsible  (value.read()?)),
+            "collapsed"    => Ok(Self::Collapsed    (value.read()?)),
             _              => Err(Error::unknown_field(value, tag, Self::FIELDS)),
         }
     }
+
+    fn read_scroll2(value: &Reader) -> Result<[bool; 2], Error> {
+        const EXPECTED: &str = "{ hscroll vscroll }";
+        let mut seq = value.read_array()?;
+        let hscroll = seq.next().ok_or_else(|| Error::invalid_length(value, 0, EXPECTED))?.read()?;
+        let vscroll = seq.next().ok_or_else(|| Error::invalid_length(value, 1, EXPECTED))?.read()?;
+        if seq.next().is_some() {
+            return Err(Error::invalid_length(value, 3, EXPECTED));
+        }
+        Ok([hscroll, vscroll])
+    }
+
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Id(id)                     => writer.field("id", id),
+            Self::ShowWhen(condition)        => writer.field("show_when", condition),
+            Self::Viewport(viewport)         => writer.field("viewport", viewport),
+            Self::Dock(dock)                 => writer.field("dock", dock),
+            Self::Staging(staging)           => writer.field("staging", staging),
+            Self::Anchor(anchor)             => writer.field("anchor", anchor),
+            Self::TitleBar(title_bar)        => writer.field("title_bar", title_bar),
+            Self::Fullscreen(fullscreen)     => writer.field("fullscreen", fullscreen),
+            Self::RespectSafeArea(respect)   => writer.field("respect_safe_area", respect),
+            Self::DefaultPos(pos)            => writer.field("default_pos", &Size::<{ SIZE_ANY_DISALLOWED }>(pos.to_vec2())),
+            Self::CurrentPos(pos)            => writer.field("current_pos", pos),
+            Self::Pivot(pivot)               => writer.field("pivot", &Align2(*pivot)),
+            Self::Constrain(constrain)       => writer.field("constrain", constrain),
+            Self::ConstrainTo(rect)          => writer.field("constrain_to", &Rect(*rect)),
+            Self::VScroll(vscroll)           => writer.field("vscroll", vscroll),
+            Self::HScroll(hscroll)           => writer.field("hscroll", hscroll),
+            Self::Scroll2(scroll)            => writer.field("scroll2", &scroll.to_vec()),
+            Self::Frame(props)               => writer.field_with("frame", |writer| FrameProperty::write_block(props, writer)),
+            Self::Opacity(opacity)           => writer.field("opacity", opacity),
+            Self::Scale(scale)               => writer.field("scale", scale),
+            Self::Theme(theme)               => writer.field("theme", theme),
+            Self::DefaultSize(size)          => writer.field("default_size", &Size::<{ SIZE_ANY_DISALLOWED }>(*size)),
+            Self::MinSize(size)              => writer.field("min_size", &Size::<{ SIZE_ANY_IS_ZERO }>(*size)),
+            Self::MaxSize(size)              => writer.field("max_size", &Size::<{ SIZE_ANY_IS_INF }>(*size)),
+            Self::FixedSize(size)            => writer.field("fixed_size", &Size::<{ SIZE_ANY_DISALLOWED }>(*size)),
+            Self::AutoSized(auto_sized)      => writer.field("auto_sized", auto_sized),
+            Self::Resizable(resizable)       => writer.field("resizable", resizable),
+            Self::Enabled(enabled)           => writer.field("enabled", enabled),
+            Self::Interactable(interactable) => writer.field("interactable", interactable),
+            Self::Movable(movable)           => writer.field("movable", movable),
+            Self::Collapsible(collapsible)   => writer.field("collapsible", collapsible),
+            Self::Collapsed(collapsed)       => writer.field("collapsed", collapsed),
+        }
+    }
 }
 
 //
-// Content
+// Dock
 //
 
-#[derive(Debug)]
-pub struct Content(Vec<ContentWidget>);
+#[derive(EnumString, EnumVariantNames, Display, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Dock {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    // regular window, which can be docked from its context menu
+    Float,
+}
 
-impl Content {
-    fn show(&self, data: &mut dyn Reflect, ui: &mut egui::Ui) {
-        for widget in self.0.iter() {
-            widget.show(data, ui);
-        }
+impl ReadUiconf for Dock {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let name = value.read_string()?;
+        Self::from_str(&name).map_err(|_| {
+            Error::unknown_variant(value, &name, Self::VARIANTS)
+        })
     }
 }
 
-impl ReadUiconf for Content {
-    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
-        if value.is_scalar() {
-            return Ok(Self(vec![ContentWidget::Label(Label::new(value.read()?))]));
-        }
+impl ToUiconf for Dock {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.scalar(self);
+    }
+}
 
-        let mut widgets = vec![];
+//
+// Staging
+//
 
-        for (key, value) in value.read_object()? {
-            widgets.push(ContentWidget::read_map_value(&key, &value)?);
+#[derive(Debug)]
+pub struct Staging {
+    // triggers are consumed here, so they aren't seen by the app
+    pub apply: Option<BindingRef<Trigger>>,
+    pub revert: Option<BindingRef<Trigger>>,
+    // set while there are changes that aren't applied yet
+    pub dirty: Option<BindingRef<bool>>,
+}
+
+impl Staging {
+    const FIELDS: &'static [&'static str] = &["apply", "revert", "dirty"];
+
+    // changes made in previous frames are applied or reverted before the window is shown
+    fn show(&self, id: egui::Id, data: &mut dyn DataSource, ctx: &egui::Context, show: impl FnOnce(&mut dyn DataSource)) {
+        let staged = StagedCopies::get(ctx, id.with("uiconf_staging"));
+        let mut copies = staged.take();
+
+        let mut triggered = |trigger: &Option<BindingRef<Trigger>>| {
+            trigger.as_ref().and_then(|trigger| trigger.resolve_mut(data).ok()).is_some_and(Trigger::check_reset)
+        };
+        if triggered(&self.revert) {
+            copies.clear();
+        }
+        if triggered(&self.apply) {
+            apply_staged(data, std::mem::take(&mut copies));
         }
 
-        Ok(Content(widgets))
+        let mut source = StagingSource::new(data, copies, ctx);
+        show(&mut source);
+        let copies = source.finish();
+
+        // only written when it changes, so that change detection of the data model isn't triggered
+        let is_dirty = !copies.is_empty();
+        if let Some(dirty) = self.dirty.as_ref().and_then(|dirty| dirty.resolve_mut(data).ok()) {
+            if *dirty != is_dirty { *dirty = is_dirty; }
+        }
+        staged.put(copies);
     }
 }
 
-#[derive(Debug)]
-pub enum ContentWidget {
+impl ReadUiconf for Staging {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut apply = None;
+        let mut revert = None;
+        let mut dirty = None;
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "apply" => {
+                    if apply.is_some() { return Err(Error::duplicate_field(&value, "apply")); }
+                    apply = Some(value.read()?);
+                }
+                "revert" => {
+                    if revert.is_some() { return Err(Error::duplicate_field(&value, "revert")); }
+                    revert = Some(value.read()?);
+                }
+                "dirty" => {
+                    if dirty.is_some() { return Err(Error::duplicate_field(&value, "dirty")); }
+                    dirty = Some(value.read()?);
+                }
+                _ => value.skip_unknown_field(&key, Self::FIELDS)?,
+            }
+        }
+
+        Ok(Self { apply, revert, dirty })
+    }
+}
+
+impl ToUiconf for Staging {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(apply) = &self.apply { writer.field("apply", apply); }
+            if let Some(revert) = &self.revert { writer.field("revert", revert); }
+            if let Some(dirty) = &self.dirty { writer.field("dirty", dirty); }
+        });
+    }
+}
+
+//
+// Content
+//
+
+#[derive(Debug)]
+pub struct Content(Vec<ContentWidget>);
+
+impl Content {
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        for widget in self.0.iter() {
+            widget.show(data, ui);
+        }
+    }
+}
+
+impl Content {
+    fn write_map_values(&self, writer: &mut Writer) {
+        for widget in self.0.iter() {
+            widget.write_map_value(writer);
+        }
+    }
+}
+
+impl ToUiconf for Content {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| self.write_map_values(writer));
+    }
+}
+
+impl ReadUiconf for Content {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        if value.is_scalar() {
+            return Ok(Self(vec![ContentWidget::Label(Label::new(value.read()?))]));
+        }
+
+        let mut widgets = vec![];
+
+        for (key, value) in value.read_object()? {
+            if ContentWidget::is_field(&value, &key) {
+                widgets.push(ContentWidget::read_map_value(&key, &value)?);
+            } else {
+                value.skip_unknown_field(&key, ContentWidget::FIELDS)?;
+            }
+        }
+
+        Ok(Content(widgets))
+    }
+}
+
+#[derive(Debug)]
+pub enum ContentWidget {
     // widgets
     Button(Button),
     Label(Label),
+    EditableLabel(EditableLabel),
+    PasswordEdit(PasswordEdit),
+    NumericEdit(NumericEdit),
+    SettingRow(SettingRow),
     Separator(Separator),
+    Spinner(Spinner),
+    Gauge(Gauge),
+    DragVector(DragVector),
     // containers
     Layout(Layout),
     Grid(Grid),
+    Columns(Columns),
+    Stack(Stack),
+    Place(Place),
+    ScrollArea(ScrollArea),
+    Frame(Frame),
+    Modal(Modal),
+    #[cfg(feature = "egui_extras")]
+    Table(Table),
+    #[cfg(feature = "egui_plot")]
+    Plot(Plot),
     // iterator
     Each(Each),
+    Match(Match),
+    Tree(Tree),
+    ListBox(ListBox),
+    SlotGrid(Box<SlotGrid>),
+    ChatLog(Box<ChatLog>),
     // other
     EndRow(Empty),
+    // registered in `UiconfWidgetRegistry`
+    Custom(String, Box<dyn CustomWidget>),
 }
 
 impl ContentWidget {
-    const FIELDS: &'static [&'static str] = &["button", "label", "separator", "layout", "grid", "each", "end_row"];
+    const FIELDS: &'static [&'static str] = &["button", "label", "editable_label", "password_edit", "numeric_edit", "setting_row", "separator", "spinner", "gauge", "drag_vec2", "drag_vec3", "drag_angle", "layout", "grid", "columns", "stack", "place", "scroll_area", "frame", "group", "modal", "table", "plot", "each", "match", "tree", "list_box", "slot_grid", "chat_log", "end_row"];
 
     fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
         match tag {
             "button"    => Ok(Self::Button    (value.read()?)),
             "label"     => Ok(Self::Label     (value.read()?)),
+            "editable_label" => Ok(Self::EditableLabel(value.read()?)),
+            "password_edit"  => Ok(Self::PasswordEdit(value.read()?)),
+            "numeric_edit"   => Ok(Self::NumericEdit(value.read()?)),
+            "setting_row"    => Ok(Self::SettingRow(value.read()?)),
             "separator" => Ok(Self::Separator (value.read()?)),
+            "spinner"   => Ok(Self::Spinner   (value.read()?)),
+            "gauge"     => Ok(Self::Gauge     (value.read()?)),
+            "drag_vec2" => Ok(Self::DragVector(DragVector::read(value, DragVectorKind::Vec2)?)),
+            "drag_vec3" => Ok(Self::DragVector(DragVector::read(value, DragVectorKind::Vec3)?)),
+            "drag_angle"=> Ok(Self::DragVector(DragVector::read(value, DragVectorKind::Angle)?)),
             "layout"    => Ok(Self::Layout    (value.read()?)),
             "grid"      => Ok(Self::Grid      (value.read()?)),
+            "columns"   => Ok(Self::Columns   (value.read()?)),
+            "stack"     => Ok(Self::Stack     (value.read()?)),
+            "place"     => Ok(Self::Place     (value.read()?)),
+            "scroll_area"    => Ok(Self::ScrollArea(value.read()?)),
+            "frame"     => Ok(Self::Frame     (Frame::read(value, false)?)),
+            "group"     => Ok(Self::Frame     (Frame::read(value, true)?)),
+            "modal"     => Ok(Self::Modal     (value.read()?)),
+            #[cfg(feature = "egui_extras")]
+            "table"     => Ok(Self::Table     (value.read()?)),
+            #[cfg(not(feature = "egui_extras"))]
+            "table"     => Err(Error::custom(value, "`table` requires `egui_extras` feature to be enabled")),
+            #[cfg(feature = "egui_plot")]
+            "plot"      => Ok(Self::Plot      (value.read()?)),
+            #[cfg(not(feature = "egui_plot"))]
+            "plot"      => Err(Error::custom(value, "`plot` requires `egui_plot` feature to be enabled")),
             "each"      => Ok(Self::Each      (value.read()?)),
+            "match"     => Ok(Self::Match     (value.read()?)),
+            "tree"      => Ok(Self::Tree      (value.read()?)),
+            "list_box"  => Ok(Self::ListBox   (value.read()?)),
+            "slot_grid" => Ok(Self::SlotGrid  (Box::new(value.read()?))),
+            "chat_log"  => Ok(Self::ChatLog   (Box::new(value.read()?))),
             "end_row"   => { value.read::<Empty>()?; Ok(Self::EndRow(Empty)) },
-            _           => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+            _           => match value.widgets().read(tag, value) {
+                Some(widget) => Ok(Self::Custom(tag.to_owned(), widget?)),
+                None         => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+            },
+        }
+    }
+
+    fn is_field(value: &Reader, tag: &str) -> bool {
+        Self::FIELDS.contains(&tag) || value.widgets().contains(tag)
+    }
+
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Button(button)       => writer.field("button", button),
+            Self::Label(label)         => writer.field("label", label),
+            Self::EditableLabel(label) => writer.field("editable_label", label),
+            Self::PasswordEdit(edit)   => writer.field("password_edit", edit),
+            Self::NumericEdit(edit)    => writer.field("numeric_edit", edit),
+            Self::SettingRow(row)      => writer.field("setting_row", row),
+            Self::Separator(separator) => writer.field("separator", separator),
+            Self::Spinner(spinner)     => writer.field("spinner", spinner),
+            Self::Gauge(gauge)         => writer.field("gauge", gauge),
+            Self::DragVector(drag)     => writer.field(&format!("drag_{}", drag.kind()), drag),
+            Self::Layout(layout)       => writer.field("layout", layout),
+            Self::Grid(grid)           => writer.field("grid", grid),
+            Self::Columns(columns)     => writer.field("columns", columns),
+            Self::Stack(stack)         => writer.field("stack", stack),
+            Self::Place(place)         => writer.field("place", place),
+            Self::ScrollArea(scroll)   => writer.field("scroll_area", scroll),
+            Self::Frame(frame)         => writer.field(if frame.group { "group" } else { "frame" }, frame),
+            Self::Modal(modal)         => writer.field("modal", modal),
+            #[cfg(feature = "egui_extras")]
+            Self::Table(table)         => writer.field("table", table),
+            #[cfg(feature = "egui_plot")]
+            Self::Plot(plot)           => writer.field("plot", plot),
+            Self::Each(each)           => writer.field("each", each),
+            Self::Match(match_)        => writer.field("match", match_),
+            Self::Tree(tree)           => writer.field("tree", tree),
+            Self::ListBox(list_box)    => writer.field("list_box", list_box),
+            Self::SlotGrid(slot_grid)  => writer.field("slot_grid", &**slot_grid),
+            Self::ChatLog(chat_log)    => writer.field("chat_log", &**chat_log),
+            Self::EndRow(empty)        => writer.field("end_row", empty),
+            Self::Custom(tag, widget)  => writer.field(tag, &**widget),
+        }
+    }
+
+    // explicit `id`, if set, scopes all egui state of a widget and its content
+    fn id(&self) -> Option<&WidgetId> {
+        match self {
+            Self::Button(button)       => button.id.as_ref(),
+            Self::Label(label)         => label.id.as_ref(),
+            Self::EditableLabel(label) => label.id.as_ref(),
+            Self::PasswordEdit(edit)   => edit.id.as_ref(),
+            Self::NumericEdit(edit)    => edit.id.as_ref(),
+            Self::SettingRow(row)      => row.id.as_ref(),
+            Self::Separator(separator) => separator.id.as_ref(),
+            Self::Spinner(spinner)     => spinner.id.as_ref(),
+            Self::Gauge(gauge)         => gauge.id.as_ref(),
+            Self::DragVector(drag)     => drag.id.as_ref(),
+            Self::Layout(layout)       => layout.id.as_ref(),
+            Self::Grid(grid)           => grid.explicit_id.as_ref(),
+            Self::Columns(columns)     => columns.id.as_ref(),
+            Self::Stack(stack)         => stack.id.as_ref(),
+            Self::Place(place)         => place.id.as_ref(),
+            Self::ScrollArea(scroll)   => scroll.id.as_ref(),
+            Self::Frame(frame)         => frame.id.as_ref(),
+            Self::Modal(modal)         => modal.explicit_id.as_ref(),
+            #[cfg(feature = "egui_extras")]
+            Self::Table(table)         => table.explicit_id.as_ref(),
+            #[cfg(feature = "egui_plot")]
+            Self::Plot(plot)           => plot.explicit_id.as_ref(),
+            Self::Tree(tree)           => tree.id.as_ref(),
+            Self::ListBox(list_box)    => list_box.id.as_ref(),
+            Self::SlotGrid(slot_grid)  => slot_grid.id.as_ref(),
+            Self::ChatLog(chat_log)    => chat_log.id.as_ref(),
+            Self::Each(_) | Self::Match(_) | Self::EndRow(_) | Self::Custom(..) => None,
+        }
+    }
+
+    fn enabled(&self) -> Option<&Binding<bool>> {
+        match self {
+            Self::Button(button)       => button.enabled.as_ref(),
+            Self::Label(label)         => label.enabled.as_ref(),
+            Self::EditableLabel(label) => label.enabled.as_ref(),
+            Self::PasswordEdit(edit)   => edit.enabled.as_ref(),
+            Self::NumericEdit(edit)    => edit.enabled.as_ref(),
+            Self::SettingRow(row)      => row.enabled.as_ref(),
+            Self::Separator(separator) => separator.enabled.as_ref(),
+            Self::Spinner(spinner)     => spinner.enabled.as_ref(),
+            Self::Gauge(gauge)         => gauge.enabled.as_ref(),
+            Self::DragVector(drag)     => drag.enabled.as_ref(),
+            Self::Layout(layout)       => layout.enabled.as_ref(),
+            Self::Grid(grid)           => grid.enabled.as_ref(),
+            Self::Columns(columns)     => columns.enabled.as_ref(),
+            Self::Stack(stack)         => stack.enabled.as_ref(),
+            Self::Place(place)         => place.enabled.as_ref(),
+            Self::ScrollArea(scroll)   => scroll.enabled.as_ref(),
+            Self::Frame(frame)         => frame.enabled.as_ref(),
+            Self::Modal(modal)         => modal.enabled.as_ref(),
+            #[cfg(feature = "egui_extras")]
+            Self::Table(table)         => table.enabled.as_ref(),
+            #[cfg(feature = "egui_plot")]
+            Self::Plot(plot)           => plot.enabled.as_ref(),
+            Self::Tree(tree)           => tree.enabled.as_ref(),
+            Self::ListBox(list_box)    => list_box.enabled.as_ref(),
+            Self::SlotGrid(slot_grid)  => slot_grid.enabled.as_ref(),
+            Self::ChatLog(chat_log)    => chat_log.enabled.as_ref(),
+            Self::Each(each)           => each.enabled.as_ref(),
+            Self::Match(match_)        => match_.enabled.as_ref(),
+            Self::Custom(_, widget)    => widget.enabled(),
+            Self::EndRow(_)            => None,
+        }
+    }
+
+    // modal has its own `visible`, which opens and closes it
+    fn visible(&self) -> Option<&Condition> {
+        match self {
+            Self::Button(button)       => button.visible.as_ref(),
+            Self::Label(label)         => label.visible.as_ref(),
+            Self::EditableLabel(label) => label.visible.as_ref(),
+            Self::PasswordEdit(edit)   => edit.visible.as_ref(),
+            Self::NumericEdit(edit)    => edit.visible.as_ref(),
+            Self::SettingRow(row)      => row.visible.as_ref(),
+            Self::Separator(separator) => separator.visible.as_ref(),
+            Self::Spinner(spinner)     => spinner.visible.as_ref(),
+            Self::Gauge(gauge)         => gauge.visible.as_ref(),
+            Self::DragVector(drag)     => drag.visible.as_ref(),
+            Self::Layout(layout)       => layout.visible.as_ref(),
+            Self::Grid(grid)           => grid.visible.as_ref(),
+            Self::Columns(columns)     => columns.visible.as_ref(),
+            Self::Stack(stack)         => stack.visible.as_ref(),
+            Self::Place(place)         => place.visible.as_ref(),
+            Self::ScrollArea(scroll)   => scroll.visible.as_ref(),
+            Self::Frame(frame)         => frame.visible.as_ref(),
+            #[cfg(feature = "egui_extras")]
+            Self::Table(table)         => table.visible.as_ref(),
+            #[cfg(feature = "egui_plot")]
+            Self::Plot(plot)           => plot.visible.as_ref(),
+            Self::Modal(modal)         => Some(&modal.visible),
+            Self::Tree(tree)           => tree.visible.as_ref(),
+            Self::ListBox(list_box)    => list_box.visible.as_ref(),
+            Self::SlotGrid(slot_grid)  => slot_grid.visible.as_ref(),
+            Self::ChatLog(chat_log)    => chat_log.visible.as_ref(),
+            Self::Each(_) | Self::Match(_) | Self::EndRow(_) | Self::Custom(..) => None,
+        }
+    }
+
+    fn groups(&self) -> &[String] {
+        match self {
+            Self::Button(button)       => &button.groups,
+            Self::Label(label)         => &label.groups,
+            Self::EditableLabel(label) => &label.groups,
+            Self::PasswordEdit(edit)   => &edit.groups,
+            Self::NumericEdit(edit)    => &edit.groups,
+            Self::SettingRow(row)      => &row.groups,
+            Self::Separator(separator) => &separator.groups,
+            Self::Spinner(spinner)     => &spinner.groups,
+            Self::Gauge(gauge)         => &gauge.groups,
+            Self::DragVector(drag)     => &drag.groups,
+            Self::Layout(layout)       => &layout.groups,
+            Self::Grid(grid)           => &grid.groups,
+            Self::Columns(columns)     => &columns.groups,
+            Self::Stack(stack)         => &stack.groups,
+            Self::Place(place)         => &place.groups,
+            Self::ScrollArea(scroll)   => &scroll.groups,
+            Self::Frame(frame)         => &frame.groups,
+            #[cfg(feature = "egui_extras")]
+            Self::Table(table)         => &table.groups,
+            #[cfg(feature = "egui_plot")]
+            Self::Plot(plot)           => &plot.groups,
+            Self::Modal(modal)         => &modal.groups,
+            Self::Tree(tree)           => &tree.groups,
+            Self::ListBox(list_box)    => &list_box.groups,
+            Self::SlotGrid(slot_grid)  => &slot_grid.groups,
+            Self::ChatLog(chat_log)    => &chat_log.groups,
+            Self::Each(_) | Self::Match(_) | Self::EndRow(_) | Self::Custom(..) => &[],
+        }
+    }
+
+    fn transition(&self) -> Option<&Transition> {
+        match self {
+            Self::Button(button)       => button.transition.as_ref(),
+            Self::Label(label)         => label.transition.as_ref(),
+            Self::EditableLabel(label) => label.transition.as_ref(),
+            Self::PasswordEdit(edit)   => edit.transition.as_ref(),
+            Self::NumericEdit(edit)    => edit.transition.as_ref(),
+            Self::SettingRow(row)      => row.transition.as_ref(),
+            Self::Separator(separator) => separator.transition.as_ref(),
+            Self::Spinner(spinner)     => spinner.transition.as_ref(),
+            Self::Gauge(gauge)         => gauge.transition.as_ref(),
+            Self::DragVector(drag)     => drag.transition.as_ref(),
+            Self::Layout(layout)       => layout.transition.as_ref(),
+            Self::Grid(grid)           => grid.transition.as_ref(),
+            Self::Columns(columns)     => columns.transition.as_ref(),
+            Self::Stack(stack)         => stack.transition.as_ref(),
+            Self::Place(place)         => place.transition.as_ref(),
+            Self::ScrollArea(scroll)   => scroll.transition.as_ref(),
+            Self::Frame(frame)         => frame.transition.as_ref(),
+            #[cfg(feature = "egui_extras")]
+            Self::Table(table)         => table.transition.as_ref(),
+            #[cfg(feature = "egui_plot")]
+            Self::Plot(plot)           => plot.transition.as_ref(),
+            Self::Modal(modal)         => modal.transition.as_ref(),
+            Self::Tree(tree)           => tree.transition.as_ref(),
+            Self::ListBox(list_box)    => list_box.transition.as_ref(),
+            Self::SlotGrid(slot_grid)  => slot_grid.transition.as_ref(),
+            Self::ChatLog(chat_log)    => chat_log.transition.as_ref(),
+            Self::Each(_) | Self::Match(_) | Self::EndRow(_) | Self::Custom(..) => None,
+        }
+    }
+
+    fn size_limits(&self) -> Option<&SizeLimits> {
+        match self {
+            Self::Button(button)       => Some(&button.size_limits),
+            Self::Label(label)         => Some(&label.size_limits),
+            Self::EditableLabel(label) => Some(&label.size_limits),
+            Self::PasswordEdit(edit)   => Some(&edit.size_limits),
+            Self::NumericEdit(edit)    => Some(&edit.size_limits),
+            Self::SettingRow(row)      => Some(&row.size_limits),
+            Self::Separator(separator) => Some(&separator.size_limits),
+            Self::Spinner(spinner)     => Some(&spinner.size_limits),
+            Self::Gauge(gauge)         => Some(&gauge.size_limits),
+            Self::DragVector(drag)     => Some(&drag.size_limits),
+            Self::Layout(layout)       => Some(&layout.size_limits),
+            Self::Grid(grid)           => Some(&grid.size_limits),
+            Self::Columns(columns)     => Some(&columns.size_limits),
+            Self::Stack(stack)         => Some(&stack.size_limits),
+            Self::ScrollArea(scroll)   => Some(&scroll.size_limits),
+            Self::Frame(frame)         => Some(&frame.size_limits),
+            #[cfg(feature = "egui_extras")]
+            Self::Table(table)         => Some(&table.size_limits),
+            #[cfg(feature = "egui_plot")]
+            Self::Plot(plot)           => Some(&plot.size_limits),
+            Self::Place(_) | Self::Modal(_) | Self::Tree(_) | Self::ListBox(_) | Self::SlotGrid(_) | Self::ChatLog(_) => None,
+            Self::Each(_) | Self::Match(_) | Self::EndRow(_) | Self::Custom(..) => None,
+        }
+    }
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        // bindings that fail to resolve don't hide the widget
+        let visible = match self.visible() {
+            Some(visible) => visible.resolve(data).unwrap_or(true),
+            None => true,
+        };
+        // widgets in hidden `UiconfGroups` are hidden the same way, with transitions if they have one
+        let groups = self.groups();
+        let visible = visible && (groups.is_empty() || UiconfGroups::get(ui.ctx()).map_or(true, |all| all.are_visible(groups)));
+
+        if let Some(transition) = self.transition() {
+            let id = self.id().map_or_else(|| ui.next_auto_id(), WidgetId::id);
+            transition.show(ui, id, visible, |ui| self.show_scoped(data, ui));
+        } else if visible {
+            self.show_scoped(data, ui);
+        }
+    }
+
+    fn show_scoped(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        if let Some(id) = self.id() {
+            let inner = ui.push_id(id.id(), |ui| self.show_limited(data, ui));
+            // containers don't have a response of their own, so the area they occupy is used instead
+            WidgetResponse::record(ui, &id.0, &inner.inner.unwrap_or(inner.response));
+        } else {
+            self.show_limited(data, ui);
+        }
+    }
+
+    fn show_limited(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
+        match self.size_limits().filter(|limits| !limits.is_empty()) {
+            Some(limits) => limits.show(ui, |ui| self.show_with_ratio(data, ui)),
+            None         => self.show_with_ratio(data, ui),
+        }
+    }
+
+    fn show_with_ratio(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
+        let aspect_ratio = match self {
+            Self::Layout(layout)   => layout.aspect_ratio.as_ref(),
+            Self::Grid(grid)       => grid.aspect_ratio.as_ref(),
+            Self::Columns(columns) => columns.aspect_ratio.as_ref(),
+            Self::Stack(stack)     => stack.aspect_ratio.as_ref(),
+            Self::Frame(frame)     => frame.aspect_ratio.as_ref(),
+            _ => None,
+        };
+        match aspect_ratio {
+            Some(aspect_ratio) => aspect_ratio.show(ui, |ui| self.show_enabled(data, ui)),
+            None               => self.show_enabled(data, ui),
+        }
+    }
+
+    // widget is always wrapped in a scope, so that layout doesn't change when binding flips
+    fn show_enabled(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
+        if let Some(enabled) = self.enabled() {
+            let enabled = enabled.resolve(data).unwrap_or(true);
+            ui.add_enabled_ui(enabled, |ui| self.show_widget(data, ui)).inner
+        } else {
+            self.show_widget(data, ui)
         }
     }
 
-    fn show(&self, data: &mut dyn Reflect, ui: &mut egui::Ui) {
+    fn show_widget(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
         match self {
-            Self::Button(button)       => button.show(data, ui),
-            Self::Label(label)         => label.show(data, ui),
-            Self::Separator(separator) => separator.show(data, ui),
+            Self::Button(button)       => return button.show(data, ui),
+            Self::Label(label)         => return label.show(data, ui),
+            Self::EditableLabel(label) => return label.show(data, ui),
+            Self::PasswordEdit(edit)   => return edit.show(data, ui),
+            Self::NumericEdit(edit)    => return edit.show(data, ui),
+            Self::SettingRow(row)      => return row.show(data, ui),
+            Self::Separator(separator) => return separator.show(data, ui),
+            Self::Spinner(spinner)     => return spinner.show(data, ui),
+            Self::Gauge(gauge)         => return gauge.show(data, ui),
+            Self::DragVector(drag)     => return drag.show(data, ui),
             Self::Layout(layout)       => layout.show(data, ui),
             Self::Grid(grid)           => grid.show(data, ui),
+            Self::Columns(columns)     => columns.show(data, ui),
+            Self::Stack(stack)         => stack.show(data, ui),
+            Self::Place(place)         => place.show(data, ui),
+            Self::ScrollArea(scroll)   => scroll.show(data, ui),
+            Self::Frame(frame)         => frame.show(data, ui),
+            Self::Modal(modal)         => modal.show(data, ui),
+            #[cfg(feature = "egui_extras")]
+            Self::Table(table)         => table.show(data, ui),
+            #[cfg(feature = "egui_plot")]
+            Self::Plot(plot)           => plot.show(data, ui),
             Self::Each(each)           => each.show(data, ui),
+            Self::Match(match_)        => match_.show(data, ui),
+            Self::Tree(tree)           => tree.show(data, ui),
+            Self::ListBox(list_box)    => list_box.show(data, ui),
+            Self::SlotGrid(slot_grid)  => slot_grid.show(data, ui),
+            Self::ChatLog(chat_log)    => chat_log.show(data, ui),
             Self::EndRow(_)            => ui.end_row(),
+            Self::Custom(_, widget)    => widget.show(data, ui),
         }
+        // containers don't have a response of their own
+        None
     }
 }
 
@@ -319,28 +1596,77 @@ impl ContentWidget {
 
 #[derive(Debug)]
 pub struct Layout {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
     pub layout: egui::Layout,
-    pub visible: Option<Binding<bool>>,
+    // distributes free space between children, instead of packing them at `main_align`
+    pub justify: Option<Justify>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub aspect_ratio: Option<AspectRatio>,
+    pub size_limits: SizeLimits,
     pub content: Content,
 }
 
 impl Layout {
     const FIELDS: &'static [&'static str] = const_concat!(
-        &["main_dir", "main_wrap", "main_align", "main_justify", "cross_align", "cross_justify", "visible"],
+        &["id", "enabled", "transition", "main_dir", "main_wrap", "main_align", "main_justify", "cross_align", "cross_justify", "justify", "aspect_ratio", "visible", "groups"],
+        SizeLimits::FIELDS,
         ContentWidget::FIELDS,
     );
 
-    fn show(&self, data: &mut dyn Reflect, ui: &mut egui::Ui) {
-        if let Some(visible) = &self.visible {
-            if let Ok(visible) = visible.resolve(data) {
-                if !visible { return; }
-            }
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        if let Some(justify) = self.justify {
+            self.show_justified(justify, data, ui);
+            return;
         }
 
         ui.with_layout(self.layout, |ui| {
             self.content.show(data, ui);
         });
     }
+
+    // Gaps are computed from sizes children had in the previous frame, so the first frame is
+    // laid out with a guess, and another one is requested whenever sizes change.
+    fn show_justified(&self, justify: Justify, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        let id = ui.next_auto_id().with("uiconf_justify");
+        let sizes = ui.data(|d| d.get_temp::<Vec<f32>>(id)).unwrap_or_default();
+
+        ui.with_layout(self.layout, |ui| {
+            let horizontal = ui.layout().is_horizontal();
+            let main_size = |size: egui::Vec2| if horizontal { size.x } else { size.y };
+
+            // hidden children take no space, and don't get gaps around them
+            let shown = sizes.iter().filter(|&&size| size > 0.).count();
+            let free = (main_size(ui.available_size_before_wrap()) - sizes.iter().sum::<f32>()).max(0.);
+            let (before, between) = justify.gaps(free, shown);
+
+            // gaps replace item spacing between children, but not inside of them
+            let item_spacing = ui.spacing().item_spacing;
+            ui.spacing_mut().item_spacing = egui::Vec2::ZERO;
+            ui.add_space(before);
+
+            let mut measured = Vec::with_capacity(self.content.0.len());
+            let mut first = true;
+            for (index, widget) in self.content.0.iter().enumerate() {
+                if sizes.get(index).is_some_and(|&size| size > 0.) {
+                    if !first { ui.add_space(between); }
+                    first = false;
+                }
+                let rect = ui.scope(|ui| {
+                    ui.spacing_mut().item_spacing = item_spacing;
+                    widget.show(data, ui);
+                }).response.rect;
+                measured.push(main_size(rect.size()));
+            }
+
+            if measured != sizes {
+                ui.ctx().request_repaint();
+                ui.data_mut(|d| d.insert_temp(id, measured));
+            }
+        });
+    }
 }
 
 impl ReadUiconf for Layout {
@@ -401,33 +1727,49 @@ impl ReadUiconf for Layout {
             }
         }
 
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
         let mut layout = egui::Layout::default();
+        let mut justify = None;
         let mut visible = None;
+        let mut groups = vec![];
+        let mut aspect_ratio = None;
+        let mut size_limits = SizeLimits::default();
         let mut content = vec![];
         let mut last_content = None;
 
         for (key, value) in value.read_object()? {
             let mut is_content = false;
             match &*key {
+                "id"            => { id                   = Some(value.read()?); }
+                "enabled"       => { enabled              = Some(value.read()?); }
+                "transition"    => { transition           = Some(value.read()?); }
                 "main_dir"      => { layout.main_dir      = value.read::<Direction>()?.into(); }
                 "main_wrap"     => { layout.main_wrap     = value.read()?; }
                 "main_align"    => { layout.main_align    = value.read::<Align>()?.into(); }
                 "main_justify"  => { layout.main_justify  = value.read()?; }
                 "cross_align"   => { layout.cross_align   = value.read::<Align>()?.into(); }
                 "cross_justify" => { layout.cross_justify = value.read()?; }
+                "justify"       => { justify              = Some(value.read()?); }
+                "aspect_ratio"  => { aspect_ratio         = Some(value.read()?); }
                 "visible"       => { visible              = Some(value.read()?); }
+                "groups"        => { groups               = value.read()?; }
                 str => {
-                    if ContentWidget::FIELDS.contains(&str) {
+                    if SizeLimits::FIELDS.contains(&str) {
+                        size_limits.read_map_value(str, &value)?;
+                    } else if ContentWidget::is_field(&value, str) {
                         content.push(ContentWidget::read_map_value(str, &value)?);
                         last_content = Some(str.to_owned());
                         is_content = true;
                     } else {
-                        return Err(Error::unknown_field(&value, str, Layout::FIELDS));
+                        value.skip_unknown_field(str, Layout::FIELDS)?;
+                        continue;
                     }
                 }
             }
 
-            if !is_content && last_content.is_some() {
+            if !is_content && last_content.is_some() && !value.is_flag() {
                 return Err(Error::custom(&value, format!(
                     "all layout properties should be above content, but `{}` is located after `{}`",
                     key, last_content.unwrap(),
@@ -436,13 +1778,134 @@ impl ReadUiconf for Layout {
         }
 
         Ok(Layout {
+            id,
+            enabled,
+            transition,
             layout,
+            justify,
             visible,
+            groups,
+            aspect_ratio,
+            size_limits,
             content: Content(content),
         })
     }
 }
 
+impl ToUiconf for Layout {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        fn direction(dir: egui::Direction) -> &'static str {
+            match dir {
+                egui::Direction::LeftToRight => "left_to_right",
+                egui::Direction::RightToLeft => "right_to_left",
+                egui::Direction::TopDown     => "top_down",
+                egui::Direction::BottomUp    => "bottom_up",
+            }
+        }
+
+        fn align(align: egui::Align) -> &'static str {
+            match align {
+                egui::Align::Min    => "min",
+                egui::Align::Center => "center",
+                egui::Align::Max    => "max",
+            }
+        }
+
+        // only write properties that differ from defaults
+        let default = egui::Layout::default();
+
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if self.layout.main_dir != default.main_dir {
+                writer.field_with("main_dir", |w| w.scalar(direction(self.layout.main_dir)));
+            }
+            if self.layout.main_wrap != default.main_wrap {
+                writer.field("main_wrap", &self.layout.main_wrap);
+            }
+            if self.layout.main_align != default.main_align {
+                writer.field_with("main_align", |w| w.scalar(align(self.layout.main_align)));
+            }
+            if self.layout.main_justify != default.main_justify {
+                writer.field("main_justify", &self.layout.main_justify);
+            }
+            if self.layout.cross_align != default.cross_align {
+                writer.field_with("cross_align", |w| w.scalar(align(self.layout.cross_align)));
+            }
+            if self.layout.cross_justify != default.cross_justify {
+                writer.field("cross_justify", &self.layout.cross_justify);
+            }
+            if let Some(justify) = &self.justify {
+                writer.field("justify", justify);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            if let Some(aspect_ratio) = &self.aspect_ratio {
+                writer.field("aspect_ratio", aspect_ratio);
+            }
+            self.size_limits.write_map_values(writer);
+            self.content.write_map_values(writer);
+        });
+    }
+}
+
+//
+// Justify
+//
+
+// `justify = space_between`, how free space of a layout is split between its children,
+// e.g. title on the left and close button on the right
+#[derive(EnumString, EnumVariantNames, Display, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum Justify {
+    // gaps only between children, first and last ones touch the edges
+    SpaceBetween,
+    // each child gets equal space on both sides, so gaps at the edges are half as big
+    SpaceAround,
+    // all gaps are equal, including ones at the edges
+    SpaceEvenly,
+}
+
+impl Justify {
+    // gap before the first child, and gap between children
+    fn gaps(self, free: f32, count: usize) -> (f32, f32) {
+        if count == 0 { return (0., 0.); }
+        let count = count as f32;
+        match self {
+            Self::SpaceBetween if count > 1. => (0., free / (count - 1.)),
+            Self::SpaceBetween => (0., 0.),
+            Self::SpaceAround  => (free / count / 2., free / count),
+            Self::SpaceEvenly  => (free / (count + 1.), free / (count + 1.)),
+        }
+    }
+}
+
+impl ReadUiconf for Justify {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let name = value.read_string()?;
+        Self::from_str(&name).map_err(|_| {
+            Error::unknown_variant(value, &name, Self::VARIANTS)
+        })
+    }
+}
+
+impl ToUiconf for Justify {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.scalar(self);
+    }
+}
+
 //
 // Grid
 //
@@ -450,29 +1913,30 @@ impl ReadUiconf for Layout {
 #[derive(Debug)]
 pub struct Grid {
     id: egui::Id,
+    pub explicit_id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
     pub num_columns: Option<u32>,
     pub striped: bool,
     pub spacing: Option<egui::Vec2>,
-    pub visible: Option<Binding<bool>>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub aspect_ratio: Option<AspectRatio>,
+    pub size_limits: SizeLimits,
     pub content: Content,
 }
 
 impl Grid {
     const FIELDS: &'static [&'static str] = const_concat!(
-        &["num_columns", "striped", "spacing", "visible"],
+        &["id", "enabled", "transition", "num_columns", "striped", "spacing", "aspect_ratio", "visible", "groups"],
+        SizeLimits::FIELDS,
         ContentWidget::FIELDS,
     );
 
-    fn show(&self, data: &mut dyn Reflect, ui: &mut egui::Ui) {
-        if let Some(visible) = &self.visible {
-            if let Ok(visible) = visible.resolve(data) {
-                if !visible { return; }
-            }
-        }
-
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
         // need to hash both position in config file (multiple grids in the same window)
         // and data model pointer (iterating over the same grid multiple times with each)
-        let mut grid = egui::Grid::new((self.id, data as *mut dyn Reflect));
+        let mut grid = egui::Grid::new((self.id, data.data_ptr()));
         if let Some(num_columns) = self.num_columns {
             grid = grid.num_columns(num_columns as usize);
         }
@@ -489,32 +1953,46 @@ impl Grid {
 
 impl ReadUiconf for Grid {
     fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut explicit_id = None;
+        let mut enabled = None;
+        let mut transition = None;
         let mut num_columns = None;
         let mut striped = false;
         let mut spacing = None;
         let mut visible = None;
+        let mut groups = vec![];
+        let mut aspect_ratio = None;
+        let mut size_limits = SizeLimits::default();
         let mut content = vec![];
         let mut last_content = None;
 
         for (key, value) in value.read_object()? {
             let mut is_content = false;
             match &*key {
-                "num_columns" => { num_columns = Some(value.read()?); }
-                "striped"     => { striped     = value.read()?; }
-                "spacing"     => { spacing     = Some(value.read::<Size::<{ SIZE_ANY_DISALLOWED }>>()?.0); }
-                "visible"     => { visible     = Some(value.read()?); }
+                "id"           => { explicit_id  = Some(value.read()?); }
+                "enabled"      => { enabled      = Some(value.read()?); }
+                "transition"   => { transition   = Some(value.read()?); }
+                "num_columns"  => { num_columns  = Some(value.read()?); }
+                "striped"      => { striped      = value.read()?; }
+                "spacing"      => { spacing      = Some(value.read::<Size::<{ SIZE_ANY_DISALLOWED }>>()?.0); }
+                "aspect_ratio" => { aspect_ratio = Some(value.read()?); }
+                "visible"      => { visible      = Some(value.read()?); }
+                "groups"       => { groups       = value.read()?; }
                 str => {
-                    if ContentWidget::FIELDS.contains(&str) {
+                    if SizeLimits::FIELDS.contains(&str) {
+                        size_limits.read_map_value(str, &value)?;
+                    } else if ContentWidget::is_field(&value, str) {
                         content.push(ContentWidget::read_map_value(str, &value)?);
                         last_content = Some(str.to_owned());
                         is_content = true;
                     } else {
-                        return Err(Error::unknown_field(&value, str, Grid::FIELDS));
+                        value.skip_unknown_field(str, Grid::FIELDS)?;
+                        continue;
                     }
                 }
             }
 
-            if !is_content && last_content.is_some() {
+            if !is_content && last_content.is_some() && !value.is_flag() {
                 return Err(Error::custom(&value, format!(
                     "all grid properties should be above content, but `{}` is located after `{}`",
                     key, last_content.unwrap(),
@@ -523,794 +2001,7098 @@ impl ReadUiconf for Grid {
         }
 
         Ok(Grid {
-            id: value.get_id(),
+            id: WidgetId::or_path_id(&explicit_id, value),
+            explicit_id,
+            enabled,
+            transition,
             num_columns,
             striped,
             spacing,
             visible,
+            groups,
+            aspect_ratio,
+            size_limits,
             content: Content(content),
         })
     }
 }
 
+impl ToUiconf for Grid {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.explicit_id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(num_columns) = &self.num_columns {
+                writer.field("num_columns", num_columns);
+            }
+            if self.striped {
+                writer.field("striped", &self.striped);
+            }
+            if let Some(spacing) = self.spacing {
+                writer.field("spacing", &Size::<{ SIZE_ANY_DISALLOWED }>(spacing));
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            if let Some(aspect_ratio) = &self.aspect_ratio {
+                writer.field("aspect_ratio", aspect_ratio);
+            }
+            self.size_limits.write_map_values(writer);
+            self.content.write_map_values(writer);
+        });
+    }
+}
+
 //
-// Each
+// Columns
 //
 
+// `columns = { column = { ... } column = { ... } }`, available width is split into equal columns,
+// one for each `column` block
 #[derive(Debug)]
-pub struct Each {
-    pub binding: BindingRef<dyn Reflect>,
-    pub content: Content,
+pub struct Columns {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub aspect_ratio: Option<AspectRatio>,
+    pub size_limits: SizeLimits,
+    pub columns: Vec<Content>,
 }
 
-impl Each {
+impl Columns {
     const FIELDS: &'static [&'static str] = const_concat!(
-        &["in"],
-        ContentWidget::FIELDS,
+        &["id", "enabled", "transition", "aspect_ratio", "visible", "groups", "column"],
+        SizeLimits::FIELDS,
     );
 
-    fn show(&self, data: &mut dyn Reflect, ui: &mut egui::Ui) {
-        if let Ok(array) = self.binding.resolve_list_mut(data) {
-            for idx in 0..array.len() {
-                let new_data = array.get_mut(idx).unwrap();
-                self.content.show(new_data, ui);
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        ui.columns(self.columns.len(), |columns| {
+            for (column, content) in columns.iter_mut().zip(self.columns.iter()) {
+                content.show(data, column);
             }
-        }
+        });
     }
 }
 
-impl ReadUiconf for Each {
+impl ReadUiconf for Columns {
     fn read_uiconf(value: &Reader) -> Result<Self, Error> {
-        let mut binding = None;
-        let mut content = vec![];
-        let mut last_content = None;
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut aspect_ratio = None;
+        let mut size_limits = SizeLimits::default();
+        let mut columns = vec![];
 
         for (key, value) in value.read_object()? {
-            let mut is_content = false;
             match &*key {
-                "in" => { binding = Some(value.read()?); }
+                "id"           => { id           = Some(value.read()?); }
+                "enabled"      => { enabled      = Some(value.read()?); }
+                "transition"   => { transition   = Some(value.read()?); }
+                "aspect_ratio" => { aspect_ratio = Some(value.read()?); }
+                "visible"      => { visible      = Some(value.read()?); }
+                "groups"       => { groups       = value.read()?; }
+                "column"       => { columns.push(value.read()?); }
                 str => {
-                    if ContentWidget::FIELDS.contains(&str) {
-                        content.push(ContentWidget::read_map_value(str, &value)?);
-                        last_content = Some(str.to_owned());
-                        is_content = true;
+                    if SizeLimits::FIELDS.contains(&str) {
+                        size_limits.read_map_value(str, &value)?;
                     } else {
-                        return Err(Error::unknown_field(&value, str, Each::FIELDS));
+                        value.skip_unknown_field(str, Columns::FIELDS)?;
                     }
                 }
             }
+        }
 
-            if !is_content && last_content.is_some() {
-                return Err(Error::custom(&value, format!(
-                    "all each properties should be above content, but `{}` is located after `{}`",
-                    key, last_content.unwrap(),
-                )));
-            }
+        // egui divides available width by the number of columns
+        if columns.is_empty() {
+            return Err(Error::missing_field(value, "column"));
         }
 
-        let binding = binding.ok_or_else(|| Error::missing_field(value, "in"))?;
+        Ok(Columns { id, enabled, transition, visible, groups, aspect_ratio, size_limits, columns })
+    }
+}
 
-        Ok(Each {
-            binding,
-            content: Content(content),
-        })
+impl ToUiconf for Columns {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            if let Some(aspect_ratio) = &self.aspect_ratio {
+                writer.field("aspect_ratio", aspect_ratio);
+            }
+            self.size_limits.write_map_values(writer);
+            for column in self.columns.iter() {
+                writer.field("column", column);
+            }
+        });
     }
 }
 
 //
-// Response
+// Stack
 //
 
+// `stack = { image = { ... } layer = { align = { right top } label = "3" } }`, children are drawn
+// on top of each other in the same rect, later ones on top; each child widget is a layer of its own,
+// and `layer` blocks group several widgets under their own alignment
 #[derive(Debug)]
-pub struct Response(Vec<ResponseProperty>);
+pub struct Stack {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    // alignment of layers without their own, left top by default
+    pub align: Option<egui::Align2>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub aspect_ratio: Option<AspectRatio>,
+    pub size_limits: SizeLimits,
+    pub layers: Vec<StackLayer>,
+}
 
-impl Response {
-    fn process(&self, data: &mut dyn Reflect, mut response: egui::Response) {
-        for prop in self.0.iter() {
-            use ResponseProperty as P;
-            match prop {
-                P::Clicked(trigger) => {
-                    if let Ok(clicked) = trigger.resolve_mut(data) {
-                        if response.clicked() { clicked.trigger(); }
-                    }
-                }
-                P::SecondaryClicked(trigger) => {
-                    if let Ok(clicked) = trigger.resolve_mut(data) {
-                        if response.secondary_clicked() { clicked.trigger(); }
-                    }
-                }
-                P::MiddleClicked(trigger) => {
-                    if let Ok(clicked) = trigger.resolve_mut(data) {
-                        if response.middle_clicked() { clicked.trigger(); }
-                    }
-                }
-                P::DoubleClicked(trigger) => {
-                    if let Ok(clicked) = trigger.resolve_mut(data) {
-                        if response.double_clicked() { clicked.trigger(); }
+#[derive(Debug)]
+pub struct StackLayer {
+    pub align: Option<egui::Align2>,
+    pub content: Content,
+}
+
+impl Stack {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "align", "aspect_ratio", "visible", "groups", "layer"],
+        SizeLimits::FIELDS,
+        ContentWidget::FIELDS,
+    );
+
+    // Stack is as big as its biggest layer, and layers are aligned using sizes they had
+    // in the previous frame, another frame is requested whenever sizes change.
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        let id = ui.next_auto_id().with("uiconf_stack");
+        let sizes = ui.data(|d| d.get_temp::<Vec<egui::Vec2>>(id)).unwrap_or_default();
+        let size = sizes.iter().fold(egui::Vec2::ZERO, |size, layer| size.max(*layer));
+
+        let available = ui.available_size_before_wrap();
+        let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+
+        let mut measured = Vec::with_capacity(self.layers.len());
+        for (index, layer) in self.layers.iter().enumerate() {
+            let align = layer.align.or(self.align).unwrap_or(egui::Align2::LEFT_TOP);
+            let layer_size = sizes.get(index).copied().unwrap_or_default();
+            let pos = align.align_size_within_rect(layer_size, rect).min;
+
+            // layer can grow past the stack, so that its size is known next frame
+            let max_rect = egui::Rect::from_min_size(pos, available);
+            let mut child = ui.child_ui_with_id_source(max_rect, *ui.layout(), id.with(index));
+            layer.content.show(data, &mut child);
+            measured.push(child.min_rect().size());
+        }
+
+        if measured != sizes {
+            ui.ctx().request_repaint();
+            ui.data_mut(|d| d.insert_temp(id, measured));
+        }
+    }
+}
+
+impl ReadUiconf for Stack {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut align = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut aspect_ratio = None;
+        let mut size_limits = SizeLimits::default();
+        let mut layers = vec![];
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "id"           => { id           = Some(value.read()?); }
+                "enabled"      => { enabled      = Some(value.read()?); }
+                "transition"   => { transition   = Some(value.read()?); }
+                "align"        => { align        = Some(value.read::<Align2>()?.0); }
+                "aspect_ratio" => { aspect_ratio = Some(value.read()?); }
+                "visible"      => { visible      = Some(value.read()?); }
+                "groups"       => { groups       = value.read()?; }
+                "layer"        => { layers.push(value.read()?); }
+                str => {
+                    if SizeLimits::FIELDS.contains(&str) {
+                        size_limits.read_map_value(str, &value)?;
+                    } else if ContentWidget::is_field(&value, str) {
+                        let widget = ContentWidget::read_map_value(str, &value)?;
+                        layers.push(StackLayer { align: None, content: Content(vec![widget]) });
+                    } else {
+                        value.skip_unknown_field(str, Stack::FIELDS)?;
                     }
                 }
-                P::TripleClicked(trigger) => {
-                    if let Ok(clicked) = trigger.resolve_mut(data) {
-                        if response.triple_clicked() { clicked.trigger(); }
-                    }
-                }
-                P::ClickedElsewhere(trigger) => {
-                    if let Ok(clicked) = trigger.resolve_mut(data) {
-                        if response.clicked_elsewhere() { clicked.trigger(); }
-                    }
+            }
+        }
+
+        Ok(Stack { id, enabled, transition, align, visible, groups, aspect_ratio, size_limits, layers })
+    }
+}
+
+impl ToUiconf for Stack {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(align) = self.align {
+                writer.field("align", &Align2(align));
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            if let Some(aspect_ratio) = &self.aspect_ratio {
+                writer.field("aspect_ratio", aspect_ratio);
+            }
+            self.size_limits.write_map_values(writer);
+            for layer in self.layers.iter() {
+                // single widgets are written as is, unless they have their own alignment
+                if layer.align.is_none() && layer.content.0.len() == 1 {
+                    layer.content.write_map_values(writer);
+                } else {
+                    writer.field("layer", layer);
                 }
-                P::Hovered(trigger) => {
-                    if let Ok(hovered) = trigger.resolve_mut(data) {
-                        if response.hovered() { hovered.trigger(); }
+            }
+        });
+    }
+}
+
+impl StackLayer {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["align"],
+        ContentWidget::FIELDS,
+    );
+}
+
+impl ReadUiconf for StackLayer {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut align = None;
+        let mut widgets = vec![];
+
+        for (key, value) in value.read_object()? {
+            if key == "align" {
+                if align.is_some() { return Err(Error::duplicate_field(&value, "align")); }
+                align = Some(value.read::<Align2>()?.0);
+            } else if ContentWidget::is_field(&value, &key) {
+                widgets.push(ContentWidget::read_map_value(&key, &value)?);
+            } else {
+                value.skip_unknown_field(&key, StackLayer::FIELDS)?;
+            }
+        }
+
+        Ok(StackLayer { align, content: Content(widgets) })
+    }
+}
+
+impl ToUiconf for StackLayer {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(align) = self.align {
+                writer.field("align", &Align2(align));
+            }
+            self.content.write_map_values(writer);
+        });
+    }
+}
+
+//
+// Place
+//
+
+// `place = { pos = { 10 50% } size = { 200 any } label = ... }`, content is put at a fixed spot
+// of the window, percentages are relative to the window content; it takes no space in the layout,
+// so it can overlap other widgets
+#[derive(Debug)]
+pub struct Place {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub pos: [PlaceCoord; 2],
+    // content extends to the bottom right corner of the window by default
+    pub size: [PlaceCoord; 2],
+    pub content: Content,
+}
+
+impl Place {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "visible", "groups", "pos", "size"],
+        ContentWidget::FIELDS,
+    );
+
+    fn egui_id(layer: egui::LayerId) -> egui::Id {
+        egui::Id::new("uiconf_window_rect").with(layer)
+    }
+
+    // called by windows before their content is shown
+    fn set_window_rect(ui: &egui::Ui) {
+        let rect = ui.max_rect();
+        ui.data_mut(|d| d.insert_temp(Self::egui_id(ui.layer_id()), rect));
+    }
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        let window = ui.data(|d| d.get_temp::<egui::Rect>(Self::egui_id(ui.layer_id()))).unwrap_or_else(|| ui.max_rect());
+
+        let min = window.min + egui::vec2(
+            self.pos[0].resolve(window.width()).unwrap_or_default(),
+            self.pos[1].resolve(window.height()).unwrap_or_default(),
+        );
+        let max = egui::pos2(
+            self.size[0].resolve(window.width()).map_or(window.max.x, |width| min.x + width),
+            self.size[1].resolve(window.height()).map_or(window.max.y, |height| min.y + height),
+        );
+
+        let id = ui.next_auto_id().with("uiconf_place");
+        let mut child = ui.child_ui_with_id_source(egui::Rect::from_min_max(min, max), *ui.layout(), id);
+        self.content.show(data, &mut child);
+    }
+
+    fn read_coords(value: &Reader) -> Result<[PlaceCoord; 2], Error> {
+        const EXPECTED: &str = "{ x y }";
+        let mut seq = value.read_array()?;
+        let x = seq.next().ok_or_else(|| Error::invalid_length(value, 0, EXPECTED))?.read()?;
+        let y = seq.next().ok_or_else(|| Error::invalid_length(value, 1, EXPECTED))?.read()?;
+        if seq.next().is_some() {
+            return Err(Error::invalid_length(value, 3, EXPECTED));
+        }
+        Ok([x, y])
+    }
+}
+
+impl ReadUiconf for Place {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut pos = None;
+        let mut size = [PlaceCoord::Any; 2];
+        let mut content = vec![];
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "id"         => { id         = Some(value.read()?); }
+                "enabled"    => { enabled    = Some(value.read()?); }
+                "transition" => { transition = Some(value.read()?); }
+                "visible"    => { visible    = Some(value.read()?); }
+                "groups"     => { groups     = value.read()?; }
+                "pos"        => {
+                    let coords = Place::read_coords(&value)?;
+                    if coords.contains(&PlaceCoord::Any) {
+                        return Err(Error::custom(&value, "`any` is only allowed in `size`"));
                     }
+                    pos = Some(coords);
                 }
-                P::Highlighted(trigger) => {
-                    if let Ok(highlighted) = trigger.resolve_mut(data) {
-                        if response.highlighted() { highlighted.trigger(); }
+                "size"       => { size = Place::read_coords(&value)?; }
+                str => {
+                    if ContentWidget::is_field(&value, str) {
+                        content.push(ContentWidget::read_map_value(str, &value)?);
+                    } else {
+                        value.skip_unknown_field(str, Place::FIELDS)?;
                     }
                 }
-                P::Changed(trigger) => {
-                    if let Ok(changed) = trigger.resolve_mut(data) {
-                        if response.changed() { changed.trigger(); }
+            }
+        }
+
+        let pos = pos.ok_or_else(|| Error::missing_field(value, "pos"))?;
+
+        Ok(Place { id, enabled, transition, visible, groups, pos, size, content: Content(content) })
+    }
+}
+
+impl ToUiconf for Place {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            writer.field("pos", &self.pos.to_vec());
+            if self.size != [PlaceCoord::Any; 2] {
+                writer.field("size", &self.size.to_vec());
+            }
+            self.content.write_map_values(writer);
+        });
+    }
+}
+
+//
+// PlaceCoord
+//
+
+// `120` is in points, `50%` is relative to the size of the window, and `any` in size means "up to the edge"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaceCoord {
+    Points(f32),
+    Percent(f32),
+    Any,
+}
+
+impl PlaceCoord {
+    fn resolve(self, window: f32) -> Option<f32> {
+        match self {
+            Self::Points(points)   => Some(points),
+            Self::Percent(percent) => Some(window * percent / 100.),
+            Self::Any              => None,
+        }
+    }
+}
+
+impl ReadUiconf for PlaceCoord {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let scalar = value.read_string()?;
+        if scalar == "any" {
+            return Ok(Self::Any);
+        }
+        if let Some(percent) = scalar.strip_suffix('%') {
+            return percent.trim().parse().map(Self::Percent).map_err(|_| {
+                Error::invalid_value(value, &scalar, "number, percentage or `any`")
+            });
+        }
+        Ok(Self::Points(value.read()?))
+    }
+}
+
+impl ToUiconf for PlaceCoord {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        match self {
+            Self::Points(points)   => writer.scalar(points),
+            Self::Percent(percent) => writer.scalar(format!("{}%", percent)),
+            Self::Any              => writer.scalar("any"),
+        }
+    }
+}
+
+//
+// ScrollArea
+//
+
+// `scroll_area = { max_height = 300 stick_to_bottom = yes each = { ... } }`, content scrolls when it
+// doesn't fit; game code can move it with `scroll_to_top`, `scroll_to_bottom` and `scroll_offset`
+#[derive(Debug)]
+pub struct ScrollArea {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub hscroll: bool,
+    pub vscroll: bool,
+    // follows new content at the bottom, until user scrolls away from it
+    pub stick_to_bottom: bool,
+    // triggers are consumed here, so they aren't seen by the app
+    pub scroll_to_top: Option<BindingRef<Trigger>>,
+    pub scroll_to_bottom: Option<BindingRef<Trigger>>,
+    // vertical offset in points, written every frame, and applied when game code changes it
+    pub scroll_offset: Option<BindingRef<f32>>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub size_limits: SizeLimits,
+    pub content: Content,
+}
+
+impl ScrollArea {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "hscroll", "vscroll", "stick_to_bottom", "scroll_to_top", "scroll_to_bottom", "scroll_offset", "visible", "groups"],
+        SizeLimits::FIELDS,
+        ContentWidget::FIELDS,
+    );
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        let id = ui.next_auto_id();
+        // offset that was written into `scroll_offset` last time, anything else was set by game code
+        let written_id = id.with("uiconf_scroll_offset");
+
+        let mut triggered = |trigger: &Option<BindingRef<Trigger>>| {
+            trigger.as_ref().and_then(|trigger| trigger.resolve_mut(data).ok()).is_some_and(Trigger::check_reset)
+        };
+        let to_top = triggered(&self.scroll_to_top);
+        let to_bottom = triggered(&self.scroll_to_bottom);
+
+        let written = ui.data(|d| d.get_temp::<f32>(written_id));
+        let offset = self.scroll_offset.as_ref()
+            .and_then(|offset| offset.resolve_ref(data).ok().copied())
+            .filter(|offset| Some(*offset) != written);
+
+        let mut scroll = egui::ScrollArea::new([self.hscroll, self.vscroll])
+            .id_source(id)
+            .stick_to_bottom(self.stick_to_bottom);
+        let offset = if to_top { Some(0.) } else { offset };
+        if let Some(offset) = offset {
+            // otherwise egui keeps the area stuck to the bottom, and the offset is lost
+            scroll = scroll.vertical_scroll_offset(offset).stick_to_bottom(false);
+        }
+
+        let output = scroll.show(ui, |ui| {
+            self.content.show(data, ui);
+            if to_bottom {
+                ui.scroll_to_cursor(Some(egui::Align::Max));
+            }
+        });
+
+        if let Some(Ok(offset)) = self.scroll_offset.as_ref().map(|offset| offset.resolve_mut(data)) {
+            if *offset != output.state.offset.y {
+                *offset = output.state.offset.y;
+            }
+            ui.data_mut(|d| d.insert_temp(written_id, output.state.offset.y));
+        }
+        WidgetState::Scroll.record(ui.ctx(), output.id);
+    }
+}
+
+impl ReadUiconf for ScrollArea {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut hscroll = false;
+        let mut vscroll = true;
+        let mut stick_to_bottom = false;
+        let mut scroll_to_top = None;
+        let mut scroll_to_bottom = None;
+        let mut scroll_offset = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut size_limits = SizeLimits::default();
+        let mut content = vec![];
+        let mut last_content = None;
+
+        for (key, value) in value.read_object()? {
+            let mut is_content = false;
+            match &*key {
+                "id"               => { id               = Some(value.read()?); }
+                "enabled"          => { enabled          = Some(value.read()?); }
+                "transition"       => { transition       = Some(value.read()?); }
+                "hscroll"          => { hscroll          = value.read()?; }
+                "vscroll"          => { vscroll          = value.read()?; }
+                "stick_to_bottom"  => { stick_to_bottom  = value.read()?; }
+                "scroll_to_top"    => { scroll_to_top    = Some(value.read()?); }
+                "scroll_to_bottom" => { scroll_to_bottom = Some(value.read()?); }
+                "scroll_offset"    => { scroll_offset    = Some(value.read()?); }
+                "visible"          => { visible          = Some(value.read()?); }
+                "groups"           => { groups           = value.read()?; }
+                str => {
+                    if SizeLimits::FIELDS.contains(&str) {
+                        size_limits.read_map_value(str, &value)?;
+                    } else if ContentWidget::is_field(&value, str) {
+                        content.push(ContentWidget::read_map_value(str, &value)?);
+                        last_content = Some(str.to_owned());
+                        is_content = true;
+                    } else {
+                        value.skip_unknown_field(str, ScrollArea::FIELDS)?;
+                        continue;
                     }
                 }
-                P::OnHover(content) => {
-                    response = response.on_hover_ui(|ui| {
-                        content.show(data, ui);
-                    });
-                }
-                P::OnDisabledHover(content) => {
-                    response = response.on_disabled_hover_ui(|ui| {
-                        content.show(data, ui);
-                    });
-                }
-                P::OnHoverAtPointer(content) => {
-                    response = response.on_hover_ui_at_pointer(|ui| {
-                        content.show(data, ui);
-                    });
-                }
-                P::Highlight(highlight) => {
-                    if let Ok(highlight) = highlight.resolve(data) {
-                        if highlight { response = response.highlight(); }
+            }
+
+            if !is_content && last_content.is_some() && !value.is_flag() {
+                return Err(Error::custom(&value, format!(
+                    "all scroll_area properties should be above content, but `{}` is located after `{}`",
+                    key, last_content.unwrap(),
+                )));
+            }
+        }
+
+        Ok(ScrollArea {
+            id,
+            enabled,
+            transition,
+            hscroll,
+            vscroll,
+            stick_to_bottom,
+            scroll_to_top,
+            scroll_to_bottom,
+            scroll_offset,
+            visible,
+            groups,
+            size_limits,
+            content: Content(content),
+        })
+    }
+}
+
+impl ToUiconf for ScrollArea {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if self.hscroll {
+                writer.field("hscroll", &self.hscroll);
+            }
+            if !self.vscroll {
+                writer.field("vscroll", &self.vscroll);
+            }
+            if self.stick_to_bottom {
+                writer.field("stick_to_bottom", &self.stick_to_bottom);
+            }
+            if let Some(scroll_to_top) = &self.scroll_to_top {
+                writer.field("scroll_to_top", scroll_to_top);
+            }
+            if let Some(scroll_to_bottom) = &self.scroll_to_bottom {
+                writer.field("scroll_to_bottom", scroll_to_bottom);
+            }
+            if let Some(scroll_offset) = &self.scroll_offset {
+                writer.field("scroll_offset", scroll_offset);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            self.size_limits.write_map_values(writer);
+            self.content.write_map_values(writer);
+        });
+    }
+}
+
+//
+// Frame
+//
+
+#[derive(Debug)]
+pub struct Frame {
+    // `group` starts from egui group style, `frame` starts from an empty frame
+    pub group: bool,
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub animate: Option<Animate>,
+    pub props: Vec<FrameProperty>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub aspect_ratio: Option<AspectRatio>,
+    pub size_limits: SizeLimits,
+    pub content: Content,
+}
+
+impl Frame {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "animate", "aspect_ratio", "visible", "groups"],
+        SizeLimits::FIELDS,
+        FrameProperty::FIELDS,
+        ContentWidget::FIELDS,
+    );
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        let frame = if self.group {
+            egui::Frame::group(ui.style())
+        } else {
+            egui::Frame::none()
+        };
+        let mut frame = FrameProperty::apply(&self.props, frame, data);
+
+        if let Some(animate) = &self.animate {
+            let tween = Tween::new(animate, ui.ctx(), ui.next_auto_id());
+            frame.fill = tween.color("fill", frame.fill);
+            frame.stroke.color = tween.color("stroke_color", frame.stroke.color);
+            frame.stroke.width = tween.f32("stroke_width", frame.stroke.width);
+            frame.inner_margin = tween.margin("inner_margin", frame.inner_margin);
+            frame.outer_margin = tween.margin("outer_margin", frame.outer_margin);
+        }
+
+        let background = FrameProperty::background(&self.props)
+            .map(|background| (background, Background::placeholder(ui.painter())));
+
+        let response = frame.show(ui, |ui| {
+            self.content.show(data, ui);
+        }).response;
+
+        if let Some((background, idx)) = background {
+            let margin = frame.outer_margin;
+            let rect = egui::Rect::from_min_max(response.rect.min + margin.left_top(), response.rect.max - margin.right_bottom());
+            background.paint(data, ui.painter(), idx, rect);
+        }
+    }
+
+    fn read(value: &Reader, group: bool) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut animate = None;
+        let mut props = vec![];
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut aspect_ratio = None;
+        let mut size_limits = SizeLimits::default();
+        let mut content = vec![];
+        let mut last_content = None;
+
+        for (key, value) in value.read_object()? {
+            let mut is_content = false;
+            match &*key {
+                "id"      => { id      = Some(value.read()?); }
+                "enabled" => { enabled = Some(value.read()?); }
+                "transition" => { transition = Some(value.read()?); }
+                "animate" => { animate = Some(value.read()?); }
+                "aspect_ratio" => { aspect_ratio = Some(value.read()?); }
+                "visible" => { visible = Some(value.read()?); }
+                "groups"  => { groups  = value.read()?; }
+                str => {
+                    if SizeLimits::FIELDS.contains(&str) {
+                        size_limits.read_map_value(str, &value)?;
+                    } else if FrameProperty::FIELDS.contains(&str) {
+                        props.push(FrameProperty::read_map_value(str, &value)?);
+                    } else if ContentWidget::is_field(&value, str) {
+                        content.push(ContentWidget::read_map_value(str, &value)?);
+                        last_content = Some(str.to_owned());
+                        is_content = true;
+                    } else {
+                        value.skip_unknown_field(str, Frame::FIELDS)?;
+                        continue;
                     }
                 }
             }
+
+            if !is_content && last_content.is_some() && !value.is_flag() {
+                return Err(Error::custom(&value, format!(
+                    "all frame properties should be above content, but `{}` is located after `{}`",
+                    key, last_content.unwrap(),
+                )));
+            }
+        }
+
+        Ok(Frame {
+            group,
+            id,
+            enabled,
+            transition,
+            animate,
+            props,
+            visible,
+            groups,
+            aspect_ratio,
+            size_limits,
+            content: Content(content),
+        })
+    }
+}
+
+impl ToUiconf for Frame {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(animate) = &self.animate {
+                writer.field("animate", animate);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            if let Some(aspect_ratio) = &self.aspect_ratio {
+                writer.field("aspect_ratio", aspect_ratio);
+            }
+            self.size_limits.write_map_values(writer);
+            for prop in self.props.iter() {
+                prop.write_map_value(writer);
+            }
+            self.content.write_map_values(writer);
+        });
+    }
+}
+
+//
+// FrameProperty
+//
+
+#[derive(Debug)]
+pub enum FrameProperty {
+    Fill(Binding<bevy::prelude::Color>),
+    Stroke(Stroke),
+    InnerMargin(Margin),
+    OuterMargin(Margin),
+    Rounding(Rounding),
+    Shadow(Shadow),
+    FillAlpha(Binding<f32>),
+    Background(Background),
+}
+
+impl FrameProperty {
+    const FIELDS: &'static [&'static str] = &["fill", "stroke", "inner_margin", "outer_margin", "rounding", "shadow", "fill_alpha", "background"];
+
+    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
+        match tag {
+            "fill"         => Ok(Self::Fill        (value.read()?)),
+            "stroke"       => Ok(Self::Stroke      (value.read()?)),
+            "inner_margin" => Ok(Self::InnerMargin (value.read()?)),
+            "outer_margin" => Ok(Self::OuterMargin (value.read()?)),
+            "rounding"     => Ok(Self::Rounding    (value.read()?)),
+            "shadow"       => Ok(Self::Shadow      (value.read()?)),
+            "fill_alpha"   => Ok(Self::FillAlpha   (value.read()?)),
+            "background"   => Ok(Self::Background  (value.read()?)),
+            _              => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+        }
+    }
+
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Fill(color)          => writer.field("fill", color),
+            Self::Stroke(stroke)       => writer.field("stroke", stroke),
+            Self::InnerMargin(margin)  => writer.field("inner_margin", margin),
+            Self::OuterMargin(margin)  => writer.field("outer_margin", margin),
+            Self::Rounding(rounding)   => writer.field("rounding", rounding),
+            Self::Shadow(shadow)       => writer.field("shadow", shadow),
+            Self::FillAlpha(alpha)     => writer.field("fill_alpha", alpha),
+            Self::Background(image)    => writer.field("background", image),
+        }
+    }
+
+    // `{ fill = ... stroke = ... }` block without any content, used for window frames
+    fn read_block(value: &Reader) -> Result<Vec<Self>, Error> {
+        let mut props = vec![];
+        for (key, value) in value.read_object()? {
+            if FrameProperty::FIELDS.contains(&&*key) {
+                props.push(FrameProperty::read_map_value(&key, &value)?);
+            } else {
+                value.skip_unknown_field(&key, FrameProperty::FIELDS)?;
+            }
+        }
+        Ok(props)
+    }
+
+    fn write_block(props: &[Self], writer: &mut Writer) {
+        writer.object(|writer| {
+            for prop in props.iter() {
+                prop.write_map_value(writer);
+            }
+        });
+    }
+
+    fn apply(props: &[Self], mut frame: egui::Frame, data: &dyn DataSource) -> egui::Frame {
+        let mut fill_alpha = 1.;
+
+        for prop in props.iter() {
+            use FrameProperty as P;
+            match prop {
+                P::Fill(fill) => {
+                    if let Ok(fill) = fill.resolve(data) {
+                        frame.fill = color_bevy_to_egui(fill);
+                    }
+                }
+                P::Stroke(stroke) => {
+                    if let Ok(stroke) = stroke.resolve(data) {
+                        frame.stroke = stroke;
+                    }
+                }
+                P::InnerMargin(margin) => frame.inner_margin = margin.0,
+                P::OuterMargin(margin) => frame.outer_margin = margin.0,
+                P::Rounding(rounding)  => frame.rounding = rounding.0,
+                P::Shadow(shadow)      => frame.shadow = shadow.0,
+                P::FillAlpha(alpha) => {
+                    if let Ok(alpha) = alpha.resolve(data) {
+                        fill_alpha = alpha.clamp(0., 1.);
+                    }
+                }
+                P::Background(_) => {
+                    // painted separately by whoever shows the frame
+                }
+            }
+        }
+
+        // `fill_alpha = @hud_alpha` multiplies whatever fill is set, regardless of property order
+        frame.fill = frame.fill.gamma_multiply(fill_alpha);
+        if Self::background(props).is_some() {
+            frame.fill = egui::Color32::TRANSPARENT;
+        }
+        frame
+    }
+
+    fn background(props: &[Self]) -> Option<&Background> {
+        props.iter().rev().find_map(|prop| match prop {
+            Self::Background(background) => Some(background),
+            _ => None,
+        })
+    }
+}
+
+//
+// Background
+//
+
+// Image painted behind a frame, window or button instead of its `fill`. `background = "ui/panel.png"`
+// stretches the whole image, `background = { texture = "ui/panel.png" slice = 8 }` keeps 8 pixels
+// of every border at their size and only stretches the middle (nine-slice scaling).
+#[derive(Debug)]
+pub struct Background {
+    pub texture: String,
+    // borders of the image in pixels, same order as `inner_margin`
+    pub slice: Option<Margin>,
+    // size of one image pixel on screen in points, 1 by default
+    pub scale: Option<f32>,
+    pub tint: Option<Binding<bevy::prelude::Color>>,
+}
+
+impl Background {
+    const FIELDS: &'static [&'static str] = &["texture", "slice", "scale", "tint"];
+
+    // reserves a place for background before anything else is painted, so that it ends up behind
+    fn placeholder(painter: &egui::Painter) -> egui::layers::ShapeIdx {
+        painter.add(egui::Shape::Noop)
+    }
+
+    fn paint(&self, data: &dyn DataSource, painter: &egui::Painter, idx: egui::layers::ShapeIdx, rect: egui::Rect) {
+        // nothing is painted until the image is loaded
+        let Some(images) = UiconfImages::get(painter.ctx()) else { return; };
+        let Some(texture) = images.texture(&self.texture) else { return; };
+        let tint = self.tint.as_ref()
+            .and_then(|tint| tint.resolve(data).ok())
+            .map_or(egui::Color32::WHITE, color_bevy_to_egui);
+
+        let mut mesh = egui::Mesh::with_texture(texture);
+        let (Some(slice), Some(size)) = (self.slice, images.size(&self.texture)) else {
+            let uv = egui::Rect::from_min_max(egui::pos2(0., 0.), egui::pos2(1., 1.));
+            mesh.add_rect_with_uv(rect, uv, tint);
+            painter.set(idx, mesh);
+            return;
+        };
+
+        // borders are scaled down if they don't fit
+        let egui::Margin { left, right, top, bottom } = slice.0;
+        let scale = self.scale.unwrap_or(1.)
+            .min(rect.width() / (left + right))
+            .min(rect.height() / (top + bottom));
+
+        let xs = [rect.min.x, rect.min.x + left * scale, rect.max.x - right * scale, rect.max.x];
+        let ys = [rect.min.y, rect.min.y + top * scale, rect.max.y - bottom * scale, rect.max.y];
+        let us = [0., left / size.x, 1. - right / size.x, 1.];
+        let vs = [0., top / size.y, 1. - bottom / size.y, 1.];
+
+        for row in 0..3 {
+            for col in 0..3 {
+                mesh.add_rect_with_uv(
+                    egui::Rect::from_min_max(egui::pos2(xs[col], ys[row]), egui::pos2(xs[col + 1], ys[row + 1])),
+                    egui::Rect::from_min_max(egui::pos2(us[col], vs[row]), egui::pos2(us[col + 1], vs[row + 1])),
+                    tint,
+                );
+            }
+        }
+        painter.set(idx, mesh);
+    }
+}
+
+impl ReadUiconf for Background {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        // `background = "ui/panel.png"` is a shortcut for `background = { texture = "ui/panel.png" }`
+        if value.is_scalar() {
+            let texture = value.read_string()?;
+            value.record_asset(&texture);
+            return Ok(Background { texture, slice: None, scale: None, tint: None });
+        }
+
+        let mut texture = None;
+        let mut slice = None;
+        let mut scale = None;
+        let mut tint = None;
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "texture" => {
+                    if texture.is_some() { return Err(Error::duplicate_field(&value, "texture")); }
+                    let path = value.read_string()?;
+                    value.record_asset(&path);
+                    texture = Some(path);
+                }
+                "slice" => { slice = Some(value.read()?); }
+                "scale" => { scale = Some(value.read()?); }
+                "tint"  => { tint  = Some(value.read()?); }
+                str => {
+                    value.skip_unknown_field(str, Background::FIELDS)?;
+                }
+            }
+        }
+
+        let texture = texture.ok_or_else(|| Error::missing_field(value, "texture"))?;
+
+        Ok(Background { texture, slice, scale, tint })
+    }
+}
+
+impl ToUiconf for Background {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        if self.slice.is_none() && self.scale.is_none() && self.tint.is_none() {
+            self.texture.write_uiconf(writer);
+            return;
+        }
+
+        writer.object(|writer| {
+            writer.field("texture", &self.texture);
+            if let Some(slice) = &self.slice {
+                writer.field("slice", slice);
+            }
+            if let Some(scale) = &self.scale {
+                writer.field("scale", scale);
+            }
+            if let Some(tint) = &self.tint {
+                writer.field("tint", tint);
+            }
+        });
+    }
+}
+
+//
+// Modal
+//
+
+#[derive(Debug)]
+pub struct Modal {
+    id: egui::Id,
+    pub explicit_id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub visible: Condition,
+    pub groups: Vec<String>,
+    pub title: Option<RichText>,
+    pub dim: Option<Binding<bevy::prelude::Color>>,
+    pub content: Content,
+}
+
+impl Modal {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "visible", "groups", "title", "dim"],
+        ContentWidget::FIELDS,
+    );
+
+    // hidden by `visible` before this is called, unlike other widgets modal isn't shown if the binding
+    // fails to resolve, since it would block the whole screen
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        if self.visible.resolve(data).is_err() { return; }
+
+        let ctx = ui.ctx().clone();
+        let id = ui.make_persistent_id((self.id, data.data_ptr()));
+        let dim = self.dim.as_ref()
+            .and_then(|dim| dim.resolve(data).ok())
+            .map(color_bevy_to_egui)
+            .unwrap_or(egui::Color32::from_black_alpha(128));
+
+        // backdrop covers the whole screen and swallows all input meant for other windows
+        egui::Area::new(id.with("backdrop"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(egui::Pos2::ZERO)
+            .show(&ctx, |ui| {
+                let screen_rect = ui.ctx().screen_rect();
+                ui.painter().rect_filled(screen_rect, 0., dim);
+                ui.allocate_rect(screen_rect, egui::Sense::click_and_drag());
+            });
+
+        let dialog = egui::Area::new(id)
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(&ctx, |dialog_ui| {
+                // faded by `transition`
+                dialog_ui.visuals_mut().clone_from(ui.visuals());
+                egui::Frame::window(dialog_ui.style()).show(dialog_ui, |ui| {
+                    if let Some(title) = &self.title {
+                        ui.label(title.resolve(data, ui.ctx()).heading());
+                        ui.separator();
+                    }
+                    self.content.show(data, ui);
+                });
+            });
+
+        // both areas are on the same layer, dialog should always be above its backdrop
+        ctx.move_to_top(dialog.response.layer_id);
+    }
+}
+
+impl ReadUiconf for Modal {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut explicit_id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut title = None;
+        let mut dim = None;
+        let mut content = vec![];
+        let mut last_content = None;
+
+        for (key, value) in value.read_object()? {
+            let mut is_content = false;
+            match &*key {
+                "id"         => { explicit_id = Some(value.read()?); }
+                "enabled"    => { enabled     = Some(value.read()?); }
+                "transition" => { transition  = Some(value.read()?); }
+                "visible"    => { visible     = Some(value.read()?); }
+                "groups"     => { groups      = value.read()?; }
+                "title"      => { title       = Some(value.read()?); }
+                "dim"        => { dim         = Some(value.read()?); }
+                str => {
+                    if ContentWidget::is_field(&value, str) {
+                        content.push(ContentWidget::read_map_value(str, &value)?);
+                        last_content = Some(str.to_owned());
+                        is_content = true;
+                    } else {
+                        value.skip_unknown_field(str, Modal::FIELDS)?;
+                        continue;
+                    }
+                }
+            }
+
+            if !is_content && last_content.is_some() && !value.is_flag() {
+                return Err(Error::custom(&value, format!(
+                    "all modal properties should be above content, but `{}` is located after `{}`",
+                    key, last_content.unwrap(),
+                )));
+            }
+        }
+
+        Ok(Modal {
+            id: WidgetId::or_path_id(&explicit_id, value),
+            explicit_id,
+            enabled,
+            transition,
+            visible: visible.ok_or_else(|| Error::missing_field(value, "visible"))?,
+            groups,
+            title,
+            dim,
+            content: Content(content),
+        })
+    }
+}
+
+impl ToUiconf for Modal {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.explicit_id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            writer.field("visible", &self.visible);
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            if let Some(title) = &self.title {
+                writer.field("title", title);
+            }
+            if let Some(dim) = &self.dim {
+                writer.field("dim", dim);
+            }
+            self.content.write_map_values(writer);
+        });
+    }
+}
+
+//
+// Table
+//
+
+#[cfg(feature = "egui_extras")]
+#[derive(Debug)]
+pub struct Table {
+    id: egui::Id,
+    pub explicit_id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub rows: BindingRef<dyn Reflect>,
+    pub striped: bool,
+    pub row_height: f32,
+    pub header_height: f32,
+    pub columns: Vec<TableColumn>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub size_limits: SizeLimits,
+}
+
+#[cfg(feature = "egui_extras")]
+impl Table {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "rows", "striped", "row_height", "header_height", "column", "visible", "groups"],
+        SizeLimits::FIELDS,
+    );
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        // same as grid, table state depends on both position in config file and data model
+        ui.push_id((self.id, data.data_ptr()), |ui| {
+            let mut table = egui_extras::TableBuilder::new(ui).striped(self.striped);
+            for column in self.columns.iter() {
+                table = table.column(column.column());
+            }
+
+            if self.columns.iter().any(|column| column.header.is_some()) {
+                table.header(self.header_height, |mut row| {
+                    for column in self.columns.iter() {
+                        row.col(|ui| {
+                            if let Some(header) = &column.header {
+                                header.show(data, ui);
+                            }
+                        });
+                    }
+                }).body(|body| self.show_body(data, body));
+            } else {
+                table.body(|body| self.show_body(data, body));
+            }
+        });
+    }
+
+    fn show_body(&self, data: &mut dyn DataSource, mut body: egui_extras::TableBody) {
+        let Ok(array) = self.rows.resolve_list_mut(data) else { return; };
+
+        for idx in 0..array.len() {
+            let item = &mut ReflectSource(array.get_mut(idx).unwrap());
+            body.row(self.row_height, |mut row| {
+                for column in self.columns.iter() {
+                    row.col(|ui| column.cell.show(item, ui));
+                }
+            });
+        }
+    }
+}
+
+#[cfg(feature = "egui_extras")]
+impl ReadUiconf for Table {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut explicit_id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut rows: Option<BindingRef<dyn Reflect>> = None;
+        let mut striped = false;
+        let mut row_height = None;
+        let mut header_height = None;
+        let mut columns = vec![];
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut size_limits = SizeLimits::default();
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "rows" => {
+                    if rows.is_some() { return Err(Error::duplicate_field(&value, "rows")); }
+                    rows = Some(value.read()?);
+                }
+                "id"            => { explicit_id   = Some(value.read()?); }
+                "enabled"       => { enabled       = Some(value.read()?); }
+                "transition"    => { transition    = Some(value.read()?); }
+                "striped"       => { striped       = value.read()?; }
+                "row_height"    => { row_height    = Some(value.read()?); }
+                "header_height" => { header_height = Some(value.read()?); }
+                "visible"       => { visible       = Some(value.read()?); }
+                "groups"        => { groups        = value.read()?; }
+                "column" => {
+                    // cells are read in the scope of a list item, so list should be known by then
+                    let Some(rows) = &rows else {
+                        return Err(Error::custom(&value, "`rows` should be located above all table columns"));
+                    };
+                    columns.push(TableColumn::read(&value, rows.name())?);
+                }
+                str => {
+                    if SizeLimits::FIELDS.contains(&str) {
+                        size_limits.read_map_value(str, &value)?;
+                    } else {
+                        value.skip_unknown_field(str, Table::FIELDS)?;
+                    }
+                }
+            }
+        }
+
+        let rows = rows.ok_or_else(|| Error::missing_field(value, "rows"))?;
+
+        Ok(Table {
+            id: WidgetId::or_path_id(&explicit_id, value),
+            explicit_id,
+            enabled,
+            transition,
+            rows,
+            striped,
+            row_height: row_height.unwrap_or(18.),
+            header_height: header_height.unwrap_or(20.),
+            columns,
+            visible,
+            groups,
+            size_limits,
+        })
+    }
+}
+
+#[cfg(feature = "egui_extras")]
+impl ToUiconf for Table {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.explicit_id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            writer.field("rows", &self.rows);
+            if self.striped {
+                writer.field("striped", &self.striped);
+            }
+            writer.field("row_height", &self.row_height);
+            writer.field("header_height", &self.header_height);
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            self.size_limits.write_map_values(writer);
+            for column in self.columns.iter() {
+                writer.field("column", column);
+            }
+        });
+    }
+}
+
+//
+// TableColumn
+//
+
+#[cfg(feature = "egui_extras")]
+#[derive(Debug)]
+pub struct TableColumn {
+    pub size: TableColumnSize,
+    pub resizable: Option<bool>,
+    pub header: Option<Content>,
+    pub cell: Content,
+}
+
+#[cfg(feature = "egui_extras")]
+impl TableColumn {
+    const FIELDS: &'static [&'static str] = &["size", "resizable", "header", "cell"];
+
+    fn column(&self) -> egui_extras::Column {
+        let mut column = match self.size {
+            TableColumnSize::Auto         => egui_extras::Column::auto(),
+            TableColumnSize::Exact(width) => egui_extras::Column::exact(width),
+            TableColumnSize::Remainder    => egui_extras::Column::remainder(),
+        };
+        if let Some(resizable) = self.resizable {
+            column = column.resizable(resizable);
+        }
+        column
+    }
+
+    fn read(value: &Reader, rows: &str) -> Result<Self, Error> {
+        let mut size = TableColumnSize::Auto;
+        let mut resizable = None;
+        let mut header = None;
+        let mut cell = None;
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "size"      => { size      = value.read()?; }
+                "resizable" => { resizable = Some(value.read()?); }
+                "header"    => { header    = Some(value.read()?); }
+                "cell" => {
+                    value.push_binding_scope(rows);
+                    let content = value.read();
+                    value.pop_binding_scope();
+                    cell = Some(content?);
+                }
+                str => { value.skip_unknown_field(str, TableColumn::FIELDS)?; }
+            }
+        }
+
+        Ok(TableColumn {
+            size,
+            resizable,
+            header,
+            cell: cell.unwrap_or(Content(vec![])),
+        })
+    }
+}
+
+#[cfg(feature = "egui_extras")]
+impl ToUiconf for TableColumn {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            writer.field("size", &self.size);
+            if let Some(resizable) = &self.resizable {
+                writer.field("resizable", resizable);
+            }
+            if let Some(header) = &self.header {
+                writer.field("header", header);
+            }
+            writer.field("cell", &self.cell);
+        });
+    }
+}
+
+//
+// TableColumnSize
+//
+
+#[cfg(feature = "egui_extras")]
+#[derive(Debug, Clone, Copy)]
+pub enum TableColumnSize {
+    Auto,
+    Exact(f32),
+    Remainder,
+}
+
+#[cfg(feature = "egui_extras")]
+impl ReadUiconf for TableColumnSize {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        const EXPECTED: &str = "auto, remainder or { exact width }";
+
+        if let Ok(str) = value.read_string() {
+            return match &*str {
+                "auto"      => Ok(Self::Auto),
+                "remainder" => Ok(Self::Remainder),
+                _           => Err(Error::unknown_variant(value, &str, &["auto", "remainder"])),
+            };
+        }
+
+        let mut seq = value.read_array()?;
+        let kind = seq.next().ok_or_else(|| Error::invalid_length(value, 0, EXPECTED))?;
+        let kind_name = kind.read_string()?;
+        if kind_name != "exact" {
+            return Err(Error::unknown_variant(&kind, &kind_name, &["exact"]));
+        }
+        let width = seq.next().ok_or_else(|| Error::invalid_length(value, 1, EXPECTED))?.read()?;
+        if seq.next().is_some() {
+            return Err(Error::invalid_length(value, 3, EXPECTED));
+        }
+        Ok(Self::Exact(width))
+    }
+}
+
+#[cfg(feature = "egui_extras")]
+impl ToUiconf for TableColumnSize {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        match self {
+            Self::Auto         => writer.scalar("auto"),
+            Self::Remainder    => writer.scalar("remainder"),
+            Self::Exact(width) => writer.array(|writer| {
+                writer.item("exact");
+                writer.item(width);
+            }),
+        }
+    }
+}
+
+//
+// Plot
+//
+
+#[cfg(feature = "egui_plot")]
+#[derive(Debug)]
+pub struct Plot {
+    id: egui::Id,
+    pub explicit_id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub height: Option<f32>,
+    pub x_axis_label: Option<String>,
+    pub y_axis_label: Option<String>,
+    pub legend: bool,
+    pub include_x: Option<[f32; 2]>,
+    pub include_y: Option<[f32; 2]>,
+    pub series: Vec<PlotSeries>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub size_limits: SizeLimits,
+}
+
+#[cfg(feature = "egui_plot")]
+impl Plot {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &[
+            "id", "enabled", "transition", "height", "x_axis_label", "y_axis_label", "legend", "include_x", "include_y", "line", "bars", "visible", "groups",
+        ],
+        SizeLimits::FIELDS,
+    );
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        let mut plot = egui_plot::Plot::new((self.id, data.data_ptr()));
+        if let Some(height) = self.height {
+            plot = plot.height(height);
+        }
+        if let Some(label) = &self.x_axis_label {
+            plot = plot.x_axis_label(label);
+        }
+        if let Some(label) = &self.y_axis_label {
+            plot = plot.y_axis_label(label);
+        }
+        if self.legend {
+            plot = plot.legend(egui_plot::Legend::default());
+        }
+        if let Some([min, max]) = self.include_x {
+            plot = plot.include_x(min).include_x(max);
+        }
+        if let Some([min, max]) = self.include_y {
+            plot = plot.include_y(min).include_y(max);
+        }
+
+        plot.show(ui, |plot_ui| {
+            for series in self.series.iter() {
+                series.show(data, plot_ui);
+            }
+        });
+    }
+}
+
+#[cfg(feature = "egui_plot")]
+impl ReadUiconf for Plot {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut explicit_id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut height = None;
+        let mut x_axis_label = None;
+        let mut y_axis_label = None;
+        let mut legend = false;
+        let mut include_x = None;
+        let mut include_y = None;
+        let mut series = vec![];
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut size_limits = SizeLimits::default();
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "id"           => { explicit_id  = Some(value.read()?); }
+                "enabled"      => { enabled      = Some(value.read()?); }
+                "transition"   => { transition   = Some(value.read()?); }
+                "height"       => { height       = Some(value.read()?); }
+                "x_axis_label" => { x_axis_label = Some(value.read()?); }
+                "y_axis_label" => { y_axis_label = Some(value.read()?); }
+                "legend"       => { legend       = value.read()?; }
+                "include_x"    => { include_x    = Some(read_range(&value)?); }
+                "include_y"    => { include_y    = Some(read_range(&value)?); }
+                "line"         => { series.push(PlotSeries::read(&value, PlotSeriesKind::Line)?); }
+                "bars"         => { series.push(PlotSeries::read(&value, PlotSeriesKind::Bars)?); }
+                "visible"      => { visible      = Some(value.read()?); }
+                "groups"       => { groups       = value.read()?; }
+                str => {
+                    if SizeLimits::FIELDS.contains(&str) {
+                        size_limits.read_map_value(str, &value)?;
+                    } else {
+                        value.skip_unknown_field(str, Plot::FIELDS)?;
+                    }
+                }
+            }
+        }
+
+        fn read_range(value: &Reader) -> Result<[f32; 2], Error> {
+            const EXPECTED: &str = "{ min max }";
+            let mut seq = value.read_array()?;
+            let min = seq.next().ok_or_else(|| Error::invalid_length(value, 0, EXPECTED))?.read()?;
+            let max = seq.next().ok_or_else(|| Error::invalid_length(value, 1, EXPECTED))?.read()?;
+            if seq.next().is_some() {
+                return Err(Error::invalid_length(value, 3, EXPECTED));
+            }
+            Ok([min, max])
+        }
+
+        Ok(Plot {
+            id: WidgetId::or_path_id(&explicit_id, value),
+            explicit_id,
+            enabled,
+            transition,
+            height,
+            x_axis_label,
+            y_axis_label,
+            legend,
+            include_x,
+            include_y,
+            series,
+            visible,
+            groups,
+            size_limits,
+        })
+    }
+}
+
+#[cfg(feature = "egui_plot")]
+impl ToUiconf for Plot {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.explicit_id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(height) = &self.height {
+                writer.field("height", height);
+            }
+            if let Some(label) = &self.x_axis_label {
+                writer.field("x_axis_label", label);
+            }
+            if let Some(label) = &self.y_axis_label {
+                writer.field("y_axis_label", label);
+            }
+            if self.legend {
+                writer.field("legend", &self.legend);
+            }
+            if let Some(range) = &self.include_x {
+                writer.field("include_x", &range.to_vec());
+            }
+            if let Some(range) = &self.include_y {
+                writer.field("include_y", &range.to_vec());
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            self.size_limits.write_map_values(writer);
+            for series in self.series.iter() {
+                let key = match series.kind {
+                    PlotSeriesKind::Line => "line",
+                    PlotSeriesKind::Bars => "bars",
+                };
+                writer.field(key, series);
+            }
+        });
+    }
+}
+
+//
+// PlotSeries
+//
+
+#[cfg(feature = "egui_plot")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlotSeriesKind {
+    Line,
+    Bars,
+}
+
+#[cfg(feature = "egui_plot")]
+#[derive(Debug)]
+pub struct PlotSeries {
+    pub kind: PlotSeriesKind,
+    // list of `f32` (plotted against item index) or `Vec2`
+    pub values: BindingRef<dyn Reflect>,
+    pub name: Option<String>,
+    pub color: Option<Binding<bevy::prelude::Color>>,
+    pub width: Option<f32>,
+}
+
+#[cfg(feature = "egui_plot")]
+impl PlotSeries {
+    const FIELDS: &'static [&'static str] = &["values", "name", "color", "width"];
+
+    fn points(&self, data: &dyn DataSource) -> Vec<[f64; 2]> {
+        let Ok(list) = self.values.resolve_list_ref(data) else { return vec![]; };
+
+        list.iter().enumerate().filter_map(|(idx, item)| {
+            if let Some(value) = item.downcast_ref::<f32>() {
+                Some([idx as f64, *value as f64])
+            } else if let Some(value) = item.downcast_ref::<f64>() {
+                Some([idx as f64, *value])
+            } else {
+                item.downcast_ref::<bevy::math::Vec2>().map(|value| [value.x as f64, value.y as f64])
+            }
+        }).collect()
+    }
+
+    fn show(&self, data: &dyn DataSource, plot_ui: &mut egui_plot::PlotUi) {
+        let points = self.points(data);
+        let color = self.color.as_ref().and_then(|color| color.resolve(data).ok()).map(color_bevy_to_egui);
+
+        match self.kind {
+            PlotSeriesKind::Line => {
+                let mut line = egui_plot::Line::new(points);
+                if let Some(name) = &self.name {
+                    line = line.name(name);
+                }
+                if let Some(color) = color {
+                    line = line.color(color);
+                }
+                if let Some(width) = self.width {
+                    line = line.width(width);
+                }
+                plot_ui.line(line);
+            }
+            PlotSeriesKind::Bars => {
+                let bars = points.into_iter().map(|[x, y]| {
+                    let bar = egui_plot::Bar::new(x, y);
+                    match self.width {
+                        Some(width) => bar.width(width as f64),
+                        None => bar,
+                    }
+                }).collect();
+                let mut chart = egui_plot::BarChart::new(bars);
+                if let Some(name) = &self.name {
+                    chart = chart.name(name);
+                }
+                if let Some(color) = color {
+                    chart = chart.color(color);
+                }
+                plot_ui.bar_chart(chart);
+            }
+        }
+    }
+
+    fn read(value: &Reader, kind: PlotSeriesKind) -> Result<Self, Error> {
+        // `line = @values` is a shorthand for `line = { values = @values }`
+        if value.is_scalar() {
+            return Ok(PlotSeries { kind, values: value.read()?, name: None, color: None, width: None });
+        }
+
+        let mut values = None;
+        let mut name = None;
+        let mut color = None;
+        let mut width = None;
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "values" => { values = Some(value.read()?); }
+                "name"   => { name   = Some(value.read()?); }
+                "color"  => { color  = Some(value.read()?); }
+                "width"  => { width  = Some(value.read()?); }
+                str => { value.skip_unknown_field(str, PlotSeries::FIELDS)?; }
+            }
+        }
+
+        Ok(PlotSeries {
+            kind,
+            values: values.ok_or_else(|| Error::missing_field(value, "values"))?,
+            name,
+            color,
+            width,
+        })
+    }
+}
+
+#[cfg(feature = "egui_plot")]
+impl ToUiconf for PlotSeries {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        if self.name.is_none() && self.color.is_none() && self.width.is_none() {
+            self.values.write_uiconf(writer);
+            return;
+        }
+
+        writer.object(|writer| {
+            writer.field("values", &self.values);
+            if let Some(name) = &self.name {
+                writer.field("name", name);
+            }
+            if let Some(color) = &self.color {
+                writer.field("color", color);
+            }
+            if let Some(width) = &self.width {
+                writer.field("width", width);
+            }
+        });
+    }
+}
+
+//
+// Each
+//
+
+#[derive(Debug)]
+pub struct Each {
+    pub binding: BindingRef<dyn Reflect>,
+    // resolved against the data `each` is in, not against its items
+    pub enabled: Option<Binding<bool>>,
+    // `virtual = yes`, items are shown in a scroll area of their own, and only the ones in view
+    // are shown at all, so that lists with thousands of items stay fast
+    pub is_virtual: bool,
+    // every item has to be this tall in virtual mode, height of a button by default
+    pub row_height: Option<f32>,
+    pub max_height: Option<f32>,
+    pub content: Content,
+}
+
+impl Each {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["in", "enabled", "virtual", "row_height", "max_height"],
+        ContentWidget::FIELDS,
+    );
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        if let Ok(array) = self.binding.resolve_list_mut(data) {
+            if self.is_virtual {
+                self.show_virtual(array, ui);
+                return;
+            }
+
+            for idx in 0..array.len() {
+                let new_data = &mut ReflectSource(array.get_mut(idx).unwrap());
+                self.content.show(new_data, ui);
+            }
+        }
+    }
+
+    // items are scoped by their index, so that their state stays the same as the list is scrolled
+    fn show_virtual(&self, array: &mut dyn bevy::reflect::List, ui: &mut egui::Ui) {
+        let row_height = self.row_height.unwrap_or(ui.spacing().interact_size.y);
+        let mut scroll = egui::ScrollArea::vertical().id_source(self.binding.name());
+        if let Some(max_height) = self.max_height {
+            scroll = scroll.max_height(max_height);
+        }
+
+        let total_rows = array.len();
+        let output = scroll.show_rows(ui, row_height, total_rows, |ui, rows| {
+            for idx in rows {
+                let new_data = &mut ReflectSource(array.get_mut(idx).unwrap());
+                ui.push_id(idx, |ui| self.content.show(new_data, ui));
+            }
+        });
+        WidgetState::Scroll.record(ui.ctx(), output.id);
+    }
+}
+
+impl ReadUiconf for Each {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut binding = None;
+        let mut enabled = None;
+        let mut is_virtual = None;
+        let mut row_height = None;
+        let mut max_height = None;
+        let mut content = vec![];
+        let mut last_content = None;
+
+        // list is read first, so that content is always bound to its items
+        let fields = value.read_object()?.collect::<Vec<_>>();
+        for (key, value) in fields.iter() {
+            match &**key {
+                "in" => {
+                    if binding.is_some() { return Err(Error::duplicate_field(value, "in")); }
+                    binding = Some(value.read::<BindingRef<dyn Reflect>>()?);
+                }
+                "enabled" => {
+                    if enabled.is_some() { return Err(Error::duplicate_field(value, "enabled")); }
+                    enabled = Some(value.read()?);
+                }
+                _ => {}
+            }
+        }
+        let binding = binding.ok_or_else(|| Error::missing_field(value, "in"))?;
+        value.push_binding_scope(binding.name());
+
+        for (key, value) in fields {
+            let mut is_content = false;
+            match &*key {
+                "in" | "enabled" => {}
+                "virtual" => {
+                    if is_virtual.is_some() { return Err(Error::duplicate_field(&value, "virtual")); }
+                    is_virtual = Some(value.read()?);
+                }
+                "row_height" => {
+                    if row_height.is_some() { return Err(Error::duplicate_field(&value, "row_height")); }
+                    row_height = Some(value.read()?);
+                }
+                "max_height" => {
+                    if max_height.is_some() { return Err(Error::duplicate_field(&value, "max_height")); }
+                    max_height = Some(value.read()?);
+                }
+                str => {
+                    if ContentWidget::is_field(&value, str) {
+                        content.push(ContentWidget::read_map_value(str, &value)?);
+                        last_content = Some(str.to_owned());
+                        is_content = true;
+                    } else {
+                        value.skip_unknown_field(str, Each::FIELDS)?;
+                        continue;
+                    }
+                }
+            }
+
+            if !is_content && last_content.is_some() && !value.is_flag() {
+                return Err(Error::custom(&value, format!(
+                    "all each properties should be above content, but `{}` is located after `{}`",
+                    key, last_content.unwrap(),
+                )));
+            }
+        }
+
+        value.pop_binding_scope();
+
+        let is_virtual = is_virtual.unwrap_or(false);
+        if !is_virtual && (row_height.is_some() || max_height.is_some()) {
+            return Err(Error::custom(value, "`row_height` and `max_height` can only be used with `virtual = yes`".to_owned()));
+        }
+
+        Ok(Each {
+            binding,
+            enabled,
+            is_virtual,
+            row_height,
+            max_height,
+            content: Content(content),
+        })
+    }
+}
+
+impl ToUiconf for Each {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            writer.field("in", &self.binding);
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if self.is_virtual {
+                writer.field("virtual", &self.is_virtual);
+            }
+            if let Some(row_height) = &self.row_height {
+                writer.field("row_height", row_height);
+            }
+            if let Some(max_height) = &self.max_height {
+                writer.field("max_height", max_height);
+            }
+            self.content.write_map_values(writer);
+        });
+    }
+}
+
+//
+// Match
+//
+
+// `match = { on = @state  MainMenu = { ... }  Paused = { ... }  _ = { ... } }`
+#[derive(Debug)]
+pub struct Match {
+    pub on: BindingRef<dyn std::any::Any + Send + Sync>,
+    pub enabled: Option<Binding<bool>>,
+    pub branches: Vec<(String, Content)>,
+    pub default: Option<Content>,
+}
+
+impl Match {
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        let Ok(field) = self.on.resolve_reflect(data) else { return; };
+
+        // branches are matched the same way as `==` in conditions
+        let branch = self.branches.iter()
+            .find(|(name, _)| Condition::compare(field, CompareOp::Equal, name).unwrap_or(false))
+            .map(|(_, content)| content)
+            .or(self.default.as_ref());
+
+        if let Some(content) = branch {
+            content.show(data, ui);
+        }
+    }
+}
+
+impl ReadUiconf for Match {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut on = None;
+        let mut enabled = None;
+        let mut branches = vec![];
+        let mut default = None;
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "on" => {
+                    if on.is_some() { return Err(Error::duplicate_field(&value, "on")); }
+                    on = Some(value.read()?);
+                }
+                "enabled" => {
+                    if enabled.is_some() { return Err(Error::duplicate_field(&value, "enabled")); }
+                    enabled = Some(value.read()?);
+                }
+                "_" => {
+                    if default.is_some() { return Err(Error::duplicate_field(&value, "_")); }
+                    default = Some(value.read()?);
+                }
+                str => {
+                    if branches.iter().any(|(name, _)| name == str) {
+                        return Err(Error::duplicate_field(&value, str));
+                    }
+                    branches.push((str.to_owned(), value.read()?));
+                }
+            }
+        }
+
+        Ok(Match {
+            on: on.ok_or_else(|| Error::missing_field(value, "on"))?,
+            enabled,
+            branches,
+            default,
+        })
+    }
+}
+
+impl ToUiconf for Match {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            writer.field("on", &self.on);
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            for (name, content) in self.branches.iter() {
+                writer.field(name, content);
+            }
+            if let Some(default) = &self.default {
+                writer.field("_", default);
+            }
+        });
+    }
+}
+
+//
+// Tree
+//
+
+// Nested collapsing nodes over a recursive list, e.g. `in = @roots  children = @children  node = { label = @name }`.
+// `children` and `node` are resolved relative to each node, clicked node path (like `0/2/1`) is written to `selected`.
+#[derive(Debug)]
+pub struct Tree {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub binding: BindingRef<dyn Reflect>,
+    pub children: BindingRef<dyn Reflect>,
+    pub node: Content,
+    pub selected: Option<BindingRef<String>>,
+    pub node_clicked: Option<BindingRef<Trigger>>,
+    pub default_open: bool,
+}
+
+impl Tree {
+    const FIELDS: &'static [&'static str] = &[
+        "id", "enabled", "transition", "visible", "groups", "in", "children", "node", "selected", "node_clicked", "default_open",
+    ];
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        let selected = self.selected.as_ref()
+            .and_then(|selected| selected.resolve_ref(data).ok())
+            .cloned();
+        let mut clicked = None;
+
+        if let Ok(roots) = self.binding.resolve_list_mut(data) {
+            ui.push_id(self.binding.name(), |ui| {
+                self.show_nodes(roots, &mut vec![], selected.as_deref(), &mut clicked, ui);
+            });
+        }
+
+        if let Some(path) = clicked {
+            if let Some(Ok(selected)) = self.selected.as_ref().map(|selected| selected.resolve_mut(data)) {
+                *selected = path;
+            }
+            if let Some(Ok(node_clicked)) = self.node_clicked.as_ref().map(|trigger| trigger.resolve_mut(data)) {
+                node_clicked.trigger();
+            }
+        }
+    }
+
+    fn show_nodes(
+        &self,
+        list: &mut dyn bevy::reflect::List,
+        path: &mut Vec<usize>,
+        selected: Option<&str>,
+        clicked: &mut Option<String>,
+        ui: &mut egui::Ui,
+    ) {
+        for idx in 0..list.len() {
+            let node = &mut ReflectSource(list.get_mut(idx).unwrap());
+            path.push(idx);
+
+            let path_str = path.iter().map(|idx| idx.to_string()).collect::<Vec<_>>().join("/");
+            let is_selected = selected == Some(&*path_str);
+            let has_children = self.children.resolve_list_ref(node).is_ok_and(|children| !children.is_empty());
+
+            let node_clicked = if has_children {
+                let id = ui.make_persistent_id(&path_str);
+                let state = egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, self.default_open);
+                WidgetState::Collapsing.record(ui.ctx(), id);
+                let header = state.show_header(ui, |ui| self.show_node(node, is_selected, ui));
+                let (_, header, _) = header.body(|ui| {
+                    if let Ok(children) = self.children.resolve_list_mut(node) {
+                        self.show_nodes(children, path, selected, clicked, ui);
+                    }
+                });
+                header.inner
+            } else {
+                // align leaf nodes with headers that have a collapse button
+                ui.horizontal(|ui| {
+                    ui.add_space(ui.spacing().icon_width + ui.spacing().item_spacing.x);
+                    self.show_node(node, is_selected, ui)
+                }).inner
+            };
+
+            if node_clicked {
+                *clicked = Some(path_str);
+            }
+            path.pop();
+        }
+    }
+
+    // returns true if node was clicked
+    fn show_node(&self, node: &mut dyn DataSource, is_selected: bool, ui: &mut egui::Ui) -> bool {
+        let background = ui.painter().add(egui::Shape::Noop);
+        let response = ui.horizontal(|ui| self.node.show(node, ui)).response.interact(egui::Sense::click());
+
+        if is_selected {
+            let visuals = ui.visuals();
+            ui.painter().set(background, egui::epaint::RectShape::filled(
+                response.rect,
+                visuals.widgets.hovered.rounding,
+                visuals.selection.bg_fill,
+            ));
+        }
+
+        response.clicked()
+    }
+}
+
+impl ReadUiconf for Tree {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut binding = None;
+        let mut children = None;
+        let mut node = None;
+        let mut selected = None;
+        let mut node_clicked = None;
+        let mut default_open = false;
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "id" => {
+                    if id.is_some() { return Err(Error::duplicate_field(&value, "id")); }
+                    id = Some(value.read()?);
+                }
+                "enabled" => {
+                    if enabled.is_some() { return Err(Error::duplicate_field(&value, "enabled")); }
+                    enabled = Some(value.read()?);
+                }
+                "transition" => {
+                    if transition.is_some() { return Err(Error::duplicate_field(&value, "transition")); }
+                    transition = Some(value.read()?);
+                }
+                "visible" => {
+                    if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
+                    visible = Some(value.read()?);
+                }
+                "groups" => {
+                    groups = value.read()?;
+                }
+                "in" => {
+                    if binding.is_some() { return Err(Error::duplicate_field(&value, "in")); }
+                    binding = Some(value.read::<BindingRef<dyn Reflect>>()?);
+                }
+                // node fields are read after the loop, once list binding is known
+                "children" => {
+                    if children.is_some() { return Err(Error::duplicate_field(&value, "children")); }
+                    children = Some(value);
+                }
+                "node" => {
+                    if node.is_some() { return Err(Error::duplicate_field(&value, "node")); }
+                    node = Some(value);
+                }
+                "selected" => {
+                    if selected.is_some() { return Err(Error::duplicate_field(&value, "selected")); }
+                    selected = Some(value.read()?);
+                }
+                "node_clicked" => {
+                    if node_clicked.is_some() { return Err(Error::duplicate_field(&value, "node_clicked")); }
+                    node_clicked = Some(value.read()?);
+                }
+                "default_open" => {
+                    default_open = value.read()?;
+                }
+                str => {
+                    value.skip_unknown_field(str, Tree::FIELDS)?;
+                }
+            }
+        }
+
+        let binding = binding.ok_or_else(|| Error::missing_field(value, "in"))?;
+        let children = children.ok_or_else(|| Error::missing_field(value, "children"))?;
+        let node = node.ok_or_else(|| Error::missing_field(value, "node"))?;
+
+        value.push_binding_scope(binding.name());
+        let children = children.read::<BindingRef<dyn Reflect>>();
+        let node = node.read::<Content>();
+        value.pop_binding_scope();
+
+        Ok(Tree {
+            id,
+            enabled,
+            transition,
+            visible,
+            groups,
+            binding,
+            children: children?,
+            node: node?,
+            selected,
+            node_clicked,
+            default_open,
+        })
+    }
+}
+
+impl ToUiconf for Tree {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            writer.field("in", &self.binding);
+            writer.field("children", &self.children);
+            if let Some(selected) = &self.selected {
+                writer.field("selected", selected);
+            }
+            if let Some(node_clicked) = &self.node_clicked {
+                writer.field("node_clicked", node_clicked);
+            }
+            if self.default_open {
+                writer.field("default_open", &self.default_open);
+            }
+            writer.field("node", &self.node);
+        });
+    }
+}
+
+//
+// ListBox
+//
+
+// Scrollable list of selectable items, items are either strings or structs with a string field named by `label`.
+#[derive(Debug)]
+pub struct ListBox {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub binding: BindingRef<dyn Reflect>,
+    pub label: Option<String>,
+    pub selected: Option<BindingRef<usize>>,
+    pub selected_value: Option<BindingRef<String>>,
+    pub max_height: Option<f32>,
+}
+
+impl ListBox {
+    const FIELDS: &'static [&'static str] = &["id", "enabled", "transition", "visible", "groups", "in", "label", "selected", "selected_value", "max_height"];
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        let selected = self.selected.as_ref().and_then(|selected| selected.resolve_ref(data).ok()).copied();
+        let mut clicked = None;
+
+        let Ok(list) = self.binding.resolve_list_ref(data) else { return; };
+
+        let mut scroll = egui::ScrollArea::vertical().id_source(self.binding.name());
+        if let Some(max_height) = self.max_height {
+            scroll = scroll.max_height(max_height);
+        }
+
+        let output = scroll.show(ui, |ui| {
+            for (idx, item) in list.iter().enumerate() {
+                let text = self.item_text(item);
+                if ui.selectable_label(selected == Some(idx), text).clicked() {
+                    clicked = Some((idx, text.to_owned()));
+                }
+            }
+        });
+        WidgetState::Scroll.record(ui.ctx(), output.id);
+
+        if let Some((idx, text)) = clicked {
+            if let Some(Ok(selected)) = self.selected.as_ref().map(|selected| selected.resolve_mut(data)) {
+                *selected = idx;
+            }
+            if let Some(Ok(selected_value)) = self.selected_value.as_ref().map(|value| value.resolve_mut(data)) {
+                *selected_value = text;
+            }
+        }
+    }
+
+    fn item_text<'a>(&self, item: &'a dyn Reflect) -> &'a str {
+        let item = match (&self.label, item.reflect_ref()) {
+            (Some(label), bevy::reflect::ReflectRef::Struct(item)) => item.field(label),
+            (Some(_), _) => None,
+            (None, _) => Some(item),
+        };
+        item.and_then(|item| item.downcast_ref::<String>()).map(String::as_str).unwrap_or_default()
+    }
+}
+
+impl ReadUiconf for ListBox {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut binding = None;
+        let mut label = None;
+        let mut selected = None;
+        let mut selected_value = None;
+        let mut max_height = None;
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "in" => {
+                    if binding.is_some() { return Err(Error::duplicate_field(&value, "in")); }
+                    binding = Some(value.read()?);
+                }
+                "id"             => { id             = Some(value.read()?); }
+                "enabled"        => { enabled        = Some(value.read()?); }
+                "transition"     => { transition     = Some(value.read()?); }
+                "visible"        => { visible        = Some(value.read()?); }
+                "groups"         => { groups         = value.read()?; }
+                "label"          => { label          = Some(value.read()?); }
+                "selected"       => { selected       = Some(value.read()?); }
+                "selected_value" => { selected_value = Some(value.read()?); }
+                "max_height"     => { max_height     = Some(value.read()?); }
+                str => {
+                    value.skip_unknown_field(str, ListBox::FIELDS)?;
+                }
+            }
+        }
+
+        let binding = binding.ok_or_else(|| Error::missing_field(value, "in"))?;
+
+        Ok(ListBox { id, enabled, transition, visible, groups, binding, label, selected, selected_value, max_height })
+    }
+}
+
+impl ToUiconf for ListBox {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            writer.field("in", &self.binding);
+            if let Some(label) = &self.label {
+                writer.field("label", label);
+            }
+            if let Some(selected) = &self.selected {
+                writer.field("selected", selected);
+            }
+            if let Some(selected_value) = &self.selected_value {
+                writer.field("selected_value", selected_value);
+            }
+            if let Some(max_height) = &self.max_height {
+                writer.field("max_height", max_height);
+            }
+        });
+    }
+}
+
+//
+// SlotGrid
+//
+
+// Fixed number of square slots for hotbars and inventories, filled from a bound list of items.
+// Items are structs, optionally wrapped in `Option` for empty slots; `icon` names a string field
+// with image path, and `count` names a numeric field shown in the corner when it's above one.
+// Events write index of the slot to `slot`, and for `dropped` index of the dragged slot to `drag_from`.
+#[derive(Debug)]
+pub struct SlotGrid {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub binding: BindingRef<dyn Reflect>,
+    pub slots: Option<u32>,
+    pub columns: Option<u32>,
+    pub slot_size: Option<f32>,
+    pub icon: Option<String>,
+    pub count: Option<String>,
+    pub selected: Option<BindingRef<usize>>,
+    pub slot: Option<BindingRef<usize>>,
+    pub drag_from: Option<BindingRef<usize>>,
+    pub clicked: Option<ResponseAction>,
+    pub secondary_clicked: Option<ResponseAction>,
+    pub dropped: Option<ResponseAction>,
+}
+
+impl SlotGrid {
+    const FIELDS: &'static [&'static str] = &[
+        "id", "enabled", "transition", "visible", "groups", "in", "slots", "columns", "slot_size", "icon", "count",
+        "selected", "slot", "drag_from", "clicked", "secondary_clicked", "dropped",
+    ];
+
+    const DEFAULT_SLOT_SIZE: f32 = 48.;
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        let selected = self.selected.as_ref().and_then(|selected| selected.resolve_ref(data).ok()).copied();
+
+        let Ok(list) = self.binding.resolve_list_ref(data) else { return; };
+        let items = (0..list.len()).map(|idx| {
+            let item = list.get(idx).and_then(SlotGrid::slot_item);
+            (self.item_icon(item), self.item_count(item))
+        }).collect::<Vec<_>>();
+
+        let slots = self.slots.map_or(items.len(), |slots| slots as usize);
+        let columns = self.columns.map_or(slots, |columns| columns as usize).max(1);
+        let size = egui::Vec2::splat(self.slot_size.unwrap_or(Self::DEFAULT_SLOT_SIZE));
+        let drag_id = ui.make_persistent_id(("uiconf_slot_drag", self.binding.name()));
+        let dragged = ui.ctx().data(|d| d.get_temp::<usize>(drag_id));
+
+        let mut events = vec![];
+        ui.vertical(|ui| {
+            for row in 0..(slots + columns - 1) / columns {
+                ui.horizontal(|ui| {
+                    for idx in row * columns..((row + 1) * columns).min(slots) {
+                        let (icon, count) = items.get(idx).cloned().unwrap_or_default();
+                        let response = self.show_slot(ui, size, icon.as_deref(), count, selected == Some(idx));
+
+                        if response.clicked() { events.push((idx, SlotEvent::Clicked, response.clone())); }
+                        if response.secondary_clicked() { events.push((idx, SlotEvent::SecondaryClicked, response.clone())); }
+                        if response.drag_started() {
+                            ui.ctx().data_mut(|d| d.insert_temp(drag_id, idx));
+                        }
+                        if response.dragged() {
+                            ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+                        }
+
+                        let released_here = ui.input(|input| input.pointer.any_released())
+                            && ui.ctx().pointer_interact_pos().is_some_and(|pos| response.rect.contains(pos));
+                        if let Some(from) = dragged.filter(|from| *from != idx && released_here) {
+                            events.push((idx, SlotEvent::Dropped(from), response.clone()));
+                        }
+                    }
+                });
+            }
+        });
+
+        if dragged.is_some() && ui.input(|input| !input.pointer.any_down()) {
+            ui.ctx().data_mut(|d| d.remove::<usize>(drag_id));
+        }
+
+        for (idx, event, response) in events {
+            if let Some(Ok(slot)) = self.slot.as_ref().map(|slot| slot.resolve_mut(data)) {
+                *slot = idx;
+            }
+            let action = match event {
+                SlotEvent::Clicked => {
+                    if let Some(Ok(selected)) = self.selected.as_ref().map(|selected| selected.resolve_mut(data)) {
+                        *selected = idx;
+                    }
+                    &self.clicked
+                }
+                SlotEvent::SecondaryClicked => &self.secondary_clicked,
+                SlotEvent::Dropped(from) => {
+                    if let Some(Ok(drag_from)) = self.drag_from.as_ref().map(|drag_from| drag_from.resolve_mut(data)) {
+                        *drag_from = from;
+                    }
+                    &self.dropped
+                }
+            };
+            if let Some(action) = action {
+                action.run(data, &response);
+            }
+        }
+    }
+
+    fn show_slot(&self, ui: &mut egui::Ui, size: egui::Vec2, icon: Option<&str>, count: Option<f64>, selected: bool) -> egui::Response {
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+        if !ui.is_rect_visible(rect) { return response; }
+
+        let visuals = ui.style().interact_selectable(&response, selected);
+        let stroke = if selected { ui.visuals().selection.stroke } else { visuals.bg_stroke };
+        ui.painter().rect(rect, visuals.rounding, ui.visuals().extreme_bg_color, stroke);
+
+        if let Some(texture) = icon.and_then(|icon| UiconfImages::get(ui.ctx())?.texture(icon)) {
+            let uv = egui::Rect::from_min_max(egui::pos2(0., 0.), egui::pos2(1., 1.));
+            ui.painter().image(texture, rect.shrink(size.x / 8.), uv, egui::Color32::WHITE);
+        }
+
+        if let Some(count) = count.filter(|count| *count > 1.) {
+            ui.painter().text(
+                rect.right_bottom() - egui::vec2(4., 2.),
+                egui::Align2::RIGHT_BOTTOM,
+                count,
+                egui::TextStyle::Small.resolve(ui.style()),
+                ui.visuals().strong_text_color(),
+            );
+        }
+
+        response
+    }
+
+    // `None` is an empty slot
+    fn slot_item(item: &dyn Reflect) -> Option<&dyn Reflect> {
+        match item.reflect_ref() {
+            bevy::reflect::ReflectRef::Enum(option) if is_option(option) => option.field_at(0),
+            _ => Some(item),
+        }
+    }
+
+    fn item_field<'a>(item: Option<&'a dyn Reflect>, name: Option<&str>) -> Option<&'a dyn Reflect> {
+        let bevy::reflect::ReflectRef::Struct(item) = item?.reflect_ref() else { return None; };
+        item.field(name?)
+    }
+
+    fn item_icon(&self, item: Option<&dyn Reflect>) -> Option<String> {
+        let icon = Self::item_field(item, self.icon.as_deref())?.downcast_ref::<String>()?;
+        (!icon.is_empty()).then(|| icon.clone())
+    }
+
+    fn item_count(&self, item: Option<&dyn Reflect>) -> Option<f64> {
+        reflect_as_f64(Self::item_field(item, self.count.as_deref())?)
+    }
+}
+
+enum SlotEvent {
+    Clicked,
+    SecondaryClicked,
+    // index of the slot drag started from
+    Dropped(usize),
+}
+
+impl ReadUiconf for SlotGrid {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut binding = None;
+        let mut slots = None;
+        let mut columns = None;
+        let mut slot_size = None;
+        let mut icon = None;
+        let mut count = None;
+        let mut selected = None;
+        let mut slot = None;
+        let mut drag_from = None;
+        let mut clicked = None;
+        let mut secondary_clicked = None;
+        let mut dropped = None;
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "in" => {
+                    if binding.is_some() { return Err(Error::duplicate_field(&value, "in")); }
+                    binding = Some(value.read()?);
+                }
+                "id"                => { id                = Some(value.read()?); }
+                "enabled"           => { enabled           = Some(value.read()?); }
+                "transition"        => { transition        = Some(value.read()?); }
+                "visible"           => { visible           = Some(value.read()?); }
+                "groups"            => { groups            = value.read()?; }
+                "slots"             => { slots             = Some(value.read()?); }
+                "columns"           => { columns           = Some(value.read()?); }
+                "slot_size"         => { slot_size         = Some(value.read()?); }
+                "icon"              => { icon              = Some(value.read()?); }
+                "count"             => { count             = Some(value.read()?); }
+                "selected"          => { selected          = Some(value.read()?); }
+                "slot"              => { slot              = Some(value.read()?); }
+                "drag_from"         => { drag_from         = Some(value.read()?); }
+                "clicked"           => { clicked           = Some(value.read()?); }
+                "secondary_clicked" => { secondary_clicked = Some(value.read()?); }
+                "dropped"           => { dropped           = Some(value.read()?); }
+                str => {
+                    value.skip_unknown_field(str, SlotGrid::FIELDS)?;
+                }
+            }
+        }
+
+        let binding = binding.ok_or_else(|| Error::missing_field(value, "in"))?;
+
+        Ok(SlotGrid {
+            id, enabled, transition, visible, groups, binding, slots, columns, slot_size, icon, count, selected, slot, drag_from,
+            clicked, secondary_clicked, dropped,
+        })
+    }
+}
+
+impl ToUiconf for SlotGrid {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            writer.field("in", &self.binding);
+            if let Some(slots) = &self.slots {
+                writer.field("slots", slots);
+            }
+            if let Some(columns) = &self.columns {
+                writer.field("columns", columns);
+            }
+            if let Some(slot_size) = &self.slot_size {
+                writer.field("slot_size", slot_size);
+            }
+            if let Some(icon) = &self.icon {
+                writer.field("icon", icon);
+            }
+            if let Some(count) = &self.count {
+                writer.field("count", count);
+            }
+            if let Some(selected) = &self.selected {
+                writer.field("selected", selected);
+            }
+            if let Some(slot) = &self.slot {
+                writer.field("slot", slot);
+            }
+            if let Some(drag_from) = &self.drag_from {
+                writer.field("drag_from", drag_from);
+            }
+            if let Some(clicked) = &self.clicked {
+                writer.field("clicked", clicked);
+            }
+            if let Some(secondary_clicked) = &self.secondary_clicked {
+                writer.field("secondary_clicked", secondary_clicked);
+            }
+            if let Some(dropped) = &self.dropped {
+                writer.field("dropped", dropped);
+            }
+        });
+    }
+}
+
+//
+// ChatLog
+//
+
+// Scrolling log of chat messages from a bound list of structs, newest last. `author`, `text`, `color` and `timestamp`
+// name fields of the message (`author` and `text` by default); `color` is a bevy `Color` of the author name,
+// `timestamp` is either a string shown as is, or seconds shown as `mm:ss`. Only the last `max_lines` messages
+// are shown, and with `fade_after` messages fade out once they're that many seconds older than `now`.
+// Clicking an author name writes it to `clicked_author` and runs `author_clicked`.
+#[derive(Debug)]
+pub struct ChatLog {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub binding: BindingRef<dyn Reflect>,
+    pub author: Option<String>,
+    pub text: Option<String>,
+    pub color: Option<String>,
+    pub timestamp: Option<String>,
+    pub max_lines: Option<u32>,
+    pub max_height: Option<f32>,
+    pub stick_to_bottom: bool,
+    pub now: Option<BindingRef<dyn Reflect>>,
+    pub fade_after: Option<f32>,
+    pub fade_duration: Option<f32>,
+    pub clicked_author: Option<BindingRef<String>>,
+    pub author_clicked: Option<ResponseAction>,
+}
+
+impl ChatLog {
+    const FIELDS: &'static [&'static str] = &[
+        "id", "enabled", "transition", "visible", "groups", "in", "author", "text", "color", "timestamp", "max_lines",
+        "max_height", "stick_to_bottom", "now", "fade_after", "fade_duration", "clicked_author", "author_clicked",
+    ];
+
+    const DEFAULT_FADE_DURATION: f32 = 1.;
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) {
+        let now = self.now.as_ref().and_then(|now| now.resolve_reflect(data).ok()).and_then(reflect_as_f64);
+
+        let Ok(list) = self.binding.resolve_list_ref(data) else { return; };
+        let first = self.max_lines.map_or(0, |max_lines| list.len().saturating_sub(max_lines as usize));
+        let messages = (first..list.len())
+            .filter_map(|idx| self.message(list.get(idx)?, now))
+            .filter(|message| message.opacity > 0.)
+            .collect::<Vec<_>>();
+
+        let mut scroll = egui::ScrollArea::vertical()
+            .id_source(self.binding.name())
+            .auto_shrink([false, true])
+            .stick_to_bottom(self.stick_to_bottom);
+        if let Some(max_height) = self.max_height {
+            scroll = scroll.max_height(max_height);
+        }
+
+        let mut clicked = None;
+        let output = scroll.show(ui, |ui| {
+            for message in messages {
+                if let Some(response) = Self::show_message(&message, ui) {
+                    clicked = Some((message.author, response));
+                }
+            }
+        });
+        WidgetState::Scroll.record(ui.ctx(), output.id);
+
+        if let Some((author, response)) = clicked {
+            if let Some(Ok(clicked_author)) = self.clicked_author.as_ref().map(|clicked_author| clicked_author.resolve_mut(data)) {
+                *clicked_author = author;
+            }
+            if let Some(action) = &self.author_clicked {
+                action.run(data, &response);
+            }
+        }
+    }
+
+    // returns response of the author name if it was clicked
+    fn show_message(message: &ChatMessage, ui: &mut egui::Ui) -> Option<egui::Response> {
+        let fade = |color: egui::Color32| color.gamma_multiply(message.opacity);
+
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.;
+
+            if let Some(timestamp) = &message.timestamp {
+                let color = fade(ui.visuals().weak_text_color());
+                ui.label(egui::RichText::new(format!("[{}] ", timestamp)).color(color));
+            }
+
+            // messages without author, e.g. from the server, are shown as plain text
+            let mut clicked = None;
+            if !message.author.is_empty() {
+                let color = fade(message.color.unwrap_or(ui.visuals().strong_text_color()));
+                let author = egui::Label::new(egui::RichText::new(&message.author).color(color).strong())
+                    .sense(egui::Sense::click());
+                let response = ui.add(author).on_hover_cursor(egui::CursorIcon::PointingHand);
+                if response.clicked() {
+                    clicked = Some(response);
+                }
+                ui.label(egui::RichText::new(": ").color(fade(ui.visuals().text_color())));
+            }
+
+            ui.label(egui::RichText::new(&message.text).color(fade(ui.visuals().text_color())));
+            clicked
+        }).inner
+    }
+
+    fn message(&self, item: &dyn Reflect, now: Option<f64>) -> Option<ChatMessage> {
+        let bevy::reflect::ReflectRef::Struct(item) = item.reflect_ref() else { return None; };
+        let string = |name: &str| item.field(name)?.downcast_ref::<String>().cloned();
+
+        let timestamp = self.timestamp.as_deref().and_then(|name| item.field(name));
+        let seconds = timestamp.and_then(reflect_as_f64);
+        let opacity = match (self.fade_after, now, seconds) {
+            (Some(fade_after), Some(now), Some(seconds)) => {
+                let faded_for = (now - seconds) as f32 - fade_after;
+                let duration = self.fade_duration.unwrap_or(Self::DEFAULT_FADE_DURATION).max(f32::EPSILON);
+                1. - (faded_for / duration).clamp(0., 1.)
+            }
+            _ => 1.,
+        };
+
+        Some(ChatMessage {
+            author: string(self.author.as_deref().unwrap_or("author")).unwrap_or_default(),
+            text: string(self.text.as_deref().unwrap_or("text")).unwrap_or_default(),
+            color: self.color.as_deref()
+                .and_then(|name| item.field(name)?.downcast_ref::<bevy::prelude::Color>().copied())
+                .map(color_bevy_to_egui),
+            timestamp: match seconds {
+                Some(seconds) => {
+                    let seconds = seconds.max(0.) as u64;
+                    Some(format!("{:02}:{:02}", seconds / 60, seconds % 60))
+                }
+                None => timestamp.and_then(|timestamp| timestamp.downcast_ref::<String>()).cloned(),
+            },
+            opacity,
+        })
+    }
+}
+
+struct ChatMessage {
+    author: String,
+    text: String,
+    color: Option<egui::Color32>,
+    timestamp: Option<String>,
+    opacity: f32,
+}
+
+impl ReadUiconf for ChatLog {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut binding = None;
+        let mut author = None;
+        let mut text = None;
+        let mut color = None;
+        let mut timestamp = None;
+        let mut max_lines = None;
+        let mut max_height = None;
+        let mut stick_to_bottom = true;
+        let mut now = None;
+        let mut fade_after = None;
+        let mut fade_duration = None;
+        let mut clicked_author = None;
+        let mut author_clicked = None;
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "in" => {
+                    if binding.is_some() { return Err(Error::duplicate_field(&value, "in")); }
+                    binding = Some(value.read()?);
+                }
+                "id"              => { id              = Some(value.read()?); }
+                "enabled"         => { enabled         = Some(value.read()?); }
+                "transition"      => { transition      = Some(value.read()?); }
+                "visible"         => { visible         = Some(value.read()?); }
+                "groups"          => { groups          = value.read()?; }
+                "author"          => { author          = Some(value.read()?); }
+                "text"            => { text            = Some(value.read()?); }
+                "color"           => { color           = Some(value.read()?); }
+                "timestamp"       => { timestamp       = Some(value.read()?); }
+                "max_lines"       => { max_lines       = Some(value.read()?); }
+                "max_height"      => { max_height      = Some(value.read()?); }
+                "stick_to_bottom" => { stick_to_bottom = value.read()?; }
+                "now"             => { now             = Some(value.read()?); }
+                "fade_after"      => { fade_after      = Some(value.read()?); }
+                "fade_duration"   => { fade_duration   = Some(value.read()?); }
+                "clicked_author"  => { clicked_author  = Some(value.read()?); }
+                "author_clicked"  => { author_clicked  = Some(value.read()?); }
+                str => {
+                    value.skip_unknown_field(str, ChatLog::FIELDS)?;
+                }
+            }
+        }
+
+        let binding = binding.ok_or_else(|| Error::missing_field(value, "in"))?;
+        if fade_after.is_some() {
+            if timestamp.is_none() { return Err(Error::missing_field(value, "timestamp")); }
+            if now.is_none() { return Err(Error::missing_field(value, "now")); }
+        }
+
+        Ok(ChatLog {
+            id, enabled, transition, visible, groups, binding, author, text, color, timestamp, max_lines, max_height, stick_to_bottom,
+            now, fade_after, fade_duration, clicked_author, author_clicked,
+        })
+    }
+}
+
+impl ToUiconf for ChatLog {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            writer.field("in", &self.binding);
+            if let Some(author) = &self.author {
+                writer.field("author", author);
+            }
+            if let Some(text) = &self.text {
+                writer.field("text", text);
+            }
+            if let Some(color) = &self.color {
+                writer.field("color", color);
+            }
+            if let Some(timestamp) = &self.timestamp {
+                writer.field("timestamp", timestamp);
+            }
+            if let Some(max_lines) = &self.max_lines {
+                writer.field("max_lines", max_lines);
+            }
+            if let Some(max_height) = &self.max_height {
+                writer.field("max_height", max_height);
+            }
+            if !self.stick_to_bottom {
+                writer.field("stick_to_bottom", &self.stick_to_bottom);
+            }
+            if let Some(now) = &self.now {
+                writer.field("now", now);
+            }
+            if let Some(fade_after) = &self.fade_after {
+                writer.field("fade_after", fade_after);
+            }
+            if let Some(fade_duration) = &self.fade_duration {
+                writer.field("fade_duration", fade_duration);
+            }
+            if let Some(clicked_author) = &self.clicked_author {
+                writer.field("clicked_author", clicked_author);
+            }
+            if let Some(author_clicked) = &self.author_clicked {
+                writer.field("author_clicked", author_clicked);
+            }
+        });
+    }
+}
+
+//
+// Response
+//
+
+#[derive(Debug)]
+pub struct Response(Vec<ResponseProperty>);
+
+impl Response {
+    fn write_map_values(&self, writer: &mut Writer) {
+        for prop in self.0.iter() {
+            prop.write_map_value(writer);
+        }
+    }
+
+    fn process(&self, data: &mut dyn DataSource, mut response: egui::Response) -> egui::Response {
+        crate::navigation::register(&mut response);
+
+        let dropped = if self.0.iter().any(|prop| matches!(prop, ResponseProperty::DropTarget(_) | ResponseProperty::Dropped(_))) {
+            Response::dropped_payload(&response)
+        } else {
+            None
+        };
+
+        for prop in self.0.iter() {
+            use ResponseProperty as P;
+            match prop {
+                P::Clicked(action) => {
+                    if response.clicked() { action.run(data, &response); }
+                }
+                P::SecondaryClicked(action) => {
+                    if response.secondary_clicked() { action.run(data, &response); }
+                }
+                P::MiddleClicked(action) => {
+                    if response.middle_clicked() { action.run(data, &response); }
+                }
+                P::DoubleClicked(action) => {
+                    if response.double_clicked() { action.run(data, &response); }
+                }
+                P::TripleClicked(action) => {
+                    if response.triple_clicked() { action.run(data, &response); }
+                }
+                P::ClickedElsewhere(action) => {
+                    if response.clicked_elsewhere() { action.run(data, &response); }
+                }
+                P::Hovered(action) => {
+                    if response.hovered() { action.run(data, &response); }
+                }
+                P::Highlighted(action) => {
+                    if response.highlighted() { action.run(data, &response); }
+                }
+                P::Changed(action) => {
+                    if response.changed() { action.run(data, &response); }
+                }
+                P::OnHover(content) => {
+                    response = response.on_hover_ui(|ui| {
+                        content.show(data, ui);
+                    });
+                }
+                P::OnDisabledHover(content) => {
+                    response = response.on_disabled_hover_ui(|ui| {
+                        content.show(data, ui);
+                    });
+                }
+                P::OnHoverAtPointer(content) => {
+                    response = response.on_hover_ui_at_pointer(|ui| {
+                        content.show(data, ui);
+                    });
+                }
+                P::Highlight(highlight) => {
+                    if let Ok(highlight) = highlight.resolve(data) {
+                        if highlight { response = response.highlight(); }
+                    }
+                }
+                P::DragSource(payload) => {
+                    response = response.interact(egui::Sense::drag());
+                    if response.drag_started() {
+                        if let Ok(payload) = payload.resolve_ref(data) {
+                            let payload = (response.id, payload.clone());
+                            response.ctx.data_mut(|d| d.insert_temp(Response::drag_payload_id(), payload));
+                        }
+                    }
+                    if response.dragged() {
+                        response.ctx.set_cursor_icon(egui::CursorIcon::Grabbing);
+                    }
+                }
+                P::DropTarget(target) => {
+                    if let (Some(payload), Ok(target)) = (&dropped, target.resolve_mut(data)) {
+                        *target = payload.clone();
+                    }
+                }
+                P::Dropped(action) => {
+                    if dropped.is_some() { action.run(data, &response); }
+                }
+                P::ClickedSound(sound) => {
+                    if response.clicked() {
+                        Response::play_sound(&response.ctx, sound);
+                    }
+                }
+                P::HoveredSound(sound) => {
+                    // only played when pointer enters the widget
+                    let hovered_id = response.id.with("uiconf_hovered");
+                    let was_hovered = response.ctx.data_mut(|d| {
+                        std::mem::replace(d.get_temp_mut_or_default::<bool>(hovered_id), response.hovered())
+                    });
+                    if response.hovered() && !was_hovered {
+                        Response::play_sound(&response.ctx, sound);
+                    }
+                }
+                P::Cursor(cursor) => {
+                    response = response.on_hover_cursor((*cursor).into());
+                }
+                P::FocusOrder(order) => {
+                    FocusOrder::register(&response, *order);
+                }
+                P::RequestFocus(Binding::Ref(focus)) => {
+                    if let Ok(focus) = focus.resolve_mut(data) {
+                        if *focus {
+                            response.request_focus();
+                            *focus = false;
+                        }
+                    }
+                }
+                P::RequestFocus(focus) => {
+                    // widget appears if it wasn't shown in the previous frame
+                    let frame = response.ctx.frame_nr();
+                    let shown_id = response.id.with("uiconf_shown_frame");
+                    let last_shown = response.ctx.data_mut(|d| d.get_temp_mut_or_default::<Option<u64>>(shown_id).replace(frame));
+                    if last_shown.filter(|last| last + 1 == frame).is_none() && focus.resolve(data).unwrap_or(false) {
+                        response.request_focus();
+                    }
+                }
+                P::RectOut(out) => {
+                    let rect = bevy::math::Rect::new(response.rect.min.x, response.rect.min.y, response.rect.max.x, response.rect.max.y);
+                    Response::write_out(data, out, rect);
+                }
+                P::HoveredOut(out) => {
+                    Response::write_out(data, out, response.hovered());
+                }
+                P::ScrollHere(trigger) => {
+                    if trigger.resolve_mut(data).is_ok_and(Trigger::check_reset) {
+                        response.scroll_to_me(Some(egui::Align::Center));
+                    }
+                }
+                P::AccesskitLabel(_) | P::AccesskitDescription(_) | P::AccesskitRole(_) => {}
+            }
+        }
+
+        self.apply_accesskit(data, &response);
+        response
+    }
+
+    // overrides what the widget itself reported, so it's done after the widget is shown
+    #[cfg(feature = "accesskit")]
+    fn apply_accesskit(&self, data: &dyn DataSource, response: &egui::Response) {
+        // context is locked while node is edited, so text is resolved beforehand
+        if response.ctx.accesskit_node_builder(response.id, |_| ()).is_none() { return; }
+
+        for prop in self.0.iter() {
+            match prop {
+                ResponseProperty::AccesskitLabel(label) => {
+                    let Some(label) = label.resolve_text(data, &response.ctx, None) else { continue; };
+                    let label = label.into_owned();
+                    response.ctx.accesskit_node_builder(response.id, |node| node.set_name(label));
+                }
+                ResponseProperty::AccesskitDescription(description) => {
+                    let Some(description) = description.resolve_text(data, &response.ctx, None) else { continue; };
+                    let description = description.into_owned();
+                    response.ctx.accesskit_node_builder(response.id, |node| node.set_description(description));
+                }
+                ResponseProperty::AccesskitRole(role) => {
+                    response.ctx.accesskit_node_builder(response.id, |node| node.set_role((*role).into()));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    #[cfg(not(feature = "accesskit"))]
+    fn apply_accesskit(&self, _data: &dyn DataSource, _response: &egui::Response) {}
+
+    // only written when changed, so that windows with `staging` don't copy the struct every frame
+    fn write_out<T: Reflect + PartialEq>(data: &mut dyn DataSource, out: &BindingRef<T>, value: T) {
+        if out.resolve_ref(data).is_ok_and(|old| *old == value) { return; }
+        if let Ok(out) = out.resolve_mut(data) {
+            *out = value;
+        }
+    }
+
+    // sounds are ignored unless `UiconfAudio` resource is inserted
+    fn play_sound(ctx: &egui::Context, sound: &str) {
+        if let Some(audio) = UiconfAudio::get(ctx) {
+            audio.queue(sound);
+        }
+    }
+
+    // payload is stored together with id of the widget being dragged
+    fn drag_payload_id() -> egui::Id {
+        egui::Id::new("uiconf_drag_payload")
+    }
+
+    // payload offered by `drag_source`, if it was released over this widget in this frame
+    fn dropped_payload(response: &egui::Response) -> Option<String> {
+        let ctx = &response.ctx;
+        let (source, payload) = ctx.data(|d| d.get_temp::<(egui::Id, String)>(Response::drag_payload_id()))?;
+
+        let is_dropped = source != response.id
+            && ctx.memory(|mem| mem.is_being_dragged(source))
+            && ctx.input(|input| input.pointer.any_released())
+            && ctx.pointer_interact_pos().is_some_and(|pos| {
+                response.rect.contains(pos) && ctx.layer_id_at(pos) == Some(response.layer_id)
+            });
+
+        is_dropped.then_some(payload)
+    }
+}
+
+#[derive(Debug)]
+pub enum ResponseProperty {
+    Clicked(ResponseAction),
+    SecondaryClicked(ResponseAction),
+    MiddleClicked(ResponseAction),
+    DoubleClicked(ResponseAction),
+    TripleClicked(ResponseAction),
+    ClickedElsewhere(ResponseAction),
+    Hovered(ResponseAction),
+    Highlighted(ResponseAction),
+    Changed(ResponseAction),
+    OnHover(Content),
+    OnDisabledHover(Content),
+    OnHoverAtPointer(Content),
+    Highlight(Binding<bool>),
+    DragSource(Binding<String>),
+    DropTarget(BindingRef<String>),
+    Dropped(ResponseAction),
+    ClickedSound(String),
+    HoveredSound(String),
+    Cursor(Cursor),
+    // Tab moves focus between widgets of the same window in this order, instead of the order they're shown in
+    FocusOrder(i32),
+    // `request_focus = yes` focuses the widget when it appears, e.g. when window is opened;
+    // `request_focus = @focus_name` focuses it whenever the field is set, and resets the field
+    RequestFocus(Binding<bool>),
+    // read by screen readers, only forwarded when crate is built with `accesskit` feature
+    AccesskitLabel(RichText),
+    AccesskitDescription(RichText),
+    AccesskitRole(AccessRole),
+    // `rect_out = @button_rect`, widget rect in egui points is written into the field every frame,
+    // e.g. for tutorial arrows pointing at the widget
+    RectOut(BindingRef<bevy::math::Rect>),
+    // `hovered_out = @over_button`, whether pointer is over the widget
+    HoveredOut(BindingRef<bool>),
+    // `scroll_here = @item.scroll_here`, scrolls enclosing scroll areas so that the widget is in view
+    ScrollHere(BindingRef<Trigger>),
+}
+
+impl ResponseProperty {
+    const FIELDS: &'static [&'static str] = &[
+        "clicked", "secondary_clicked", "middle_clicked", "double_clicked", "triple_clicked", "clicked_elsewhere",
+        "hovered", "highlighted", "changed", "on_hover", "on_disabled_hover", "on_hover_at_pointer", "highlight",
+        "drag_source", "drop_target", "dropped", "clicked_sound", "hovered_sound", "cursor", "focus_order",
+        "request_focus", "accesskit_label", "accesskit_description", "accesskit_role", "rect_out", "hovered_out",
+        "scroll_here",
+    ];
+
+    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
+        match tag {
+            "clicked"            => Ok(Self::Clicked            (value.read()?)),
+            "secondary_clicked"  => Ok(Self::SecondaryClicked   (value.read()?)),
+            "middle_clicked"     => Ok(Self::MiddleClicked      (value.read()?)),
+            "double_clicked"     => Ok(Self::DoubleClicked      (value.read()?)),
+            "triple_clicked"     => Ok(Self::TripleClicked      (value.read()?)),
+            "clicked_elsewhere"  => Ok(Self::ClickedElsewhere   (value.read()?)),
+            "hovered"            => Ok(Self::Hovered            (value.read()?)),
+            "highlighted"        => Ok(Self::Highlighted        (value.read()?)),
+            "changed"            => Ok(Self::Changed            (value.read()?)),
+            "on_hover"           => Ok(Self::OnHover            (value.read()?)),
+            "on_disabled_hover"  => Ok(Self::OnDisabledHover    (value.read()?)),
+            "on_hover_at_pointer"=> Ok(Self::OnHoverAtPointer   (value.read()?)),
+            "highlight"          => Ok(Self::Highlight          (value.read()?)),
+            "drag_source"        => Ok(Self::DragSource         (value.read()?)),
+            "drop_target"        => Ok(Self::DropTarget         (value.read()?)),
+            "dropped"            => Ok(Self::Dropped            (value.read()?)),
+            "clicked_sound"      => Ok(Self::ClickedSound       (Self::read_sound(value)?)),
+            "hovered_sound"      => Ok(Self::HoveredSound       (Self::read_sound(value)?)),
+            "cursor"             => Ok(Self::Cursor             (value.read()?)),
+            "focus_order"        => Ok(Self::FocusOrder         (value.read()?)),
+            "request_focus"      => Ok(Self::RequestFocus       (value.read()?)),
+            "accesskit_label"    => Ok(Self::AccesskitLabel     (value.read()?)),
+            "accesskit_description" => Ok(Self::AccesskitDescription(value.read()?)),
+            "accesskit_role"     => Ok(Self::AccesskitRole      (value.read()?)),
+            "rect_out"           => Ok(Self::RectOut            (value.read()?)),
+            "hovered_out"        => Ok(Self::HoveredOut         (value.read()?)),
+            "scroll_here"        => Ok(Self::ScrollHere         (value.read()?)),
+            _                    => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+        }
+    }
+
+    fn read_sound(value: &Reader) -> Result<String, Error> {
+        let path = value.read_string()?;
+        value.record_asset(&path);
+        Ok(path)
+    }
+
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Clicked(action)           => writer.field("clicked", action),
+            Self::SecondaryClicked(action)  => writer.field("secondary_clicked", action),
+            Self::MiddleClicked(action)     => writer.field("middle_clicked", action),
+            Self::DoubleClicked(action)     => writer.field("double_clicked", action),
+            Self::TripleClicked(action)     => writer.field("triple_clicked", action),
+            Self::ClickedElsewhere(action)  => writer.field("clicked_elsewhere", action),
+            Self::Hovered(action)           => writer.field("hovered", action),
+            Self::Highlighted(action)       => writer.field("highlighted", action),
+            Self::Changed(action)           => writer.field("changed", action),
+            Self::OnHover(content)          => writer.field("on_hover", content),
+            Self::OnDisabledHover(content)  => writer.field("on_disabled_hover", content),
+            Self::OnHoverAtPointer(content) => writer.field("on_hover_at_pointer", content),
+            Self::Highlight(highlight)      => writer.field("highlight", highlight),
+            Self::DragSource(payload)       => writer.field("drag_source", payload),
+            Self::DropTarget(payload)       => writer.field("drop_target", payload),
+            Self::Dropped(action)           => writer.field("dropped", action),
+            Self::ClickedSound(sound)       => writer.field("clicked_sound", sound),
+            Self::HoveredSound(sound)       => writer.field("hovered_sound", sound),
+            Self::Cursor(cursor)            => writer.field("cursor", cursor),
+            Self::FocusOrder(order)         => writer.field("focus_order", order),
+            Self::RequestFocus(focus)       => writer.field("request_focus", focus),
+            Self::AccesskitLabel(text)      => writer.field("accesskit_label", text),
+            Self::AccesskitDescription(text)=> writer.field("accesskit_description", text),
+            Self::AccesskitRole(role)       => writer.field("accesskit_role", role),
+            Self::RectOut(rect)             => writer.field("rect_out", rect),
+            Self::HoveredOut(hovered)       => writer.field("hovered_out", hovered),
+            Self::ScrollHere(trigger)       => writer.field("scroll_here", trigger),
+        }
+    }
+}
+
+//
+// Cursor
+//
+
+// `cursor = pointer`, mouse cursor shown while the widget is hovered
+#[derive(EnumString, EnumVariantNames, Display, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum Cursor {
+    Default,
+    None,
+    Pointer,
+    Text,
+    Grab,
+    Grabbing,
+    Move,
+    Help,
+    Wait,
+    Progress,
+    Crosshair,
+    NotAllowed,
+    NoDrop,
+    ContextMenu,
+    Copy,
+    Alias,
+    Cell,
+    AllScroll,
+    ResizeHorizontal,
+    ResizeVertical,
+    ResizeNeSw,
+    ResizeNwSe,
+    ZoomIn,
+    ZoomOut,
+}
+
+impl ReadUiconf for Cursor {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let name = value.read_string()?;
+        Self::from_str(&name).map_err(|_| {
+            Error::unknown_variant(value, &name, Self::VARIANTS)
+        })
+    }
+}
+
+impl ToUiconf for Cursor {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.scalar(self);
+    }
+}
+
+impl From<Cursor> for egui::CursorIcon {
+    fn from(cursor: Cursor) -> egui::CursorIcon {
+        match cursor {
+            Cursor::Default          => egui::CursorIcon::Default,
+            Cursor::None             => egui::CursorIcon::None,
+            Cursor::Pointer          => egui::CursorIcon::PointingHand,
+            Cursor::Text             => egui::CursorIcon::Text,
+            Cursor::Grab             => egui::CursorIcon::Grab,
+            Cursor::Grabbing         => egui::CursorIcon::Grabbing,
+            Cursor::Move             => egui::CursorIcon::Move,
+            Cursor::Help             => egui::CursorIcon::Help,
+            Cursor::Wait             => egui::CursorIcon::Wait,
+            Cursor::Progress         => egui::CursorIcon::Progress,
+            Cursor::Crosshair        => egui::CursorIcon::Crosshair,
+            Cursor::NotAllowed       => egui::CursorIcon::NotAllowed,
+            Cursor::NoDrop           => egui::CursorIcon::NoDrop,
+            Cursor::ContextMenu      => egui::CursorIcon::ContextMenu,
+            Cursor::Copy             => egui::CursorIcon::Copy,
+            Cursor::Alias            => egui::CursorIcon::Alias,
+            Cursor::Cell             => egui::CursorIcon::Cell,
+            Cursor::AllScroll        => egui::CursorIcon::AllScroll,
+            Cursor::ResizeHorizontal => egui::CursorIcon::ResizeHorizontal,
+            Cursor::ResizeVertical   => egui::CursorIcon::ResizeVertical,
+            Cursor::ResizeNeSw       => egui::CursorIcon::ResizeNeSw,
+            Cursor::ResizeNwSe       => egui::CursorIcon::ResizeNwSe,
+            Cursor::ZoomIn           => egui::CursorIcon::ZoomIn,
+            Cursor::ZoomOut          => egui::CursorIcon::ZoomOut,
+        }
+    }
+}
+
+//
+// AccessRole
+//
+
+// `accesskit_role = heading`, what the widget is for screen readers, if it's not what egui reports
+#[derive(EnumString, EnumVariantNames, Display, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum AccessRole {
+    Unknown,
+    Label,
+    Paragraph,
+    Heading,
+    Image,
+    Link,
+    Button,
+    ToggleButton,
+    CheckBox,
+    RadioButton,
+    RadioGroup,
+    Switch,
+    Slider,
+    SpinButton,
+    TextInput,
+    ComboBox,
+    List,
+    ListItem,
+    ListBox,
+    Menu,
+    MenuBar,
+    MenuItem,
+    Tab,
+    TabList,
+    TabPanel,
+    Table,
+    Row,
+    Cell,
+    Group,
+    Toolbar,
+    Tooltip,
+    Window,
+    Dialog,
+    Alert,
+    Status,
+    ProgressIndicator,
+    Meter,
+    ScrollView,
+    Splitter,
+}
+
+impl ReadUiconf for AccessRole {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let name = value.read_string()?;
+        Self::from_str(&name).map_err(|_| {
+            Error::unknown_variant(value, &name, Self::VARIANTS)
+        })
+    }
+}
+
+impl ToUiconf for AccessRole {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.scalar(self);
+    }
+}
+
+#[cfg(feature = "accesskit")]
+impl From<AccessRole> for egui::accesskit::Role {
+    fn from(role: AccessRole) -> egui::accesskit::Role {
+        use egui::accesskit::Role;
+        match role {
+            AccessRole::Unknown           => Role::Unknown,
+            AccessRole::Label             => Role::StaticText,
+            AccessRole::Paragraph         => Role::Paragraph,
+            AccessRole::Heading           => Role::Heading,
+            AccessRole::Image             => Role::Image,
+            AccessRole::Link              => Role::Link,
+            AccessRole::Button            => Role::Button,
+            AccessRole::ToggleButton      => Role::ToggleButton,
+            AccessRole::CheckBox          => Role::CheckBox,
+            AccessRole::RadioButton       => Role::RadioButton,
+            AccessRole::RadioGroup        => Role::RadioGroup,
+            AccessRole::Switch            => Role::Switch,
+            AccessRole::Slider            => Role::Slider,
+            AccessRole::SpinButton        => Role::SpinButton,
+            AccessRole::TextInput         => Role::TextInput,
+            AccessRole::ComboBox          => Role::ComboBox,
+            AccessRole::List              => Role::List,
+            AccessRole::ListItem          => Role::ListItem,
+            AccessRole::ListBox           => Role::ListBox,
+            AccessRole::Menu              => Role::Menu,
+            AccessRole::MenuBar           => Role::MenuBar,
+            AccessRole::MenuItem          => Role::MenuItem,
+            AccessRole::Tab               => Role::Tab,
+            AccessRole::TabList           => Role::TabList,
+            AccessRole::TabPanel          => Role::TabPanel,
+            AccessRole::Table             => Role::Table,
+            AccessRole::Row               => Role::Row,
+            AccessRole::Cell              => Role::Cell,
+            AccessRole::Group             => Role::Group,
+            AccessRole::Toolbar           => Role::Toolbar,
+            AccessRole::Tooltip           => Role::Tooltip,
+            AccessRole::Window            => Role::Window,
+            AccessRole::Dialog            => Role::Dialog,
+            AccessRole::Alert             => Role::Alert,
+            AccessRole::Status            => Role::Status,
+            AccessRole::ProgressIndicator => Role::ProgressIndicator,
+            AccessRole::Meter             => Role::Meter,
+            AccessRole::ScrollView        => Role::ScrollView,
+            AccessRole::Splitter          => Role::Splitter,
+        }
+    }
+}
+
+//
+// FocusOrder
+//
+
+// Widgets with `focus_order` shown in a window during this frame, collected per window layer.
+#[derive(Clone, Default)]
+struct FocusOrder(Vec<(i32, egui::Id)>);
+
+impl FocusOrder {
+    fn egui_id(layer: egui::LayerId) -> egui::Id {
+        egui::Id::new("uiconf_focus_order").with(layer)
+    }
+
+    fn register(response: &egui::Response, order: i32) {
+        response.ctx.data_mut(|d| {
+            d.get_temp_mut_or_default::<FocusOrder>(Self::egui_id(response.layer_id)).0.push((order, response.id));
+        });
+    }
+
+    fn begin(ctx: &egui::Context, layer: egui::LayerId) {
+        ctx.data_mut(|d| d.remove::<FocusOrder>(Self::egui_id(layer)));
+    }
+
+    // egui has already moved focus to the next widget in the order they were shown,
+    // so it's overridden here if focus was on one of ordered widgets before Tab was pressed
+    fn end(ctx: &egui::Context, layer: egui::LayerId, focused_before: Option<egui::Id>) {
+        let Some(mut widgets) = ctx.data(|d| d.get_temp::<FocusOrder>(Self::egui_id(layer))) else { return; };
+        let Some(focused_before) = focused_before else { return; };
+        if !ctx.input(|input| input.key_pressed(egui::Key::Tab)) { return; }
+
+        widgets.0.sort_by_key(|(order, _)| *order);
+        let Some(idx) = widgets.0.iter().position(|(_, id)| *id == focused_before) else { return; };
+        let count = widgets.0.len();
+        let next = if ctx.input(|input| input.modifiers.shift) { (idx + count - 1) % count } else { (idx + 1) % count };
+        ctx.memory_mut(|mem| mem.request_focus(widgets.0[next].1));
+    }
+}
+
+//
+// ResponseAction
+//
+
+// What happens when response event fires: `clicked = @trigger` increments trigger field in data model,
+// `clicked = emit(QuitGame)` sends `UiconfCommand` event with that name instead.
+// `save_state(@settings)` and `load_state(@settings)` take and restore a snapshot of the field in `UiconfStates`.
+#[derive(Debug)]
+pub enum ResponseAction {
+    Trigger(BindingRef<Trigger>),
+    Emit {
+        name: String,
+        // path of the widget in config file, e.g. `window.layout.button`
+        widget: String,
+    },
+    SaveState(BindingRef<dyn std::any::Any + Send + Sync>),
+    LoadState(BindingRef<dyn std::any::Any + Send + Sync>),
+    // only work when `UiconfHistory` resource is present
+    Undo,
+    Redo,
+}
+
+impl ResponseAction {
+    fn run(&self, data: &mut dyn DataSource, response: &egui::Response) {
+        match self {
+            Self::Trigger(trigger) => {
+                if let Ok(trigger) = trigger.resolve_mut(data) {
+                    trigger.trigger();
+                }
+            }
+            Self::Emit { name, widget } => {
+                crate::UiconfCommand::queue(&response.ctx, name, widget);
+            }
+            Self::SaveState(binding) => {
+                if let (Some(states), Ok(field)) = (UiconfStates::get(&response.ctx), binding.resolve_reflect(data)) {
+                    states.save(binding.name(), field);
+                }
+            }
+            Self::LoadState(binding) => {
+                if let (Some(states), Ok(field)) = (UiconfStates::get(&response.ctx), binding.resolve_reflect_mut(data)) {
+                    states.load(binding.name(), field);
+                }
+            }
+            Self::Undo => {
+                if let Some(history) = UiconfHistory::get(&response.ctx) {
+                    history.undo();
+                }
+            }
+            Self::Redo => {
+                if let Some(history) = UiconfHistory::get(&response.ctx) {
+                    history.redo();
+                }
+            }
+        }
+    }
+}
+
+impl ReadUiconf for ResponseAction {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        if let TextToken::Unquoted(scalar) = value.token() {
+            let string = scalar.to_string();
+            match &*string {
+                "undo" => return Ok(Self::Undo),
+                "redo" => return Ok(Self::Redo),
+                _ => {}
+            }
+            if let Some(name) = string.strip_prefix("emit(").and_then(|name| name.strip_suffix(')')) {
+                if name.is_empty() {
+                    return Err(Error::invalid_value(value, &string, "emit(name)"));
+                }
+                // path of this value is `<widget>.clicked`
+                let path = value.path();
+                let widget = path.rsplit_once('.').map_or(&*path, |(widget, _)| widget).to_owned();
+                return Ok(Self::Emit { name: name.to_owned(), widget });
+            }
+            if let Some(binding) = string.strip_prefix("save_state(").and_then(|binding| binding.strip_suffix(')')) {
+                return Ok(Self::SaveState(BindingRef::parse(value, binding.trim())?));
+            }
+            if let Some(binding) = string.strip_prefix("load_state(").and_then(|binding| binding.strip_suffix(')')) {
+                return Ok(Self::LoadState(BindingRef::parse(value, binding.trim())?));
+            }
+        }
+
+        Ok(Self::Trigger(value.read()?))
+    }
+}
+
+impl ToUiconf for ResponseAction {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        match self {
+            Self::Trigger(trigger)  => trigger.write_uiconf(writer),
+            Self::Emit { name, .. } => writer.scalar(format!("emit({})", name)),
+            Self::SaveState(field)  => writer.scalar(format!("save_state(@{})", field.name())),
+            Self::LoadState(field)  => writer.scalar(format!("load_state(@{})", field.name())),
+            Self::Undo              => writer.scalar("undo"),
+            Self::Redo              => writer.scalar("redo"),
+        }
+    }
+}
+
+//
+// WidgetId
+//
+
+// `id = name`, keeps egui state (scroll position, collapsed headers, etc.) of a widget
+// when it's moved around in the config file; unique within a window
+#[derive(Debug, Clone)]
+pub struct WidgetId(pub String);
+
+impl WidgetId {
+    pub fn id(&self) -> egui::Id {
+        egui::Id::new(&self.0)
+    }
+
+    // widgets without explicit id are identified by their position in config file
+    fn or_path_id(id: &Option<Self>, value: &Reader) -> egui::Id {
+        id.as_ref().map(Self::id).unwrap_or_else(|| value.get_id())
+    }
+}
+
+impl ReadUiconf for WidgetId {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        Ok(WidgetId(value.read()?))
+    }
+}
+
+impl ToUiconf for WidgetId {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        self.0.write_uiconf(writer);
+    }
+}
+
+//
+// Anchor
+//
+
+#[derive(Debug)]
+pub struct Anchor {
+    pub align: egui::Align2,
+    pub offset: egui::Vec2,
+}
+
+impl ReadUiconf for Anchor {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        const EXPECTED: &str = "{ align valign x y }";
+        let mut seq = value.read_array()?;
+        let align_x = seq.next().ok_or_else(|| Error::invalid_length(value, 0, EXPECTED))?.read::<Alignment>()?;
+        let align_y = seq.next().ok_or_else(|| Error::invalid_length(value, 1, EXPECTED))?.read::<Alignment>()?;
+        let align = Align2::from_alignments(value, align_x, align_y)?.0;
+
+        let offset = if let Some(offset_x) = seq.next() {
+            let offset_x = offset_x.read::<f32>()?;
+            let offset_y = seq.next().ok_or_else(|| Error::invalid_length(value, 3, EXPECTED))?.read::<f32>()?;
+            if seq.next().is_some() {
+                return Err(Error::invalid_length(value, 5, EXPECTED));
+            }
+            egui::Vec2::new(offset_x, offset_y)
+        } else {
+            if seq.next().is_some() {
+                return Err(Error::invalid_length(value, 3, EXPECTED));
+            }
+            egui::Vec2::ZERO
+        };
+
+        Ok(Anchor { align, offset })
+    }
+}
+
+impl ToUiconf for Anchor {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        let (align_x, align_y) = Align2(self.align).to_alignments();
+
+        writer.array(|writer| {
+            writer.item(&align_x);
+            writer.item(&align_y);
+            if self.offset != egui::Vec2::ZERO {
+                writer.item(&self.offset.x);
+                writer.item(&self.offset.y);
+            }
+        });
+    }
+}
+
+//
+// Align2
+//
+
+#[derive(Debug, Clone, Copy)]
+pub struct Align2(pub egui::Align2);
+
+impl Align2 {
+    // accepts alignments in any order, e.g. both `left top` and `top left`
+    fn from_alignments(value: &Reader, mut align_x: Alignment, mut align_y: Alignment) -> Result<Self, Error> {
+        if align_x.can_be_horizontal() && align_y.can_be_vertical() {
+            // all good
+        } else if align_x.can_be_vertical() && align_y.can_be_horizontal() {
+            std::mem::swap(&mut align_x, &mut align_y);
+        } else {
+            return Err(Error::custom(value, format!(
+                "invalid alignment: `{} {}`",
+                align_x.to_string(), align_y.to_string(),
+            )));
+        }
+
+        Ok(Align2(egui::Align2([
+            match align_x {
+                Alignment::Left   => egui::Align::Min,
+                Alignment::Center => egui::Align::Center,
+                Alignment::Right  => egui::Align::Max,
+                _ => unreachable!(),
+            },
+            match align_y {
+                Alignment::Top    => egui::Align::Min,
+                Alignment::Center => egui::Align::Center,
+                Alignment::Bottom => egui::Align::Max,
+                _ => unreachable!(),
+            },
+        ])))
+    }
+
+    fn to_alignments(self) -> (Alignment, Alignment) {
+        let align_x = match self.0.x() {
+            egui::Align::Min    => Alignment::Left,
+            egui::Align::Center => Alignment::Center,
+            egui::Align::Max    => Alignment::Right,
+        };
+        let align_y = match self.0.y() {
+            egui::Align::Min    => Alignment::Top,
+            egui::Align::Center => Alignment::Center,
+            egui::Align::Max    => Alignment::Bottom,
+        };
+        (align_x, align_y)
+    }
+}
+
+impl ReadUiconf for Align2 {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        const EXPECTED: &str = "{ align valign }";
+        let mut seq = value.read_array()?;
+        let align_x = seq.next().ok_or_else(|| Error::invalid_length(value, 0, EXPECTED))?.read::<Alignment>()?;
+        let align_y = seq.next().ok_or_else(|| Error::invalid_length(value, 1, EXPECTED))?.read::<Alignment>()?;
+        if seq.next().is_some() {
+            return Err(Error::invalid_length(value, 3, EXPECTED));
+        }
+        Align2::from_alignments(value, align_x, align_y)
+    }
+}
+
+impl ToUiconf for Align2 {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        let (align_x, align_y) = self.to_alignments();
+        writer.array(|writer| {
+            writer.item(&align_x);
+            writer.item(&align_y);
+        });
+    }
+}
+
+//
+// RichText
+//
+
+#[derive(Debug)]
+pub struct RichText {
+    pub text: TextSource,
+    pub props: Vec<RichTextProperty>,
+}
+
+impl RichText {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["text", "args"],
+        RichTextProperty::FIELDS,
+    );
+
+    pub fn new(text: Binding<String>) -> Self {
+        Self { text: TextSource::Binding(text), props: vec![] }
+    }
+
+    pub fn resolve(&self, data: &dyn DataSource, ctx: &egui::Context) -> egui::WidgetText {
+        self.resolve_with_fallback(data, ctx, None).unwrap_or_else(|| self.with_text(Cow::Borrowed(""), data, ctx, None).into())
+    }
+
+    // returns `None` if text is bound to `Option` field which is `None`, and there is no fallback
+    pub fn resolve_with_fallback(&self, data: &dyn DataSource, ctx: &egui::Context, fallback: Option<&str>) -> Option<egui::WidgetText> {
+        self.resolve_animated(data, ctx, fallback, None)
+    }
+
+    // same as `resolve_with_fallback`, but size and colors are tweened when they change
+    pub fn resolve_animated(&self, data: &dyn DataSource, ctx: &egui::Context, fallback: Option<&str>, tween: Option<&Tween>) -> Option<egui::WidgetText> {
+        if let TextSource::Spans(spans) = &self.text {
+            return Some(self.layout_spans(spans, data, ctx, tween).0.into());
+        }
+
+        let text = self.resolve_text(data, ctx, fallback)?;
+        Some(self.with_text(text, data, ctx, tween).into())
+    }
+
+    // text without formatting, spans can't be nested, so they are never resolved here
+    fn resolve_text<'a>(&'a self, data: &'a dyn DataSource, ctx: &egui::Context, fallback: Option<&'a str>) -> Option<Cow<'a, str>> {
+        match &self.text {
+            TextSource::Binding(text) => match text.resolve_ref(data) {
+                Ok(text) => Some(Cow::Borrowed(text.as_str())),
+                Err(err) if err.is::<NoneValue>() => fallback.map(Cow::Borrowed),
+                Err(_) => Some(Cow::Borrowed("")),
+            },
+            TextSource::Localized(text) => Some(Cow::Owned(text.resolve(data, ctx))),
+            TextSource::Formatted(text) => text.resolve(data, ctx, fallback),
+            TextSource::Spans(_) => None,
+            // placeholder, which is widened to the size of the icon in `layout_spans`
+            TextSource::Icon(_) => Some(Cow::Borrowed(" ")),
+        }
+    }
+
+    // spans inherit formatting of the whole text, and can override it,
+    // spans bound to `None` fields are skipped, so spans for each layout section are returned as well
+    fn layout_spans<'a>(&self, spans: &'a [TextSpan], data: &dyn DataSource, ctx: &egui::Context, tween: Option<&Tween>) -> (egui::text::LayoutJob, Vec<&'a TextSpan>) {
+        let style = ctx.style();
+        let mut job = egui::text::LayoutJob::default();
+        let mut sections = vec![];
+
+        for (idx, span) in spans.iter().enumerate() {
+            let Some(text) = span.text.resolve_text(data, ctx, None) else { continue; };
+            let tween = tween.map(|tween| tween.with(idx));
+            let text = span.text.apply_props(self.with_text(text, data, ctx, tween.as_ref()), data, ctx, tween.as_ref());
+            text.append_to(&mut job, &style, egui::FontSelection::Default, egui::Align::Center);
+            if let (TextSource::Icon(icon), Some(section)) = (&span.text.text, job.sections.last_mut()) {
+                let width = section.format.font_id.size * icon.region.aspect_ratio();
+                let space = ctx.fonts(|fonts| fonts.glyph_width(&section.format.font_id, ' '));
+                section.leading_space = (width - space).max(0.);
+                section.format.color = egui::Color32::TRANSPARENT;
+            }
+            sections.push(span);
+        }
+
+        (job, sections)
+    }
+
+    // Clickable spans and icons need to know where each span ends up, so text is laid out here instead of in egui label.
+    // Returns `None` if there are no such spans.
+    fn layout_links(&self, data: &dyn DataSource, ui: &egui::Ui, tween: Option<&Tween>) -> Option<(std::sync::Arc<egui::Galley>, Vec<&TextSpan>)> {
+        let TextSource::Spans(spans) = &self.text else { return None; };
+        if spans.iter().all(|span| span.clicked.is_none() && !matches!(span.text.text, TextSource::Icon(_))) { return None; }
+
+        let (mut job, sections) = self.layout_spans(spans, data, ui.ctx(), tween);
+        job.wrap.max_width = ui.available_width();
+        Some((ui.fonts(|fonts| fonts.layout_job(job)), sections))
+    }
+
+    // egui needs an owned string, so this is the only place where bound text is copied
+    fn with_text(&self, text: Cow<str>, data: &dyn DataSource, ctx: &egui::Context, tween: Option<&Tween>) -> egui::RichText {
+        self.apply_props(egui::RichText::new(text), data, ctx, tween)
+    }
+
+    fn apply_props(&self, mut result: egui::RichText, data: &dyn DataSource, ctx: &egui::Context, tween: Option<&Tween>) -> egui::RichText {
+        for prop in self.props.iter() {
+            use RichTextProperty as P;
+            match prop {
+                P::Size(size) => {
+                    if let Ok(size) = size.resolve(data) {
+                        // explicit sizes aren't in the style, so window `scale` is applied here
+                        let size = size * UiconfScale::current(ctx);
+                        result = result.size(tween.map_or(size, |tween| tween.f32("size", size)));
+                    }
+                }
+                P::Style(styles) => {
+                    for style in styles {
+                        result = match style {
+                            RichTextStyle::Small         => result.text_style(egui::TextStyle::Small),
+                            RichTextStyle::Body          => result.text_style(egui::TextStyle::Body),
+                            RichTextStyle::Monospace     => result.text_style(egui::TextStyle::Monospace),
+                            RichTextStyle::Button        => result.text_style(egui::TextStyle::Button),
+                            RichTextStyle::Heading       => result.text_style(egui::TextStyle::Heading),
+                            RichTextStyle::Code          => result.code(),
+                            RichTextStyle::Strong        => result.strong(),
+                            RichTextStyle::Weak          => result.weak(),
+                            RichTextStyle::Strikethrough => result.strikethrough(),
+                            RichTextStyle::Underline     => result.underline(),
+                            RichTextStyle::Italics       => result.italics(),
+                            RichTextStyle::Raised        => result.raised(),
+                            RichTextStyle::Named(name)   => result.text_style(egui::TextStyle::Name(name.as_str().into())),
+                        };
+                    }
+                }
+                P::Color(color) => {
+                    if let Ok(color) = color.resolve(data) {
+                        let color = color_bevy_to_egui(color);
+                        result = result.color(tween.map_or(color, |tween| tween.color("color", color)));
+                    }
+                }
+                P::BackgroundColor(color) => {
+                    if let Ok(color) = color.resolve(data) {
+                        let color = color_bevy_to_egui(color);
+                        result = result.background_color(tween.map_or(color, |tween| tween.color("background_color", color)));
+                    }
+                }
+                P::LineHeight(line_height) => {
+                    if let Ok(line_height) = line_height.resolve(data) {
+                        result = result.line_height(Some(line_height));
+                    }
+                }
+                P::ExtraLetterSpacing(spacing) => {
+                    if let Ok(spacing) = spacing.resolve(data) {
+                        result = result.extra_letter_spacing(spacing);
+                    }
+                }
+                P::FontFamily(family) => {
+                    result = result.family(family.clone());
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl ReadUiconf for RichText {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        // either `text = "..."` or `text = { "HP: " { text = @hp  color = red } }` with spans
+        if value.is_scalar() || matches!(value.token(), TextToken::Array { .. }) {
+            return Ok(Self { text: value.read()?, props: vec![] });
+        }
+
+        RichText::read_object(value, None)
+    }
+}
+
+impl RichText {
+    // `clicked` is only allowed in spans, it's read into `clicked` if provided
+    fn read_object(value: &Reader, mut clicked: Option<&mut Option<BindingRef<Trigger>>>) -> Result<Self, Error> {
+        let mut text = None;
+        let mut args = None;
+        let mut props = vec![];
+
+        for (key, value) in value.read_object()? {
+            if key == "text" {
+                if text.is_some() { return Err(Error::duplicate_field(&value, "text")); }
+                text = Some(value.read::<TextSource>()?);
+            } else if let (true, Some(_)) = (key == "icon", clicked.as_deref()) {
+                if text.is_some() { return Err(Error::custom(&value, "span can have either `text` or `icon`")); }
+                text = Some(TextSource::Icon(value.read()?));
+            } else if key == "args" {
+                if args.is_some() { return Err(Error::duplicate_field(&value, "args")); }
+                args = Some(value);
+            } else if let (true, Some(clicked)) = (key == "clicked", clicked.as_deref_mut()) {
+                if clicked.is_some() { return Err(Error::duplicate_field(&value, "clicked")); }
+                *clicked = Some(value.read()?);
+            } else if RichTextProperty::FIELDS.contains(&&*key) {
+                props.push(RichTextProperty::read_map_value(&key, &value)?);
+            } else if clicked.is_some() {
+                value.skip_unknown_field(&key, TextSpan::FIELDS)?;
+            } else {
+                value.skip_unknown_field(&key, RichText::FIELDS)?;
+            }
+        }
+
+        let mut text = text.ok_or_else(|| Error::missing_field(value, "text"))?;
+
+        if let Some(args) = args {
+            let TextSource::Localized(localized) = &mut text else {
+                return Err(Error::custom(&args, "`args` can only be used with `loc(key)` text"));
+            };
+            for (name, value) in args.read_object()? {
+                localized.args.push((name.to_string(), value.read()?));
+            }
+        }
+
+        Ok(Self { text, props })
+    }
+}
+
+impl ToUiconf for RichText {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        let args = match &self.text {
+            TextSource::Localized(localized) => &*localized.args,
+            TextSource::Binding(_) | TextSource::Formatted(_) | TextSource::Spans(_) | TextSource::Icon(_) => &[],
+        };
+
+        if self.props.is_empty() && args.is_empty() && !matches!(self.text, TextSource::Icon(_)) {
+            self.text.write_uiconf(writer);
+            return;
+        }
+
+        writer.object(|writer| self.write_map_values(writer));
+    }
+}
+
+impl RichText {
+    fn write_map_values(&self, writer: &mut Writer) {
+        match &self.text {
+            TextSource::Icon(icon) => writer.field("icon", icon),
+            text => writer.field("text", text),
+        }
+        if let TextSource::Localized(localized) = &self.text {
+            if !localized.args.is_empty() {
+                writer.field_with("args", |writer| writer.object(|writer| {
+                    for (name, binding) in localized.args.iter() {
+                        writer.field(name, binding);
+                    }
+                }));
+            }
+        }
+        for prop in self.props.iter() {
+            prop.write_map_value(writer);
+        }
+    }
+}
+
+//
+// TextSource
+//
+
+#[derive(Debug)]
+pub enum TextSource {
+    Binding(Binding<String>),
+    Localized(Localized),
+    Formatted(Formatted),
+    // rendered as a single layout job, so that one label can mix colors and styles
+    Spans(Vec<TextSpan>),
+    // only used in spans, `{ icon = sword }`
+    Icon(Icon),
+}
+
+impl ReadUiconf for TextSource {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        if matches!(value.token(), TextToken::Array { .. }) {
+            let mut spans = vec![];
+            for value in value.read_array()? {
+                let span = value.read::<TextSpan>()?;
+                if matches!(span.text.text, TextSource::Spans(_)) {
+                    return Err(Error::custom(&value, "text spans can't be nested"));
+                }
+                spans.push(span);
+            }
+            return Ok(Self::Spans(spans));
+        }
+
+        if let Ok(string) = value.read_string() {
+            if let Some(key) = string.strip_prefix("loc(").and_then(|key| key.strip_suffix(')')) {
+                let key = key.trim_matches('"');
+                if key.is_empty() {
+                    return Err(Error::invalid_value(value, &string, "loc(key)"));
+                }
+                return Ok(Self::Localized(Localized { key: key.to_owned(), args: vec![], warned: AtomicBool::new(false) }));
+            }
+
+            if let Some((binding, filter)) = string.split_once('|').filter(|_| string.starts_with('@')) {
+                let (name, arg) = match filter.split_once(':') {
+                    Some((name, arg)) => (name.trim(), Some(arg.trim().to_owned())),
+                    None => (filter.trim(), None),
+                };
+                if name.is_empty() {
+                    return Err(Error::invalid_value(value, &string, "@ref | formatter"));
+                }
+                return Ok(Self::Formatted(Formatted {
+                    binding: BindingRef::parse(value, binding.trim())?,
+                    formatter: name.to_owned(),
+                    arg,
+                    warned: AtomicBool::new(false),
+                }));
+            }
+        }
+
+        Ok(Self::Binding(value.read()?))
+    }
+}
+
+impl ToUiconf for TextSource {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        match self {
+            Self::Binding(text) => text.write_uiconf(writer),
+            Self::Localized(localized) => writer.scalar(format!("loc({})", localized.key)),
+            Self::Formatted(formatted) => match &formatted.arg {
+                Some(arg) => writer.quoted(&format!("@{} | {}:{}", formatted.binding.name(), formatted.formatter, arg)),
+                None => writer.quoted(&format!("@{} | {}", formatted.binding.name(), formatted.formatter)),
+            },
+            Self::Spans(spans) => writer.array(|writer| {
+                for span in spans.iter() {
+                    writer.item(span);
+                }
+            }),
+            Self::Icon(icon) => icon.write_uiconf(writer),
+        }
+    }
+}
+
+//
+// TextSpan
+//
+
+// Part of text with its own formatting, `{ text = "here" color = blue clicked = @open_link }`,
+// `clicked` only works in labels.
+#[derive(Debug)]
+pub struct TextSpan {
+    pub text: RichText,
+    pub clicked: Option<BindingRef<Trigger>>,
+}
+
+impl TextSpan {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["clicked", "icon"],
+        RichText::FIELDS,
+    );
+
+    // span under the pointer, `pos` is relative to galley
+    fn find<'a>(galley: &egui::Galley, sections: &[&'a TextSpan], pos: egui::Vec2) -> Option<&'a TextSpan> {
+        let glyph = galley.rows.iter()
+            .flat_map(|row| row.glyphs.iter())
+            .find(|glyph| glyph.logical_rect().contains(pos.to_pos2()))?;
+        sections.get(glyph.section_index as usize).copied()
+    }
+}
+
+impl ReadUiconf for TextSpan {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        if value.is_scalar() || matches!(value.token(), TextToken::Array { .. }) {
+            return Ok(Self { text: value.read()?, clicked: None });
+        }
+
+        let mut clicked = None;
+        let text = RichText::read_object(value, Some(&mut clicked))?;
+        Ok(Self { text, clicked })
+    }
+}
+
+impl ToUiconf for TextSpan {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        let Some(clicked) = &self.clicked else {
+            self.text.write_uiconf(writer);
+            return;
+        };
+
+        writer.object(|writer| {
+            self.text.write_map_values(writer);
+            writer.field("clicked", clicked);
+        });
+    }
+}
+
+//
+// Icon
+//
+
+// Part of a texture shown inline with text: `text = { "Attack: " { icon = sword } }`, it's as tall as the font
+// of the span. Icons are declared in `icons` section, or inline with `{ texture = "ui/icons.png" region = { 0 0 16 16 } }`.
+// Only labels paint icons, other widgets leave blank space in their place.
+#[derive(Debug, Clone)]
+pub struct Icon {
+    pub texture: String,
+    // in pixels
+    pub region: egui::Rect,
+}
+
+impl Icon {
+    const FIELDS: &'static [&'static str] = &["texture", "region"];
+
+    pub(crate) fn read_region(value: &Reader) -> Result<egui::Rect, Error> {
+        const EXPECTED: &str = "{ x y width height }";
+        let mut seq = value.read_array()?;
+        let mut next = |idx| seq.next().ok_or_else(|| Error::invalid_length(value, idx, EXPECTED))?.read::<f32>();
+        let [x, y, width, height] = [next(0)?, next(1)?, next(2)?, next(3)?];
+        if seq.next().is_some() {
+            return Err(Error::invalid_length(value, 5, EXPECTED));
+        }
+        if width <= 0. || height <= 0. {
+            return Err(Error::custom(value, "icon region should have positive size"));
+        }
+        Ok(egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(width, height)))
+    }
+
+    // icons are laid out as spaces, and painted over them once position of the text is known
+    fn paint(galley: &egui::Galley, sections: &[&TextSpan], painter: &egui::Painter, origin: egui::Pos2) {
+        let Some(images) = UiconfImages::get(painter.ctx()) else { return; };
+
+        for glyph in galley.rows.iter().flat_map(|row| row.glyphs.iter()) {
+            let Some(TextSource::Icon(icon)) = sections.get(glyph.section_index as usize).map(|span| &span.text.text) else { continue; };
+            // nothing is painted until the image is loaded
+            let Some(texture) = images.texture(&icon.texture) else { continue; };
+            let Some(size) = images.size(&icon.texture) else { continue; };
+
+            let section = &galley.job.sections[glyph.section_index as usize];
+            let height = section.format.font_id.size;
+            let width = height * icon.region.aspect_ratio();
+            let glyph_rect = glyph.logical_rect();
+            let rect = egui::Rect::from_min_size(
+                egui::pos2(glyph_rect.min.x - section.leading_space, glyph_rect.center().y - height / 2.),
+                egui::vec2(width, height),
+            );
+            let uv = egui::Rect::from_min_max((icon.region.min.to_vec2() / size).to_pos2(), (icon.region.max.to_vec2() / size).to_pos2());
+            painter.image(texture, rect.translate(origin.to_vec2()), uv, egui::Color32::WHITE);
+        }
+    }
+}
+
+impl ReadUiconf for Icon {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        if value.is_scalar() {
+            let name = value.read_string()?;
+            return value.icon(&name).ok_or_else(|| Error::custom(value, format!(
+                "unknown icon `{}`, icons should be defined in `icons` section",
+                name,
+            )));
+        }
+
+        let mut texture = None;
+        let mut region = None;
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "texture" => {
+                    if texture.is_some() { return Err(Error::duplicate_field(&value, "texture")); }
+                    let path = value.read_string()?;
+                    value.record_asset(&path);
+                    texture = Some(path);
+                }
+                "region" => {
+                    if region.is_some() { return Err(Error::duplicate_field(&value, "region")); }
+                    region = Some(Icon::read_region(&value)?);
+                }
+                str => {
+                    value.skip_unknown_field(str, Icon::FIELDS)?;
+                }
+            }
+        }
+
+        let texture = texture.ok_or_else(|| Error::missing_field(value, "texture"))?;
+        let region = region.ok_or_else(|| Error::missing_field(value, "region"))?;
+
+        Ok(Icon { texture, region })
+    }
+}
+
+// names from `icons` section aren't kept, so icons are always written in full
+impl ToUiconf for Icon {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            writer.field("texture", &self.texture);
+            writer.field_with("region", |writer| writer.array(|writer| {
+                writer.item(&self.region.min.x);
+                writer.item(&self.region.min.y);
+                writer.item(&self.region.width());
+                writer.item(&self.region.height());
+            }));
+        });
+    }
+}
+
+//
+// Localized
+//
+
+// `text = loc(key)`, translated with `UiconfLocalizer`, with optional arguments from data model:
+// `text = { text = loc(gold_amount)  args = { amount = @gold } }`
+#[derive(Debug)]
+pub struct Localized {
+    pub key: String,
+    pub args: Vec<(String, BindingRef<dyn std::any::Any + Send + Sync>)>,
+    warned: AtomicBool,
+}
+
+impl Localized {
+    // falls back to the key itself if there is no localizer or key is unknown
+    fn resolve(&self, data: &dyn DataSource, ctx: &egui::Context) -> String {
+        let args = self.args.iter()
+            .filter_map(|(name, binding)| {
+                let field = binding.resolve_reflect(data).ok()?;
+                Some((name.as_str(), LocArg::from_reflect(field)?))
+            })
+            .collect::<Vec<_>>();
+
+        let Some(localizer) = UiconfLocalizer::get(ctx) else { return self.key.clone(); };
+        localizer.0.localize(&self.key, &args).unwrap_or_else(|| {
+            if !self.warned.fetch_or(true, std::sync::atomic::Ordering::Relaxed) {
+                crate::diagnostics::report(
+                    crate::DiagnosticKind::UnknownLocalizationKey,
+                    format!("unknown localization key `{}`", self.key),
+                );
+            }
+            self.key.clone()
+        })
+    }
+}
+
+//
+// Formatted
+//
+
+// `text = "@gold | thousands"`, bound value of any type is turned into text with one of `UiconfFormatters`,
+// formatter argument follows the colon: `"@ratio | percent:1"`
+#[derive(Debug)]
+pub struct Formatted {
+    pub binding: BindingRef<dyn std::any::Any + Send + Sync>,
+    pub formatter: String,
+    pub arg: Option<String>,
+    warned: AtomicBool,
+}
+
+impl Formatted {
+    // `None` fields show fallback, values that formatter doesn't accept show nothing
+    fn resolve<'a>(&self, data: &dyn DataSource, ctx: &egui::Context, fallback: Option<&'a str>) -> Option<Cow<'a, str>> {
+        let Ok(mut value) = self.binding.resolve_reflect(data) else { return Some(Cow::Borrowed("")); };
+
+        if let bevy::reflect::ReflectRef::Enum(option) = value.reflect_ref() {
+            if option.reflect_type_path().starts_with("core::option::Option<") {
+                let Some(inner) = option.field_at(0) else { return fallback.map(Cow::Borrowed); };
+                value = inner;
+            }
+        }
+
+        let formatters = UiconfFormatters::get(ctx).unwrap_or_default();
+        let result = formatters.format(&self.formatter, value, self.arg.as_deref());
+        if result.is_none() && !self.warned.fetch_or(true, std::sync::atomic::Ordering::Relaxed) {
+            let message = if formatters.contains(&self.formatter) {
+                format!("formatter `{}` can't format @{}", self.formatter, self.binding.name())
+            } else {
+                format!("unknown formatter `{}` used for @{}", self.formatter, self.binding.name())
+            };
+            crate::diagnostics::report(crate::DiagnosticKind::Formatter, message);
+        }
+        Some(Cow::Owned(result.unwrap_or_default()))
+    }
+}
+
+//
+// RichTextProperty
+//
+
+#[derive(Debug)]
+pub enum RichTextProperty {
+    Size(Binding<f32>),
+    Style(Vec<RichTextStyle>),
+    Color(Binding<bevy::prelude::Color>),
+    BackgroundColor(Binding<bevy::prelude::Color>),
+    LineHeight(Binding<f32>),
+    ExtraLetterSpacing(Binding<f32>),
+    FontFamily(egui::FontFamily),
+}
+
+impl RichTextProperty {
+    const FIELDS: &'static [&'static str] = &[
+        "size", "style", "color", "background_color", "line_height", "extra_letter_spacing", "font_family",
+    ];
+
+    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
+        match tag {
+            "size"                 => Ok(Self::Size               (value.read()?)),
+            "extra_letter_spacing" => Ok(Self::ExtraLetterSpacing (value.read()?)),
+            "line_height"          => Ok(Self::LineHeight         (value.read()?)),
+            "style"                => Ok(Self::Style              (value.read()?)),
+            "background_color"     => Ok(Self::BackgroundColor    (value.read()?)),
+            "color"                => Ok(Self::Color              (value.read()?)),
+            "font_family"          => Ok(Self::FontFamily         (read_font_family(value)?)),
+            _ => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+        }
+    }
+
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Size(size)                  => writer.field("size", size),
+            Self::Style(styles)               => writer.field("style", styles),
+            Self::Color(color)                => writer.field("color", color),
+            Self::BackgroundColor(color)      => writer.field("background_color", color),
+            Self::LineHeight(line_height)     => writer.field("line_height", line_height),
+            Self::ExtraLetterSpacing(spacing) => writer.field("extra_letter_spacing", spacing),
+            Self::FontFamily(family) => {
+                writer.field_with("font_family", |writer| writer.scalar(font_family_name(family)));
+            }
+        }
+    }
+}
+
+//
+// RichTextStyle
+//
+
+#[derive(EnumString, EnumVariantNames, Display, Debug, Clone)]
+#[strum(serialize_all = "snake_case")]
+pub enum RichTextStyle {
+    Small,
+    Body,
+    Monospace,
+    Button,
+    Heading,
+    Code,
+    Strong,
+    Weak,
+    Strikethrough,
+    Underline,
+    Italics,
+    Raised,
+    // defined in `text_styles`
+    #[strum(default)]
+    Named(String),
+}
+
+impl ReadUiconf for RichTextStyle {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let name = value.read_string()?;
+        let style = Self::from_str(&name).map_err(|_| {
+            Error::unknown_variant(value, &name, Self::VARIANTS)
+        })?;
+
+        if matches!(style, Self::Named(_)) && !value.is_text_style_defined(&name) {
+            return Err(Error::custom(value, format!(
+                "unknown text style `{}`, expected built-in style or one defined in `text_styles`",
+                name,
+            )));
+        }
+
+        Ok(style)
+    }
+}
+
+impl ToUiconf for RichTextStyle {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.scalar(self);
+    }
+}
+
+//
+// Button
+//
+
+#[derive(Debug)]
+pub struct Button {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub animate: Option<Animate>,
+    pub text: RichText,
+    // shown when text is bound to `Option` field which is `None`
+    pub fallback: Option<String>,
+    pub small: bool,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub size_limits: SizeLimits,
+    pub props: Vec<ButtonProperty>,
+    pub response: Response,
+}
+
+impl Button {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "animate", "text", "fallback", "small", "visible", "groups"],
+        SizeLimits::FIELDS,
+        ButtonProperty::FIELDS,
+        ResponseProperty::FIELDS,
+    );
+
+    pub fn new(text: RichText) -> Self {
+        Self {
+            id: None,
+            enabled: None,
+            transition: None,
+            animate: None,
+            text,
+            fallback: None,
+            small: false,
+            visible: None,
+            groups: vec![],
+            size_limits: SizeLimits::default(),
+            props: vec![],
+            response: Response(vec![]),
+        }
+    }
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
+        let tween = self.animate.as_ref().map(|animate| Tween::new(animate, ui.ctx(), ui.next_auto_id()));
+        let text = self.text.resolve_animated(data, ui.ctx(), self.fallback.as_deref(), tween.as_ref())?;
+        let mut button = egui::Button::new(text);
+
+        if self.small {
+            button = button.small();
+        }
+
+        for prop in self.props.iter() {
+            use ButtonProperty as P;
+            button = match prop {
+                P::Shortcut(shortcut) => button.shortcut_text(ui.ctx().format_shortcut(&shortcut.0)),
+                P::ShortcutText(text) => button.shortcut_text(text.resolve(data, ui.ctx())),
+                P::Wrap(wrap) => button.wrap(*wrap),
+                P::Fill(color) => {
+                    if let Ok(color) = color.resolve(data) {
+                        let color = color_bevy_to_egui(color);
+                        button.fill(tween.as_ref().map_or(color, |tween| tween.color("fill", color)))
+                    } else {
+                        button
+                    }
+                }
+                P::Stroke(stroke) => {
+                    if let Ok(stroke) = stroke.resolve(data) {
+                        button.stroke(stroke)
+                    } else {
+                        button
+                    }
+                }
+                P::Sense(sense)       => button.sense(sense.0),
+                P::Frame(frame)       => button.frame(*frame),
+                P::MinSize(size)      => button.min_size(*size),
+                P::Rounding(rounding) => button.rounding(*rounding),
+                P::Selected(selected) => button.selected(*selected),
+                P::Background(_)      => button,
+                P::Custom(_, prop)    => prop.apply_button(data, button),
+            };
+        }
+
+        // background replaces fill, but button still shows stroke when hovered
+        let background = self.props.iter().rev().find_map(|prop| match prop {
+            ButtonProperty::Background(background) => Some((background, Background::placeholder(ui.painter()))),
+            _ => None,
+        });
+        if background.is_some() {
+            button = button.fill(egui::Color32::TRANSPARENT);
+        }
+
+        let mut response = ui.add(button);
+        if let Some((background, idx)) = background {
+            background.paint(data, ui.painter(), idx, response.rect);
+        }
+
+        // pressing the shortcut counts as a click, but only in the topmost window
+        for prop in self.props.iter() {
+            if let ButtonProperty::Shortcut(shortcut) = prop {
+                let is_top = ui.ctx().memory(|mem| {
+                    mem.layer_ids().filter(|layer| layer.order == egui::Order::Middle).last()
+                }) == Some(ui.layer_id());
+                if response.enabled && is_top && ui.input_mut(|input| input.consume_shortcut(&shortcut.0)) {
+                    response.clicked[egui::PointerButton::Primary as usize] = true;
+                }
+            }
+        }
+
+        for prop in self.props.iter() {
+            if let ButtonProperty::Custom(_, prop) = prop {
+                prop.process_response(data, &response);
+            }
+        }
+
+        Some(self.response.process(data, response))
+    }
+}
+
+impl ReadUiconf for Button {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        if value.is_scalar() {
+            return Ok(Self::new(value.read()?));
+        }
+
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut animate = None;
+        let mut text = None;
+        let mut fallback = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut size_limits = SizeLimits::default();
+        let mut small = false;
+        let mut props = vec![];
+        let mut response = vec![];
+
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "id" => {
+                    if id.is_some() { return Err(Error::duplicate_field(&value, "id")); }
+                    id = Some(value.read()?);
+                }
+                "enabled" => {
+                    if enabled.is_some() { return Err(Error::duplicate_field(&value, "enabled")); }
+                    enabled = Some(value.read()?);
+                }
+                "transition" => {
+                    if transition.is_some() { return Err(Error::duplicate_field(&value, "transition")); }
+                    transition = Some(value.read()?);
+                }
+                "animate" => {
+                    if animate.is_some() { return Err(Error::duplicate_field(&value, "animate")); }
+                    animate = Some(value.read()?);
+                }
+                "text" => {
+                    if text.is_some() { return Err(Error::duplicate_field(&value, "text")); }
+                    text = Some(value.read()?);
+                }
+                "fallback" => {
+                    if fallback.is_some() { return Err(Error::duplicate_field(&value, "fallback")); }
+                    fallback = Some(value.read()?);
+                }
+                "visible" => {
+                    if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
+                    visible = Some(value.read()?);
+                }
+                "groups" => {
+                    if !groups.is_empty() { return Err(Error::duplicate_field(&value, "groups")); }
+                    groups = value.read()?;
+                }
+                "small" => {
+                    small = value.read()?;
+                }
+                str => {
+                    if SizeLimits::FIELDS.contains(&str) {
+                        size_limits.read_map_value(str, &value)?;
+                    } else if ButtonProperty::FIELDS.contains(&str) {
+                        props.push(ButtonProperty::read_map_value(&key, &value)?);
+                    } else if ResponseProperty::FIELDS.contains(&str) {
+                        response.push(ResponseProperty::read_map_value(&key, &value)?);
+                    } else if let Some(prop) = value.widgets().read_property("button", str, &value) {
+                        props.push(ButtonProperty::Custom(str.to_owned(), prop?));
+                    } else {
+                        value.skip_unknown_field(&key, Button::FIELDS)?;
+                    }
+                }
+            }
+        }
+
+        let text = text.ok_or_else(|| Error::missing_field(value, "text"))?;
+
+        Ok(Button { id, enabled, transition, animate, text, fallback, visible, groups, size_limits, small, props, response: Response(response) })
+    }
+}
+
+impl ToUiconf for Button {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        if self.id.is_none() && self.enabled.is_none() && self.transition.is_none() && self.animate.is_none() && self.fallback.is_none() && !self.small && self.visible.is_none() && self.groups.is_empty() && self.size_limits.is_empty() && self.props.is_empty() && self.response.0.is_empty() {
+            self.text.write_uiconf(writer);
+            return;
+        }
+
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(animate) = &self.animate {
+                writer.field("animate", animate);
+            }
+            writer.field("text", &self.text);
+            if let Some(fallback) = &self.fallback {
+                writer.field("fallback", fallback);
+            }
+            if self.small {
+                writer.field("small", &self.small);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            self.size_limits.write_map_values(writer);
+            for prop in self.props.iter() {
+                prop.write_map_value(writer);
+            }
+            self.response.write_map_values(writer);
+        });
+    }
+}
+
+//
+// ButtonProperty
+//
+
+#[derive(Debug)]
+pub enum ButtonProperty {
+    Shortcut(Shortcut),
+    ShortcutText(RichText),
+    Wrap(bool),
+    Fill(Binding<bevy::prelude::Color>),
+    Stroke(Stroke),
+    Sense(Sense),
+    Frame(bool),
+    MinSize(egui::Vec2),
+    Rounding(egui::Rounding),
+    Selected(bool),
+    Background(Background),
+    // registered in `UiconfWidgetRegistry`
+    Custom(String, Arc<dyn CustomProperty>),
+}
+
+impl ButtonProperty {
+    const FIELDS: &'static [&'static str] = &[
+        "shortcut", "shortcut_text", "wrap", "fill", "stroke", "sense", "frame", "min_size", "rounding", "selected",
+        "background",
+    ];
+
+    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
+        match tag {
+            "shortcut"      => Ok(Self::Shortcut     (value.read()?)),
+            "shortcut_text" => Ok(Self::ShortcutText (value.read()?)),
+            "wrap"          => Ok(Self::Wrap         (value.read()?)),
+            "fill"          => Ok(Self::Fill         (value.read()?)),
+            "stroke"        => Ok(Self::Stroke       (value.read()?)),
+            "sense"         => Ok(Self::Sense        (value.read()?)),
+            "frame"         => Ok(Self::Frame        (value.read()?)),
+            "min_size"      => Ok(Self::MinSize      (value.read::<Size<{ SIZE_ANY_IS_ZERO }>>()?.0)),
+            "rounding"      => Ok(Self::Rounding     (value.read::<Rounding>()?.0)),
+            "selected"      => Ok(Self::Selected     (value.read()?)),
+            "background"    => Ok(Self::Background   (value.read()?)),
+            _               => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+        }
+    }
+
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Shortcut(shortcut)  => writer.field("shortcut", shortcut),
+            Self::ShortcutText(text)  => writer.field("shortcut_text", text),
+            Self::Wrap(wrap)          => writer.field("wrap", wrap),
+            Self::Fill(color)         => writer.field("fill", color),
+            Self::Stroke(stroke)      => writer.field("stroke", stroke),
+            Self::Sense(sense)        => writer.field("sense", sense),
+            Self::Frame(frame)        => writer.field("frame", frame),
+            Self::MinSize(size)       => writer.field("min_size", &Size::<{ SIZE_ANY_IS_ZERO }>(*size)),
+            Self::Rounding(rounding)  => writer.field("rounding", &Rounding(*rounding)),
+            Self::Selected(selected)  => writer.field("selected", selected),
+            Self::Background(image)   => writer.field("background", image),
+            Self::Custom(name, prop)  => writer.field(name, &**prop),
+        }
+    }
+}
+
+//
+// Shortcut
+//
+
+// `shortcut = "Ctrl+Shift+S"`, modifiers are `Ctrl`, `Shift`, `Alt`, `Cmd` (Ctrl on Windows/Linux)
+#[derive(Debug, Clone, Copy)]
+pub struct Shortcut(pub egui::KeyboardShortcut);
+
+impl Shortcut {
+    const KEYS: &'static [egui::Key] = {
+        use egui::Key::*;
+        &[
+            ArrowDown, ArrowLeft, ArrowRight, ArrowUp, Escape, Tab, Backspace, Enter, Space,
+            Insert, Delete, Home, End, PageUp, PageDown, Minus, PlusEquals,
+            Num0, Num1, Num2, Num3, Num4, Num5, Num6, Num7, Num8, Num9,
+            A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+            F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15, F16, F17, F18, F19, F20,
+        ]
+    };
+
+    fn parse(value: &Reader, string: &str) -> Result<Self, Error> {
+        const EXPECTED: &str = "shortcut like `Ctrl+S`";
+        let mut modifiers = egui::Modifiers::NONE;
+        let mut parts = string.split('+').map(str::trim).collect::<Vec<_>>();
+        let key = parts.pop().unwrap_or_default();
+
+        for part in parts {
+            modifiers = modifiers.plus(match &*part.to_ascii_lowercase() {
+                "ctrl"            => egui::Modifiers::CTRL,
+                "shift"           => egui::Modifiers::SHIFT,
+                "alt"             => egui::Modifiers::ALT,
+                "cmd" | "command" => egui::Modifiers::COMMAND,
+                _                 => return Err(Error::invalid_value(value, string, EXPECTED)),
+            });
+        }
+
+        // key names are the same as shown by egui, e.g. `S`, `F5`, `Enter`, `Up`
+        let key = Self::KEYS.iter()
+            .find(|k| k.name().eq_ignore_ascii_case(key))
+            .ok_or_else(|| Error::invalid_value(value, string, EXPECTED))?;
+
+        Ok(Shortcut(egui::KeyboardShortcut::new(modifiers, *key)))
+    }
+}
+
+impl ReadUiconf for Shortcut {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        Shortcut::parse(value, &value.read::<String>()?)
+    }
+}
+
+impl ToUiconf for Shortcut {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        let modifiers = self.0.modifiers;
+        let mut string = String::new();
+        if modifiers.ctrl  { string.push_str("Ctrl+"); }
+        if modifiers.command && !modifiers.ctrl { string.push_str("Cmd+"); }
+        if modifiers.alt   { string.push_str("Alt+"); }
+        if modifiers.shift { string.push_str("Shift+"); }
+        string.push_str(self.0.key.name());
+        writer.quoted(&string);
+    }
+}
+
+//
+// Label
+//
+
+#[derive(Debug)]
+pub struct Label {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub animate: Option<Animate>,
+    pub text: RichText,
+    // shown when text is bound to `Option` field which is `None`
+    pub fallback: Option<String>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub size_limits: SizeLimits,
+    pub props: Vec<LabelProperty>,
+    pub response: Response,
+}
+
+impl Label {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "animate", "text", "fallback", "visible", "groups"],
+        SizeLimits::FIELDS,
+        LabelProperty::FIELDS,
+        ResponseProperty::FIELDS,
+    );
+
+    pub fn new(text: RichText) -> Self {
+        Self {
+            id: None,
+            enabled: None,
+            transition: None,
+            animate: None,
+            text,
+            fallback: None,
+            visible: None,
+            groups: vec![],
+            size_limits: SizeLimits::default(),
+            props: vec![],
+            response: Response(vec![]),
+        }
+    }
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
+        let tween = self.animate.as_ref().map(|animate| Tween::new(animate, ui.ctx(), ui.next_auto_id()));
+        let links = self.text.layout_links(data, ui, tween.as_ref());
+        let mut label = if let Some((galley, sections)) = &links {
+            let label = egui::Label::new(egui::WidgetText::Galley(galley.clone()));
+            if sections.iter().any(|span| span.clicked.is_some()) { label.sense(egui::Sense::click()) } else { label }
+        } else {
+            let text = self.text.resolve_animated(data, ui.ctx(), self.fallback.as_deref(), tween.as_ref())?;
+            egui::Label::new(text)
+        };
+
+        for prop in self.props.iter() {
+            use LabelProperty as P;
+            label = match prop {
+                P::Wrap(wrap)         => label.wrap(*wrap),
+                P::Truncate(truncate) => label.truncate(*truncate),
+                P::Sense(sense)       => label.sense(sense.0),
+                P::Custom(_, prop)    => prop.apply_label(data, label),
+            };
+        }
+
+        let response = ui.add(label);
+
+        for prop in self.props.iter() {
+            if let LabelProperty::Custom(_, prop) = prop {
+                prop.process_response(data, &response);
+            }
+        }
+
+        if let Some((galley, sections)) = &links {
+            Icon::paint(galley, sections, ui.painter(), response.rect.left_top());
+            let span = response.hover_pos().and_then(|pos| TextSpan::find(galley, sections, pos - response.rect.left_top()));
+            if let Some(clicked) = span.and_then(|span| span.clicked.as_ref()) {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                if response.clicked() {
+                    if let Ok(clicked) = clicked.resolve_mut(data) {
+                        clicked.trigger();
+                    }
+                }
+            }
+        }
+
+        Some(self.response.process(data, response))
+    }
+}
+
+impl ReadUiconf for Label {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        if value.is_scalar() {
+            return Ok(Self::new(value.read()?));
+        }
+
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut animate = None;
+        let mut text = None;
+        let mut fallback = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut size_limits = SizeLimits::default();
+        let mut props = vec![];
+        let mut response = vec![];
+
+        for (key, value) in value.read_object()? {
+            if key == "id" {
+                if id.is_some() { return Err(Error::duplicate_field(&value, "id")); }
+                id = Some(value.read()?);
+            } else if key == "enabled" {
+                if enabled.is_some() { return Err(Error::duplicate_field(&value, "enabled")); }
+                enabled = Some(value.read()?);
+            } else if key == "transition" {
+                if transition.is_some() { return Err(Error::duplicate_field(&value, "transition")); }
+                transition = Some(value.read()?);
+            } else if key == "animate" {
+                if animate.is_some() { return Err(Error::duplicate_field(&value, "animate")); }
+                animate = Some(value.read()?);
+            } else if key == "text" {
+                if text.is_some() { return Err(Error::duplicate_field(&value, "text")); }
+                text = Some(value.read()?);
+            } else if key == "fallback" {
+                if fallback.is_some() { return Err(Error::duplicate_field(&value, "fallback")); }
+                fallback = Some(value.read()?);
+            } else if SizeLimits::FIELDS.contains(&&*key) {
+                size_limits.read_map_value(&key, &value)?;
+            } else if key == "visible" {
+                if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
+                visible = Some(value.read()?);
+            } else if key == "groups" {
+                if !groups.is_empty() { return Err(Error::duplicate_field(&value, "groups")); }
+                groups = value.read()?;
+            } else if LabelProperty::FIELDS.contains(&&*key) {
+                props.push(LabelProperty::read_map_value(&key, &value)?);
+            } else if ResponseProperty::FIELDS.contains(&&*key) {
+                response.push(ResponseProperty::read_map_value(&key, &value)?);
+            } else if let Some(prop) = value.widgets().read_property("label", &key, &value) {
+                props.push(LabelProperty::Custom(key.to_string(), prop?));
+            } else {
+                value.skip_unknown_field(&key, Label::FIELDS)?;
+            }
+        }
+
+        let text = text.ok_or_else(|| Error::missing_field(value, "text"))?;
+
+        Ok(Label { id, enabled, transition, animate, text, fallback, visible, groups, size_limits, props, response: Response(response) })
+    }
+}
+
+impl ToUiconf for Label {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        if self.id.is_none() && self.enabled.is_none() && self.transition.is_none() && self.animate.is_none() && self.fallback.is_none() && self.visible.is_none() && self.groups.is_empty() && self.size_limits.is_empty() && self.props.is_empty() && self.response.0.is_empty() {
+            self.text.write_uiconf(writer);
+            return;
+        }
+
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(animate) = &self.animate {
+                writer.field("animate", animate);
+            }
+            writer.field("text", &self.text);
+            if let Some(fallback) = &self.fallback {
+                writer.field("fallback", fallback);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            self.size_limits.write_map_values(writer);
+            for prop in self.props.iter() {
+                prop.write_map_value(writer);
+            }
+            self.response.write_map_values(writer);
+        });
+    }
+}
+
+//
+// LabelProperty
+//
+
+#[derive(Debug, Clone)]
+pub enum LabelProperty {
+    Wrap(bool),
+    Truncate(bool),
+    Sense(Sense),
+    // registered in `UiconfWidgetRegistry`
+    Custom(String, Arc<dyn CustomProperty>),
+}
+
+impl LabelProperty {
+    const FIELDS: &'static [&'static str] = &["wrap", "truncate", "sense"];
+
+    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
+        match tag {
+            "wrap"     => Ok(Self::Wrap     (value.read()?)),
+            "truncate" => Ok(Self::Truncate (value.read()?)),
+            "sense"    => Ok(Self::Sense    (value.read()?)),
+            _          => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+        }
+    }
+
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Wrap(wrap)         => writer.field("wrap", wrap),
+            Self::Truncate(truncate) => writer.field("truncate", truncate),
+            Self::Sense(sense)       => writer.field("sense", sense),
+            Self::Custom(name, prop) => writer.field(name, &**prop),
+        }
+    }
+}
+
+//
+// EditableLabel
+//
+
+// Label that turns into a text field when double-clicked, for renaming saves, units, etc.
+// Edited text is written to the field on Enter or when focus is lost, Escape discards it.
+#[derive(Debug)]
+pub struct EditableLabel {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub size_limits: SizeLimits,
+    pub text: BindingRef<String>,
+    // fired when edited text is written, if it's different from the old one
+    pub renamed: Option<ResponseAction>,
+    pub response: Response,
+}
+
+impl EditableLabel {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "visible", "groups", "text", "renamed"],
+        SizeLimits::FIELDS,
+        ResponseProperty::FIELDS,
+    );
+
+    fn new(text: BindingRef<String>) -> Self {
+        Self {
+            id: None,
+            enabled: None,
+            transition: None,
+            visible: None,
+            groups: vec![],
+            size_limits: SizeLimits::default(),
+            text,
+            renamed: None,
+            response: Response(vec![]),
+        }
+    }
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
+        let text = self.text.resolve_mut(data).ok()?;
+        // label and text field take turns, so they share the same id
+        let id = ui.next_auto_id();
+        let edited_id = id.with("uiconf_edited_text");
+
+        let Some(mut edited) = ui.data(|d| d.get_temp::<String>(edited_id)) else {
+            let response = ui.add(egui::Label::new(text.as_str()).sense(egui::Sense::click()));
+            if response.double_clicked() {
+                ui.data_mut(|d| d.insert_temp(edited_id, text.clone()));
+                ui.memory_mut(|mem| mem.request_focus(id));
+            }
+            return Some(self.response.process(data, response));
+        };
+
+        let response = ui.add(egui::TextEdit::singleline(&mut edited).id(id));
+        WidgetState::TextEdit.record(ui.ctx(), id);
+        FocusedTextInput::record(&response);
+
+        if response.lost_focus() {
+            ui.data_mut(|d| d.remove::<String>(edited_id));
+            let discarded = ui.input(|input| input.key_pressed(egui::Key::Escape));
+            if !discarded && edited != *text {
+                *text = edited;
+                if let Some(renamed) = &self.renamed {
+                    renamed.run(data, &response);
+                }
+            }
+        } else {
+            ui.data_mut(|d| d.insert_temp(edited_id, edited));
+        }
+
+        Some(self.response.process(data, response))
+    }
+}
+
+impl ReadUiconf for EditableLabel {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        if value.is_scalar() {
+            return Ok(Self::new(value.read()?));
+        }
+
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut size_limits = SizeLimits::default();
+        let mut text = None;
+        let mut renamed = None;
+        let mut response = vec![];
+
+        for (key, value) in value.read_object()? {
+            if key == "id" {
+                if id.is_some() { return Err(Error::duplicate_field(&value, "id")); }
+                id = Some(value.read()?);
+            } else if key == "enabled" {
+                if enabled.is_some() { return Err(Error::duplicate_field(&value, "enabled")); }
+                enabled = Some(value.read()?);
+            } else if key == "transition" {
+                if transition.is_some() { return Err(Error::duplicate_field(&value, "transition")); }
+                transition = Some(value.read()?);
+            } else if SizeLimits::FIELDS.contains(&&*key) {
+                size_limits.read_map_value(&key, &value)?;
+            } else if key == "visible" {
+                if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
+                visible = Some(value.read()?);
+            } else if key == "groups" {
+                if !groups.is_empty() { return Err(Error::duplicate_field(&value, "groups")); }
+                groups = value.read()?;
+            } else if key == "text" {
+                if text.is_some() { return Err(Error::duplicate_field(&value, "text")); }
+                text = Some(value.read()?);
+            } else if key == "renamed" {
+                if renamed.is_some() { return Err(Error::duplicate_field(&value, "renamed")); }
+                renamed = Some(value.read()?);
+            } else if ResponseProperty::FIELDS.contains(&&*key) {
+                response.push(ResponseProperty::read_map_value(&key, &value)?);
+            } else {
+                value.skip_unknown_field(&key, EditableLabel::FIELDS)?;
+            }
+        }
+
+        let text = text.ok_or_else(|| Error::missing_field(value, "text"))?;
+
+        Ok(EditableLabel { id, enabled, transition, visible, groups, size_limits, text, renamed, response: Response(response) })
+    }
+}
+
+impl ToUiconf for EditableLabel {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        if self.id.is_none() && self.enabled.is_none() && self.transition.is_none() && self.visible.is_none() && self.groups.is_empty() && self.size_limits.is_empty() && self.renamed.is_none() && self.response.0.is_empty() {
+            self.text.write_uiconf(writer);
+            return;
+        }
+
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            self.size_limits.write_map_values(writer);
+            writer.field("text", &self.text);
+            if let Some(renamed) = &self.renamed {
+                writer.field("renamed", renamed);
+            }
+            self.response.write_map_values(writer);
+        });
+    }
+}
+
+//
+// PasswordEdit
+//
+
+// Masked text field for passwords and server keys in login and connect dialogs,
+// `submitted` is fired when Enter is pressed in it.
+#[derive(Debug)]
+pub struct PasswordEdit {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub size_limits: SizeLimits,
+    pub text: BindingRef<String>,
+    pub submitted: Option<ResponseAction>,
+    pub props: Vec<PasswordEditProperty>,
+    pub response: Response,
+}
+
+impl PasswordEdit {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "visible", "groups", "text", "submitted"],
+        SizeLimits::FIELDS,
+        PasswordEditProperty::FIELDS,
+        ResponseProperty::FIELDS,
+    );
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
+        let id = ui.next_auto_id();
+        let revealed_id = id.with("uiconf_revealed");
+        let mut revealed = ui.data(|d| d.get_temp::<bool>(revealed_id)).unwrap_or(false);
+        let mut reveal = false;
+        let mut hint = None;
+        let mut width = None;
+
+        for prop in self.props.iter() {
+            use PasswordEditProperty as P;
+            match prop {
+                P::Hint(text)    => hint = Some(text.resolve(data, ui.ctx())),
+                P::Width(value)  => width = Some(*value),
+                P::Reveal(value) => reveal = *value,
+            }
+        }
+
+        let text = self.text.resolve_mut(data).ok()?;
+        let mut edit = egui::TextEdit::singleline(text).password(!revealed).id(id);
+        if let Some(hint) = hint {
+            edit = edit.hint_text(hint);
+        }
+        if let Some(width) = width {
+            edit = edit.desired_width(width);
+        }
+
+        // toggle is placed next to the field, but response is the one of the field itself
+        let response = if reveal {
+            ui.horizontal(|ui| {
+                let response = ui.add(edit);
+                if ui.selectable_label(revealed, "👁").clicked() {
+                    revealed = !revealed;
+                    ui.data_mut(|d| d.insert_temp(revealed_id, revealed));
+                }
+                response
+            }).inner
+        } else {
+            ui.add(edit)
+        };
+        WidgetState::TextEdit.record(ui.ctx(), id);
+        FocusedTextInput::record(&response);
+
+        if response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+            if let Some(submitted) = &self.submitted {
+                submitted.run(data, &response);
+            }
+        }
+
+        Some(self.response.process(data, response))
+    }
+}
+
+impl ReadUiconf for PasswordEdit {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut size_limits = SizeLimits::default();
+        let mut text = None;
+        let mut submitted = None;
+        let mut props = vec![];
+        let mut response = vec![];
+
+        for (key, value) in value.read_object()? {
+            if key == "id" {
+                if id.is_some() { return Err(Error::duplicate_field(&value, "id")); }
+                id = Some(value.read()?);
+            } else if key == "enabled" {
+                if enabled.is_some() { return Err(Error::duplicate_field(&value, "enabled")); }
+                enabled = Some(value.read()?);
+            } else if key == "transition" {
+                if transition.is_some() { return Err(Error::duplicate_field(&value, "transition")); }
+                transition = Some(value.read()?);
+            } else if SizeLimits::FIELDS.contains(&&*key) {
+                size_limits.read_map_value(&key, &value)?;
+            } else if key == "visible" {
+                if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
+                visible = Some(value.read()?);
+            } else if key == "groups" {
+                if !groups.is_empty() { return Err(Error::duplicate_field(&value, "groups")); }
+                groups = value.read()?;
+            } else if key == "text" {
+                if text.is_some() { return Err(Error::duplicate_field(&value, "text")); }
+                text = Some(value.read()?);
+            } else if key == "submitted" {
+                if submitted.is_some() { return Err(Error::duplicate_field(&value, "submitted")); }
+                submitted = Some(value.read()?);
+            } else if PasswordEditProperty::FIELDS.contains(&&*key) {
+                props.push(PasswordEditProperty::read_map_value(&key, &value)?);
+            } else if ResponseProperty::FIELDS.contains(&&*key) {
+                response.push(ResponseProperty::read_map_value(&key, &value)?);
+            } else {
+                value.skip_unknown_field(&key, PasswordEdit::FIELDS)?;
+            }
+        }
+
+        let text = text.ok_or_else(|| Error::missing_field(value, "text"))?;
+
+        Ok(PasswordEdit { id, enabled, transition, visible, groups, size_limits, text, submitted, props, response: Response(response) })
+    }
+}
+
+impl ToUiconf for PasswordEdit {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            self.size_limits.write_map_values(writer);
+            writer.field("text", &self.text);
+            if let Some(submitted) = &self.submitted {
+                writer.field("submitted", submitted);
+            }
+            for prop in self.props.iter() {
+                prop.write_map_value(writer);
+            }
+            self.response.write_map_values(writer);
+        });
+    }
+}
+
+//
+// PasswordEditProperty
+//
+
+#[derive(Debug)]
+pub enum PasswordEditProperty {
+    // shown while the field is empty
+    Hint(RichText),
+    Width(f32),
+    // adds a button which shows typed text
+    Reveal(bool),
+}
+
+impl PasswordEditProperty {
+    const FIELDS: &'static [&'static str] = &["hint", "width", "reveal"];
+
+    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
+        match tag {
+            "hint"   => Ok(Self::Hint   (value.read()?)),
+            "width"  => Ok(Self::Width  (value.read()?)),
+            "reveal" => Ok(Self::Reveal (value.read()?)),
+            _        => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+        }
+    }
+
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Hint(hint)     => writer.field("hint", hint),
+            Self::Width(width)   => writer.field("width", width),
+            Self::Reveal(reveal) => writer.field("reveal", reveal),
+        }
+    }
+}
+
+//
+// NumericEdit
+//
+
+// Text field for typing numbers into a numeric field of any type, `numeric_edit = { value = @port min = 1 max = 65535 }`.
+// Text that isn't a number or is out of range is highlighted while typing, and the number is written
+// (clamped to range) on Enter or when focus is lost; Escape or invalid text keeps the old value.
+#[derive(Debug)]
+pub struct NumericEdit {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub size_limits: SizeLimits,
+    pub value: BindingRef<dyn std::any::Any + Send + Sync>,
+    // fired when typed number is written
+    pub validated: Option<ResponseAction>,
+    pub props: Vec<NumericEditProperty>,
+    pub response: Response,
+}
+
+impl NumericEdit {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "visible", "groups", "value", "validated"],
+        SizeLimits::FIELDS,
+        NumericEditProperty::FIELDS,
+        ResponseProperty::FIELDS,
+    );
+
+    fn range(&self) -> (f64, f64) {
+        let mut range = (f64::NEG_INFINITY, f64::INFINITY);
+        for prop in self.props.iter() {
+            match prop {
+                NumericEditProperty::Min(min) => range.0 = *min,
+                NumericEditProperty::Max(max) => range.1 = *max,
+                _ => {}
+            }
+        }
+        range
+    }
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
+        let field = self.value.resolve_reflect(data).ok()?;
+        let value = reflect_as_f64(field)?;
+        let mut shown = match field.downcast_ref::<f32>() {
+            Some(value) => value.to_string(),
+            None => value.to_string(),
+        };
+
+        let (min, max) = self.range();
+        let mut width = None;
+        for prop in self.props.iter() {
+            use NumericEditProperty as P;
+            match prop {
+                P::Decimals(decimals) => shown = format!("{:.*}", *decimals as usize, value),
+                P::Width(value)       => width = Some(*value),
+                P::Min(_) | P::Max(_) => {}
+            }
+        }
+
+        // typed text is kept while the field is focused, otherwise the value is shown
+        let id = ui.next_auto_id();
+        let text_id = id.with("uiconf_typed_text");
+        let mut text = ui.data(|d| d.get_temp::<String>(text_id)).unwrap_or(shown);
+        let parsed = text.trim().parse::<f64>().ok().filter(|value| value.is_finite());
+
+        let mut edit = egui::TextEdit::singleline(&mut text).id(id);
+        if parsed.filter(|value| (min..=max).contains(value)).is_none() {
+            edit = edit.text_color(ui.visuals().error_fg_color);
+        }
+        if let Some(width) = width {
+            edit = edit.desired_width(width);
+        }
+        let response = ui.add(edit);
+        WidgetState::TextEdit.record(ui.ctx(), id);
+        FocusedTextInput::record(&response);
+
+        if response.lost_focus() {
+            ui.data_mut(|d| d.remove::<String>(text_id));
+            let discarded = ui.input(|input| input.key_pressed(egui::Key::Escape));
+            if let (false, Some(parsed)) = (discarded, parsed) {
+                let written = self.value.resolve_reflect_mut(data).is_ok_and(|field| reflect_set_f64(field, parsed.clamp(min, max)));
+                if let (true, Some(validated)) = (written, &self.validated) {
+                    validated.run(data, &response);
+                }
+            }
+        } else if response.has_focus() {
+            ui.data_mut(|d| d.insert_temp(text_id, text));
+        }
+
+        Some(self.response.process(data, response))
+    }
+}
+
+impl ReadUiconf for NumericEdit {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut size_limits = SizeLimits::default();
+        let mut number = None;
+        let mut validated = None;
+        let mut props = vec![];
+        let mut response = vec![];
+
+        for (key, value) in value.read_object()? {
+            if key == "id" {
+                if id.is_some() { return Err(Error::duplicate_field(&value, "id")); }
+                id = Some(value.read()?);
+            } else if key == "enabled" {
+                if enabled.is_some() { return Err(Error::duplicate_field(&value, "enabled")); }
+                enabled = Some(value.read()?);
+            } else if key == "transition" {
+                if transition.is_some() { return Err(Error::duplicate_field(&value, "transition")); }
+                transition = Some(value.read()?);
+            } else if SizeLimits::FIELDS.contains(&&*key) {
+                size_limits.read_map_value(&key, &value)?;
+            } else if key == "visible" {
+                if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
+                visible = Some(value.read()?);
+            } else if key == "groups" {
+                if !groups.is_empty() { return Err(Error::duplicate_field(&value, "groups")); }
+                groups = value.read()?;
+            } else if key == "value" {
+                if number.is_some() { return Err(Error::duplicate_field(&value, "value")); }
+                number = Some(value.read()?);
+            } else if key == "validated" {
+                if validated.is_some() { return Err(Error::duplicate_field(&value, "validated")); }
+                validated = Some(value.read()?);
+            } else if NumericEditProperty::FIELDS.contains(&&*key) {
+                props.push(NumericEditProperty::read_map_value(&key, &value)?);
+            } else if ResponseProperty::FIELDS.contains(&&*key) {
+                response.push(ResponseProperty::read_map_value(&key, &value)?);
+            } else {
+                value.skip_unknown_field(&key, NumericEdit::FIELDS)?;
+            }
+        }
+
+        let number = number.ok_or_else(|| Error::missing_field(value, "value"))?;
+
+        Ok(NumericEdit { id, enabled, transition, visible, groups, size_limits, value: number, validated, props, response: Response(response) })
+    }
+}
+
+impl ToUiconf for NumericEdit {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            self.size_limits.write_map_values(writer);
+            writer.field("value", &self.value);
+            if let Some(validated) = &self.validated {
+                writer.field("validated", validated);
+            }
+            for prop in self.props.iter() {
+                prop.write_map_value(writer);
+            }
+            self.response.write_map_values(writer);
+        });
+    }
+}
+
+//
+// NumericEditProperty
+//
+
+#[derive(Debug)]
+pub enum NumericEditProperty {
+    Min(f64),
+    Max(f64),
+    // digits after decimal point shown while the field isn't edited
+    Decimals(u32),
+    Width(f32),
+}
+
+impl NumericEditProperty {
+    const FIELDS: &'static [&'static str] = &["min", "max", "decimals", "width"];
+
+    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
+        match tag {
+            "min"      => Ok(Self::Min      (value.read()?)),
+            "max"      => Ok(Self::Max      (value.read()?)),
+            "decimals" => Ok(Self::Decimals (value.read()?)),
+            "width"    => Ok(Self::Width    (value.read()?)),
+            _          => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+        }
+    }
+
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Min(min)           => writer.field("min", min),
+            Self::Max(max)           => writer.field("max", max),
+            Self::Decimals(decimals) => writer.field("decimals", decimals),
+            Self::Width(width)       => writer.field("width", width),
+        }
+    }
+}
+
+//
+// SettingRow
+//
+
+// One row of an options screen: name, slider bound to a numeric field, its value, and a button
+// resetting it to `default` (only shown if `default` is set), e.g.
+//
+//     setting_row = { name = loc(music_volume) value = @music_volume min = 0 max = 100 default = 80 suffix = "%" }
+//
+#[derive(Debug)]
+pub struct SettingRow {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub size_limits: SizeLimits,
+    pub name: RichText,
+    pub value: BindingRef<dyn std::any::Any + Send + Sync>,
+    pub min: f64,
+    pub max: f64,
+    pub default: Option<f64>,
+    pub props: Vec<SettingRowProperty>,
+    pub response: Response,
+}
+
+impl SettingRow {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "visible", "groups", "name", "value", "min", "max", "default"],
+        SizeLimits::FIELDS,
+        SettingRowProperty::FIELDS,
+        ResponseProperty::FIELDS,
+    );
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
+        let field = self.value.resolve_reflect(data).ok()?;
+        let integer = !field.is::<f32>() && !field.is::<f64>();
+        let mut value = reflect_as_f64(field)?;
+
+        let mut drag = false;
+        let mut step = None;
+        let mut decimals = None;
+        let mut suffix = "";
+        let mut reset_text = None;
+        for prop in self.props.iter() {
+            use SettingRowProperty as P;
+            match prop {
+                P::Drag(value)     => drag = *value,
+                P::Step(value)     => step = Some(*value),
+                P::Decimals(value) => decimals = Some(*value as usize),
+                P::Suffix(value)   => suffix = value,
+                P::ResetText(text) => reset_text = Some(text.resolve(data, ui.ctx())),
+            }
+        }
+
+        let name = self.name.resolve(data, ui.ctx());
+        let inner = ui.horizontal(|ui| {
+            ui.label(name);
+
+            // drag value shows the number by itself, slider is followed by a label
+            let mut response = if drag {
+                let mut drag = egui::DragValue::new(&mut value).clamp_range(self.min..=self.max).suffix(suffix);
+                if let Some(step) = step { drag = drag.speed(step); }
+                if let Some(decimals) = decimals { drag = drag.fixed_decimals(decimals); }
+                ui.add(drag)
+            } else {
+                let mut slider = egui::Slider::new(&mut value, self.min..=self.max).show_value(false);
+                if integer { slider = slider.integer(); }
+                if let Some(step) = step { slider = slider.step_by(step); }
+                let response = ui.add(slider);
+                let shown = match decimals {
+                    Some(decimals) => format!("{:.*}{}", decimals, value, suffix),
+                    None => format!("{}{}", value, suffix),
+                };
+                ui.label(shown);
+                response
+            };
+
+            if let Some(default) = self.default {
+                let text = reset_text.unwrap_or_else(|| "Reset".into());
+                if ui.add_enabled(value != default, egui::Button::new(text)).clicked() {
+                    value = default;
+                    response.mark_changed();
+                }
+            }
+
+            response
+        });
+
+        let response = inner.inner;
+        if response.changed() {
+            if let Ok(field) = self.value.resolve_reflect_mut(data) {
+                reflect_set_f64(field, value);
+            }
+        }
+
+        Some(self.response.process(data, response))
+    }
+}
+
+impl ReadUiconf for SettingRow {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut size_limits = SizeLimits::default();
+        let mut name = None;
+        let mut number = None;
+        let mut min = None;
+        let mut max = None;
+        let mut default = None;
+        let mut props = vec![];
+        let mut response = vec![];
+
+        for (key, value) in value.read_object()? {
+            if key == "id" {
+                if id.is_some() { return Err(Error::duplicate_field(&value, "id")); }
+                id = Some(value.read()?);
+            } else if key == "enabled" {
+                if enabled.is_some() { return Err(Error::duplicate_field(&value, "enabled")); }
+                enabled = Some(value.read()?);
+            } else if key == "transition" {
+                if transition.is_some() { return Err(Error::duplicate_field(&value, "transition")); }
+                transition = Some(value.read()?);
+            } else if SizeLimits::FIELDS.contains(&&*key) {
+                size_limits.read_map_value(&key, &value)?;
+            } else if key == "visible" {
+                if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
+                visible = Some(value.read()?);
+            } else if key == "groups" {
+                if !groups.is_empty() { return Err(Error::duplicate_field(&value, "groups")); }
+                groups = value.read()?;
+            } else if key == "name" {
+                if name.is_some() { return Err(Error::duplicate_field(&value, "name")); }
+                name = Some(value.read()?);
+            } else if key == "value" {
+                if number.is_some() { return Err(Error::duplicate_field(&value, "value")); }
+                number = Some(value.read()?);
+            } else if key == "min" {
+                if min.is_some() { return Err(Error::duplicate_field(&value, "min")); }
+                min = Some(value.read()?);
+            } else if key == "max" {
+                if max.is_some() { return Err(Error::duplicate_field(&value, "max")); }
+                max = Some(value.read()?);
+            } else if key == "default" {
+                if default.is_some() { return Err(Error::duplicate_field(&value, "default")); }
+                default = Some(value.read()?);
+            } else if SettingRowProperty::FIELDS.contains(&&*key) {
+                props.push(SettingRowProperty::read_map_value(&key, &value)?);
+            } else if ResponseProperty::FIELDS.contains(&&*key) {
+                response.push(ResponseProperty::read_map_value(&key, &value)?);
+            } else {
+                value.skip_unknown_field(&key, SettingRow::FIELDS)?;
+            }
+        }
+
+        let name = name.ok_or_else(|| Error::missing_field(value, "name"))?;
+        let number = number.ok_or_else(|| Error::missing_field(value, "value"))?;
+        let min = min.ok_or_else(|| Error::missing_field(value, "min"))?;
+        let max = max.ok_or_else(|| Error::missing_field(value, "max"))?;
+
+        Ok(SettingRow { id, enabled, transition, visible, groups, size_limits, name, value: number, min, max, default, props, response: Response(response) })
+    }
+}
+
+impl ToUiconf for SettingRow {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            self.size_limits.write_map_values(writer);
+            writer.field("name", &self.name);
+            writer.field("value", &self.value);
+            writer.field("min", &self.min);
+            writer.field("max", &self.max);
+            if let Some(default) = &self.default {
+                writer.field("default", default);
+            }
+            for prop in self.props.iter() {
+                prop.write_map_value(writer);
+            }
+            self.response.write_map_values(writer);
+        });
+    }
+}
+
+//
+// SettingRowProperty
+//
+
+#[derive(Debug)]
+pub enum SettingRowProperty {
+    // drag value instead of slider, for values without meaningful range
+    Drag(bool),
+    Step(f64),
+    Decimals(u32),
+    // appended to shown value, e.g. `%`
+    Suffix(String),
+    ResetText(RichText),
+}
+
+impl SettingRowProperty {
+    const FIELDS: &'static [&'static str] = &["drag", "step", "decimals", "suffix", "reset_text"];
+
+    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
+        match tag {
+            "drag"       => Ok(Self::Drag      (value.read()?)),
+            "step"       => Ok(Self::Step      (value.read()?)),
+            "decimals"   => Ok(Self::Decimals  (value.read()?)),
+            "suffix"     => Ok(Self::Suffix    (value.read()?)),
+            "reset_text" => Ok(Self::ResetText (value.read()?)),
+            _            => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+        }
+    }
+
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Drag(drag)         => writer.field("drag", drag),
+            Self::Step(step)         => writer.field("step", step),
+            Self::Decimals(decimals) => writer.field("decimals", decimals),
+            Self::Suffix(suffix)     => writer.field("suffix", suffix),
+            Self::ResetText(text)    => writer.field("reset_text", text),
+        }
+    }
+}
+
+//
+// Separator
+//
+
+#[derive(Debug)]
+pub struct Separator {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub size_limits: SizeLimits,
+    pub props: Vec<SeparatorProperty>,
+    pub response: Response,
+}
+
+impl Separator {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "visible", "groups"],
+        SizeLimits::FIELDS,
+        SeparatorProperty::FIELDS,
+        ResponseProperty::FIELDS,
+    );
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
+        let mut separator = egui::Separator::default();
+
+        for prop in self.props.iter() {
+            use SeparatorProperty as P;
+            separator = match prop {
+                P::Vertical(vertical) => if *vertical {
+                    separator.vertical()
+                } else {
+                    separator.horizontal()
+                }
+                P::Spacing(spacing)   => separator.spacing(*spacing),
+                P::Grow(grow)         => separator.grow(*grow),
+                P::Shrink(shrink)     => separator.shrink(*shrink),
+            };
+        }
+
+        Some(self.response.process(data, ui.add(separator)))
+    }
+}
+
+impl ReadUiconf for Separator {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut size_limits = SizeLimits::default();
+        let mut props = vec![];
+        let mut response = vec![];
+
+        for (key, value) in value.read_object()? {
+            if SizeLimits::FIELDS.contains(&&*key) {
+                size_limits.read_map_value(&key, &value)?;
+            } else if key == "visible" {
+                if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
+                visible = Some(value.read()?);
+            } else if key == "groups" {
+                if !groups.is_empty() { return Err(Error::duplicate_field(&value, "groups")); }
+                groups = value.read()?;
+            } else if key == "id" {
+                if id.is_some() { return Err(Error::duplicate_field(&value, "id")); }
+                id = Some(value.read()?);
+            } else if key == "enabled" {
+                if enabled.is_some() { return Err(Error::duplicate_field(&value, "enabled")); }
+                enabled = Some(value.read()?);
+            } else if key == "transition" {
+                if transition.is_some() { return Err(Error::duplicate_field(&value, "transition")); }
+                transition = Some(value.read()?);
+            } else if SeparatorProperty::FIELDS.contains(&&*key) {
+                props.push(SeparatorProperty::read_map_value(&key, &value)?);
+            } else if ResponseProperty::FIELDS.contains(&&*key) {
+                response.push(ResponseProperty::read_map_value(&key, &value)?);
+            } else {
+                value.skip_unknown_field(&key, Separator::FIELDS)?;
+            }
+        }
+
+        Ok(Separator { id, enabled, transition, visible, groups, size_limits, props, response: Response(response) })
+    }
+}
+
+impl ToUiconf for Separator {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            self.size_limits.write_map_values(writer);
+            for prop in self.props.iter() {
+                prop.write_map_value(writer);
+            }
+            self.response.write_map_values(writer);
+        });
+    }
+}
+
+//
+// SeparatorProperty
+//
+
+#[derive(Debug, Clone)]
+pub enum SeparatorProperty {
+    Vertical(bool),
+    Spacing(f32),
+    Grow(f32),
+    Shrink(f32),
+}
+
+impl SeparatorProperty {
+    const FIELDS: &'static [&'static str] = &["vertical", "spacing", "grow", "shrink"];
+
+    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
+        match tag {
+            "vertical" => Ok(Self::Vertical   (value.read()?)),
+            "spacing"  => Ok(Self::Spacing    (value.read()?)),
+            "grow"     => Ok(Self::Grow       (value.read()?)),
+            "shrink"   => Ok(Self::Shrink     (value.read()?)),
+            _          => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+        }
+    }
+
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Vertical(vertical) => writer.field("vertical", vertical),
+            Self::Spacing(spacing)   => writer.field("spacing", spacing),
+            Self::Grow(grow)         => writer.field("grow", grow),
+            Self::Shrink(shrink)     => writer.field("shrink", shrink),
+        }
+    }
+}
+
+//
+// Spinner
+//
+
+#[derive(Debug)]
+pub struct Spinner {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub size_limits: SizeLimits,
+    pub props: Vec<SpinnerProperty>,
+    pub response: Response,
+}
+
+impl Spinner {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "visible", "groups"],
+        SizeLimits::FIELDS,
+        SpinnerProperty::FIELDS,
+        ResponseProperty::FIELDS,
+    );
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
+        let mut spinner = egui::Spinner::new();
+
+        for prop in self.props.iter() {
+            use SpinnerProperty as P;
+            spinner = match prop {
+                P::Size(size)   => spinner.size(*size),
+                P::Color(color) => {
+                    let Ok(color) = color.resolve(data) else { continue; };
+                    spinner.color(color_bevy_to_egui(color))
+                }
+            };
+        }
+
+        Some(self.response.process(data, ui.add(spinner)))
+    }
+}
+
+impl ReadUiconf for Spinner {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut size_limits = SizeLimits::default();
+        let mut props = vec![];
+        let mut response = vec![];
+
+        for (key, value) in value.read_object()? {
+            if SizeLimits::FIELDS.contains(&&*key) {
+                size_limits.read_map_value(&key, &value)?;
+            } else if key == "visible" {
+                if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
+                visible = Some(value.read()?);
+            } else if key == "groups" {
+                if !groups.is_empty() { return Err(Error::duplicate_field(&value, "groups")); }
+                groups = value.read()?;
+            } else if key == "id" {
+                if id.is_some() { return Err(Error::duplicate_field(&value, "id")); }
+                id = Some(value.read()?);
+            } else if key == "enabled" {
+                if enabled.is_some() { return Err(Error::duplicate_field(&value, "enabled")); }
+                enabled = Some(value.read()?);
+            } else if key == "transition" {
+                if transition.is_some() { return Err(Error::duplicate_field(&value, "transition")); }
+                transition = Some(value.read()?);
+            } else if SpinnerProperty::FIELDS.contains(&&*key) {
+                props.push(SpinnerProperty::read_map_value(&key, &value)?);
+            } else if ResponseProperty::FIELDS.contains(&&*key) {
+                response.push(ResponseProperty::read_map_value(&key, &value)?);
+            } else {
+                value.skip_unknown_field(&key, Spinner::FIELDS)?;
+            }
+        }
+
+        Ok(Spinner { id, enabled, transition, visible, groups, size_limits, props, response: Response(response) })
+    }
+}
+
+impl ToUiconf for Spinner {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            self.size_limits.write_map_values(writer);
+            for prop in self.props.iter() {
+                prop.write_map_value(writer);
+            }
+            self.response.write_map_values(writer);
+        });
+    }
+}
+
+//
+// SpinnerProperty
+//
+
+#[derive(Debug)]
+pub enum SpinnerProperty {
+    Size(f32),
+    Color(Binding<bevy::prelude::Color>),
+}
+
+impl SpinnerProperty {
+    const FIELDS: &'static [&'static str] = &["size", "color"];
+
+    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
+        match tag {
+            "size"  => Ok(Self::Size  (value.read()?)),
+            "color" => Ok(Self::Color (value.read()?)),
+            _       => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+        }
+    }
+
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Size(size)   => writer.field("size", size),
+            Self::Color(color) => writer.field("color", color),
+        }
+    }
+}
+
+//
+// Gauge
+//
+
+// Circular progress for speedometers and cooldowns: arc is filled from `start_angle` to `end_angle`
+// proportionally to value between `min` and `max`. Angles are in degrees, clockwise from the top.
+#[derive(Debug)]
+pub struct Gauge {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub value: Binding<f32>,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub size_limits: SizeLimits,
+    pub props: Vec<GaugeProperty>,
+    pub response: Response,
+}
+
+impl Gauge {
+    const FIELDS: &'static [&'static str] = const_concat!(
+        &["id", "enabled", "transition", "value", "visible", "groups"],
+        SizeLimits::FIELDS,
+        GaugeProperty::FIELDS,
+        ResponseProperty::FIELDS,
+    );
+
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
+        let mut min = 0.;
+        let mut max = 1.;
+        let mut size = 64.;
+        let mut thickness = None;
+        let mut start_angle = -135.;
+        let mut end_angle = 135.;
+        let mut color = ui.visuals().selection.bg_fill;
+        let mut background_color = ui.visuals().extreme_bg_color;
+        let mut stops = vec![];
+        let mut text = None;
+
+        for prop in self.props.iter() {
+            use GaugeProperty as P;
+            match prop {
+                P::Min(value)        => min = *value,
+                P::Max(value)        => max = *value,
+                P::Size(value)       => size = *value,
+                P::Thickness(value)  => thickness = Some(*value),
+                P::StartAngle(value) => start_angle = *value,
+                P::EndAngle(value)   => end_angle = *value,
+                P::Color(value) => {
+                    let Ok(value) = value.resolve(data) else { continue; };
+                    color = color_bevy_to_egui(value);
+                }
+                P::BackgroundColor(value) => {
+                    let Ok(value) = value.resolve(data) else { continue; };
+                    background_color = color_bevy_to_egui(value);
+                }
+                P::Stop(stop) => stops.push(stop),
+                P::Text(value) => text = Some(value.resolve(data, ui.ctx())),
+            }
+        }
+
+        let value = self.value.resolve(data).unwrap_or(min);
+        let fraction = if max > min { ((value - min) / (max - min)).clamp(0., 1.) } else { 0. };
+        // last stop that value has reached overrides the color
+        stops.sort_by(|a, b| a.at.total_cmp(&b.at));
+        if let Some(stop) = stops.iter().rev().find(|stop| value >= stop.at) {
+            color = color_bevy_to_egui(stop.color);
+        }
+
+        let (rect, response) = ui.allocate_exact_size(egui::Vec2::splat(size), egui::Sense::hover());
+        if ui.is_rect_visible(rect) {
+            let thickness = thickness.unwrap_or(size / 8.);
+            let radius = (size - thickness) / 2.;
+            let arc = |from: f32, to: f32| -> Vec<egui::Pos2> {
+                let segments = ((to - from).abs() / 5.).ceil().max(1.) as usize;
+                (0..=segments).map(|idx| {
+                    let angle = (from + (to - from) * idx as f32 / segments as f32).to_radians();
+                    rect.center() + egui::vec2(angle.sin(), -angle.cos()) * radius
+                }).collect()
+            };
+
+            let painter = ui.painter();
+            painter.add(egui::Shape::line(arc(start_angle, end_angle), egui::Stroke::new(thickness, background_color)));
+            if fraction > 0. {
+                let filled = start_angle + (end_angle - start_angle) * fraction;
+                painter.add(egui::Shape::line(arc(start_angle, filled), egui::Stroke::new(thickness, color)));
+            }
+
+            if let Some(text) = text {
+                let galley = text.into_galley(ui, Some(false), f32::INFINITY, egui::TextStyle::Body);
+                let pos = rect.center() - galley.size() / 2.;
+                galley.paint_with_visuals(ui.painter(), pos, ui.style().noninteractive());
+            }
+        }
+
+        Some(self.response.process(data, response))
+    }
+}
+
+impl ReadUiconf for Gauge {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut binding = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut size_limits = SizeLimits::default();
+        let mut props = vec![];
+        let mut response = vec![];
+
+        for (key, value) in value.read_object()? {
+            if key == "value" {
+                if binding.is_some() { return Err(Error::duplicate_field(&value, "value")); }
+                binding = Some(value.read()?);
+            } else if SizeLimits::FIELDS.contains(&&*key) {
+                size_limits.read_map_value(&key, &value)?;
+            } else if key == "visible" {
+                if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
+                visible = Some(value.read()?);
+            } else if key == "groups" {
+                if !groups.is_empty() { return Err(Error::duplicate_field(&value, "groups")); }
+                groups = value.read()?;
+            } else if key == "id" {
+                if id.is_some() { return Err(Error::duplicate_field(&value, "id")); }
+                id = Some(value.read()?);
+            } else if key == "enabled" {
+                if enabled.is_some() { return Err(Error::duplicate_field(&value, "enabled")); }
+                enabled = Some(value.read()?);
+            } else if key == "transition" {
+                if transition.is_some() { return Err(Error::duplicate_field(&value, "transition")); }
+                transition = Some(value.read()?);
+            } else if GaugeProperty::FIELDS.contains(&&*key) {
+                props.push(GaugeProperty::read_map_value(&key, &value)?);
+            } else if ResponseProperty::FIELDS.contains(&&*key) {
+                response.push(ResponseProperty::read_map_value(&key, &value)?);
+            } else {
+                value.skip_unknown_field(&key, Gauge::FIELDS)?;
+            }
         }
+
+        let binding = binding.ok_or_else(|| Error::missing_field(value, "value"))?;
+
+        Ok(Gauge { id, enabled, transition, value: binding, visible, groups, size_limits, props, response: Response(response) })
+    }
+}
+
+impl ToUiconf for Gauge {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            writer.field("value", &self.value);
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            self.size_limits.write_map_values(writer);
+            for prop in self.props.iter() {
+                prop.write_map_value(writer);
+            }
+            self.response.write_map_values(writer);
+        });
     }
 }
 
+//
+// GaugeProperty
+//
+
 #[derive(Debug)]
-pub enum ResponseProperty {
-    Clicked(BindingRef<Trigger>),
-    SecondaryClicked(BindingRef<Trigger>),
-    MiddleClicked(BindingRef<Trigger>),
-    DoubleClicked(BindingRef<Trigger>),
-    TripleClicked(BindingRef<Trigger>),
-    ClickedElsewhere(BindingRef<Trigger>),
-    Hovered(BindingRef<Trigger>),
-    Highlighted(BindingRef<Trigger>),
-    Changed(BindingRef<Trigger>),
-    OnHover(Content),
-    OnDisabledHover(Content),
-    OnHoverAtPointer(Content),
-    Highlight(Binding<bool>),
+pub enum GaugeProperty {
+    Min(f32),
+    Max(f32),
+    Size(f32),
+    // width of the arc, `size / 8` by default
+    Thickness(f32),
+    StartAngle(f32),
+    EndAngle(f32),
+    Color(Binding<bevy::prelude::Color>),
+    BackgroundColor(Binding<bevy::prelude::Color>),
+    // `stop = { at = 150 color = red }`, can be repeated
+    Stop(GaugeStop),
+    // shown in the center, e.g. `text = @speed_label`
+    Text(RichText),
 }
 
-impl ResponseProperty {
+impl GaugeProperty {
     const FIELDS: &'static [&'static str] = &[
-        "clicked", "secondary_clicked", "middle_clicked", "double_clicked", "triple_clicked", "clicked_elsewhere",
-        "hovered", "highlighted", "changed", "on_hover", "on_disabled_hover", "on_hover_at_pointer", "highlight",
+        "min", "max", "size", "thickness", "start_angle", "end_angle", "color", "background_color", "stop", "text",
     ];
 
     fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
         match tag {
-            "clicked"            => Ok(Self::Clicked            (value.read()?)),
-            "secondary_clicked"  => Ok(Self::SecondaryClicked   (value.read()?)),
-            "middle_clicked"     => Ok(Self::MiddleClicked      (value.read()?)),
-            "double_clicked"     => Ok(Self::DoubleClicked      (value.read()?)),
-            "triple_clicked"     => Ok(Self::TripleClicked      (value.read()?)),
-            "clicked_elsewhere"  => Ok(Self::ClickedElsewhere   (value.read()?)),
-            "hovered"            => Ok(Self::Hovered            (value.read()?)),
-            "highlighted"        => Ok(Self::Highlighted        (value.read()?)),
-            "changed"            => Ok(Self::Changed            (value.read()?)),
-            "on_hover"           => Ok(Self::OnHover            (value.read()?)),
-            "on_disabled_hover"  => Ok(Self::OnDisabledHover    (value.read()?)),
-            "on_hover_at_pointer"=> Ok(Self::OnHoverAtPointer   (value.read()?)),
-            "highlight"          => Ok(Self::Highlight          (value.read()?)),
-            _                    => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+            "min"              => Ok(Self::Min             (value.read()?)),
+            "max"              => Ok(Self::Max             (value.read()?)),
+            "size"             => Ok(Self::Size            (value.read()?)),
+            "thickness"        => Ok(Self::Thickness       (value.read()?)),
+            "start_angle"      => Ok(Self::StartAngle      (value.read()?)),
+            "end_angle"        => Ok(Self::EndAngle        (value.read()?)),
+            "color"            => Ok(Self::Color           (value.read()?)),
+            "background_color" => Ok(Self::BackgroundColor (value.read()?)),
+            "stop"             => Ok(Self::Stop            (value.read()?)),
+            "text"             => Ok(Self::Text            (value.read()?)),
+            _                  => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+        }
+    }
+
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Min(min)               => writer.field("min", min),
+            Self::Max(max)               => writer.field("max", max),
+            Self::Size(size)             => writer.field("size", size),
+            Self::Thickness(thickness)   => writer.field("thickness", thickness),
+            Self::StartAngle(angle)      => writer.field("start_angle", angle),
+            Self::EndAngle(angle)        => writer.field("end_angle", angle),
+            Self::Color(color)           => writer.field("color", color),
+            Self::BackgroundColor(color) => writer.field("background_color", color),
+            Self::Stop(stop)             => writer.field("stop", stop),
+            Self::Text(text)             => writer.field("text", text),
         }
     }
 }
 
-//
-// Anchor
-//
+#[derive(Debug, Clone, Copy)]
+pub struct GaugeStop {
+    pub at: f32,
+    pub color: bevy::prelude::Color,
+}
 
-#[derive(Debug)]
-pub struct Anchor {
-    pub align: egui::Align2,
-    pub offset: egui::Vec2,
+impl GaugeStop {
+    const FIELDS: &'static [&'static str] = &["at", "color"];
 }
 
-impl ReadUiconf for Anchor {
+impl ReadUiconf for GaugeStop {
     fn read_uiconf(value: &Reader) -> Result<Self, Error> {
-        const EXPECTED: &str = "{ align valign x y }";
-        let mut seq = value.read_array()?;
-        let mut align_x = seq.next().ok_or_else(|| Error::invalid_length(value, 0, EXPECTED))?.read::<Alignment>()?;
-        let mut align_y = seq.next().ok_or_else(|| Error::invalid_length(value, 1, EXPECTED))?.read::<Alignment>()?;
+        let mut at = None;
+        let mut color = None;
 
-        if align_x.can_be_horizontal() && align_y.can_be_vertical() {
-            // all good
-        } else if align_x.can_be_vertical() && align_y.can_be_horizontal() {
-            std::mem::swap(&mut align_x, &mut align_y);
-        } else {
-            return Err(Error::custom(value, format!(
-                "invalid alignment: `{} {}`",
-                align_x.to_string(), align_y.to_string(),
-            )));
+        for (key, value) in value.read_object()? {
+            match &*key {
+                "at" => {
+                    if at.is_some() { return Err(Error::duplicate_field(&value, "at")); }
+                    at = Some(value.read()?);
+                }
+                "color" => {
+                    if color.is_some() { return Err(Error::duplicate_field(&value, "color")); }
+                    color = Some(value.read()?);
+                }
+                str => {
+                    value.skip_unknown_field(str, GaugeStop::FIELDS)?;
+                }
+            }
         }
 
-        let align = egui::Align2([
-            match align_x {
-                Alignment::Left   => egui::Align::Min,
-                Alignment::Center => egui::Align::Center,
-                Alignment::Right  => egui::Align::Max,
-                _ => unreachable!(),
-            },
-            match align_y {
-                Alignment::Top    => egui::Align::Min,
-                Alignment::Center => egui::Align::Center,
-                Alignment::Bottom => egui::Align::Max,
-                _ => unreachable!(),
-            },
-        ]);
+        let at = at.ok_or_else(|| Error::missing_field(value, "at"))?;
+        let color = color.ok_or_else(|| Error::missing_field(value, "color"))?;
 
-        let offset = if let Some(offset_x) = seq.next() {
-            let offset_x = offset_x.read::<f32>()?;
-            let offset_y = seq.next().ok_or_else(|| Error::invalid_length(value, 3, EXPECTED))?.read::<f32>()?;
-            if seq.next().is_some() {
-                return Err(Error::invalid_length(value, 5, EXPECTED));
-            }
-            egui::Vec2::new(offset_x, offset_y)
-        } else {
-            if seq.next().is_some() {
-                return Err(Error::invalid_length(value, 3, EXPECTED));
-            }
-            egui::Vec2::ZERO
-        };
+        Ok(GaugeStop { at, color })
+    }
+}
 
-        Ok(Anchor { align, offset })
+impl ToUiconf for GaugeStop {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            writer.field("at", &self.at);
+            writer.field("color", &self.color);
+        });
     }
 }
 
 //
-// RichText
+// DragVector
 //
 
+// `drag_vec2`, `drag_vec3` and `drag_angle`, editors for transform-like fields: one drag value per component,
+// angle is stored in radians and shown in degrees
 #[derive(Debug)]
-pub struct RichText {
-    pub text: Binding<String>,
-    pub props: Vec<RichTextProperty>,
+pub struct DragVector {
+    pub id: Option<WidgetId>,
+    pub enabled: Option<Binding<bool>>,
+    pub transition: Option<Transition>,
+    pub value: DragVectorValue,
+    pub visible: Option<Condition>,
+    pub groups: Vec<String>,
+    pub size_limits: SizeLimits,
+    pub props: Vec<DragVectorProperty>,
+    pub response: Response,
 }
 
-impl RichText {
+#[derive(Debug)]
+pub enum DragVectorValue {
+    Vec2(BindingRef<bevy::prelude::Vec2>),
+    Vec3(BindingRef<bevy::prelude::Vec3>),
+    Angle(BindingRef<f32>),
+}
+
+#[derive(EnumString, EnumVariantNames, Display, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum DragVectorKind {
+    Vec2,
+    Vec3,
+    Angle,
+}
+
+impl DragVector {
     const FIELDS: &'static [&'static str] = const_concat!(
-        &["text"],
-        RichTextProperty::FIELDS,
+        &["id", "enabled", "transition", "value", "visible", "groups"],
+        SizeLimits::FIELDS,
+        DragVectorProperty::FIELDS,
+        ResponseProperty::FIELDS,
     );
 
-    pub fn new(text: Binding<String>) -> Self {
-        Self { text, props: vec![] }
+    pub fn kind(&self) -> DragVectorKind {
+        match self.value {
+            DragVectorValue::Vec2(_)  => DragVectorKind::Vec2,
+            DragVectorValue::Vec3(_)  => DragVectorKind::Vec3,
+            DragVectorValue::Angle(_) => DragVectorKind::Angle,
+        }
     }
-}
 
-impl ResolveBinding for RichText {
-    type Item = egui::RichText;
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui) -> Option<egui::Response> {
+        // components are edited on a copy, and written back only if changed
+        let mut components = match &self.value {
+            DragVectorValue::Vec2(value)  => match value.resolve_ref(data) { Ok(v) => v.to_array().to_vec(), Err(_) => return None },
+            DragVectorValue::Vec3(value)  => match value.resolve_ref(data) { Ok(v) => v.to_array().to_vec(), Err(_) => return None },
+            DragVectorValue::Angle(value) => match value.resolve_ref(data) { Ok(v) => vec![v.to_degrees()], Err(_) => return None },
+        };
+
+        let labels = self.props.iter().rev().find_map(|prop| match prop {
+            DragVectorProperty::Labels(labels) => Some(labels.as_slice()),
+            _ => None,
+        });
 
-    fn resolve(&self, data: &dyn Reflect) -> anyhow::Result<Self::Item> {
-        let text = self.text.resolve_ref(data).cloned().unwrap_or_default();
-        let mut result = egui::RichText::new(text);
+        let response = ui.horizontal(|ui| {
+            let mut response: Option<egui::Response> = None;
 
-        for prop in self.props.iter() {
-            use RichTextProperty as P;
-            match prop {
-                P::Size(size) => {
-                    if let Ok(size) = size.resolve(data) {
-                        result = result.size(size);
-                    }
-                }
-                P::Style(styles) => {
-                    for style in styles {
-                        result = match style {
-                            RichTextStyle::Small         => result.text_style(egui::TextStyle::Small),
-                            RichTextStyle::Body          => result.text_style(egui::TextStyle::Body),
-                            RichTextStyle::Monospace     => result.text_style(egui::TextStyle::Monospace),
-                            RichTextStyle::Button        => result.text_style(egui::TextStyle::Button),
-                            RichTextStyle::Heading       => result.text_style(egui::TextStyle::Heading),
-                            RichTextStyle::Code          => result.code(),
-                            RichTextStyle::Strong        => result.strong(),
-                            RichTextStyle::Weak          => result.weak(),
-                            RichTextStyle::Strikethrough => result.strikethrough(),
-                            RichTextStyle::Underline     => result.underline(),
-                            RichTextStyle::Italics       => result.italics(),
-                            RichTextStyle::Raised        => result.raised(),
-                        };
-                    }
+            for (idx, component) in components.iter_mut().enumerate() {
+                let mut drag = egui::DragValue::new(component);
+
+                if self.kind() == DragVectorKind::Angle {
+                    drag = drag.suffix("°");
+                } else {
+                    let label = labels.and_then(|labels| labels.get(idx)).map(String::as_str);
+                    drag = drag.prefix(format!("{}: ", label.unwrap_or(["x", "y", "z"][idx])));
                 }
-                P::Color(color) => {
-                    if let Ok(color) = color.resolve(data) {
-                        result = result.color(color_bevy_to_egui(color));
-                    }
+
+                for prop in self.props.iter() {
+                    use DragVectorProperty as P;
+                    drag = match prop {
+                        P::Speed(speed)       => drag.speed(*speed),
+                        P::Min(min)           => drag.clamp_range(*min..=f64::INFINITY),
+                        P::Max(max)           => drag.clamp_range(f64::NEG_INFINITY..=*max),
+                        P::Decimals(decimals) => drag.fixed_decimals(*decimals as usize),
+                        P::Labels(_)          => drag,
+                    };
                 }
-                P::BackgroundColor(color) => {
-                    if let Ok(color) = color.resolve(data) {
-                        result = result.background_color(color_bevy_to_egui(color));
-                    }
+
+                let component = ui.add(drag);
+                response = Some(match response {
+                    Some(response) => response.union(component),
+                    None => component,
+                });
+            }
+
+            response.unwrap()
+        }).inner;
+
+        if response.changed() {
+            match &self.value {
+                DragVectorValue::Vec2(value) => if let Ok(value) = value.resolve_mut(data) {
+                    *value = bevy::prelude::Vec2::from_slice(&components);
                 }
-                P::LineHeight(line_height) => {
-                    if let Ok(line_height) = line_height.resolve(data) {
-                        result = result.line_height(Some(line_height));
-                    }
+                DragVectorValue::Vec3(value) => if let Ok(value) = value.resolve_mut(data) {
+                    *value = bevy::prelude::Vec3::from_slice(&components);
                 }
-                P::ExtraLetterSpacing(spacing) => {
-                    if let Ok(spacing) = spacing.resolve(data) {
-                        result = result.extra_letter_spacing(spacing);
-                    }
+                DragVectorValue::Angle(value) => if let Ok(value) = value.resolve_mut(data) {
+                    *value = components[0].to_radians();
                 }
             }
         }
 
-        Ok(result)
+        Some(self.response.process(data, response))
     }
-}
 
-impl ReadUiconf for RichText {
-    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+    pub fn read(value: &Reader, kind: DragVectorKind) -> Result<Self, Error> {
+        // `drag_vec2 = @position` is a shortcut for `drag_vec2 = { value = @position }`
         if value.is_scalar() {
-            return Ok(Self::new(value.read()?));
+            return Ok(DragVector {
+                id: None,
+                enabled: None,
+                transition: None,
+                value: DragVectorValue::read(value, kind)?,
+                visible: None,
+            groups: vec![],
+                size_limits: SizeLimits::default(),
+                props: vec![],
+                response: Response(vec![]),
+            });
         }
 
-        let mut text = None;
+        let mut id = None;
+        let mut enabled = None;
+        let mut transition = None;
+        let mut binding = None;
+        let mut visible = None;
+        let mut groups = vec![];
+        let mut size_limits = SizeLimits::default();
         let mut props = vec![];
+        let mut response = vec![];
 
         for (key, value) in value.read_object()? {
-            if key == "text" {
-                if text.is_some() { return Err(Error::duplicate_field(&value, "text")); }
-                text = Some(value.read::<Binding<String>>()?);
-            } else if RichTextProperty::FIELDS.contains(&&*key) {
-                props.push(RichTextProperty::read_map_value(&key, &value)?);
+            if key == "value" {
+                if binding.is_some() { return Err(Error::duplicate_field(&value, "value")); }
+                binding = Some(DragVectorValue::read(&value, kind)?);
+            } else if SizeLimits::FIELDS.contains(&&*key) {
+                size_limits.read_map_value(&key, &value)?;
+            } else if key == "visible" {
+                if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
+                visible = Some(value.read()?);
+            } else if key == "groups" {
+                if !groups.is_empty() { return Err(Error::duplicate_field(&value, "groups")); }
+                groups = value.read()?;
+            } else if key == "id" {
+                if id.is_some() { return Err(Error::duplicate_field(&value, "id")); }
+                id = Some(value.read()?);
+            } else if key == "enabled" {
+                if enabled.is_some() { return Err(Error::duplicate_field(&value, "enabled")); }
+                enabled = Some(value.read()?);
+            } else if key == "transition" {
+                if transition.is_some() { return Err(Error::duplicate_field(&value, "transition")); }
+                transition = Some(value.read()?);
+            } else if DragVectorProperty::FIELDS.contains(&&*key) {
+                props.push(DragVectorProperty::read_map_value(&key, &value, kind)?);
+            } else if ResponseProperty::FIELDS.contains(&&*key) {
+                response.push(ResponseProperty::read_map_value(&key, &value)?);
             } else {
-                return Err(Error::unknown_field(&value, &key, RichText::FIELDS));
+                value.skip_unknown_field(&key, DragVector::FIELDS)?;
             }
         }
 
-        let text = text.ok_or_else(|| Error::missing_field(value, "text"))?;
-        Ok(Self { text, props })
+        let binding = binding.ok_or_else(|| Error::missing_field(value, "value"))?;
+
+        Ok(DragVector { id, enabled, transition, value: binding, visible, groups, size_limits, props, response: Response(response) })
     }
 }
 
-//
-// RichTextProperty
-//
-
-#[derive(Debug)]
-pub enum RichTextProperty {
-    Size(Binding<f32>),
-    Style(Vec<RichTextStyle>),
-    Color(Binding<bevy::prelude::Color>),
-    BackgroundColor(Binding<bevy::prelude::Color>),
-    LineHeight(Binding<f32>),
-    ExtraLetterSpacing(Binding<f32>),
+impl DragVectorValue {
+    fn read(value: &Reader, kind: DragVectorKind) -> Result<Self, Error> {
+        match kind {
+            DragVectorKind::Vec2  => Ok(Self::Vec2  (value.read()?)),
+            DragVectorKind::Vec3  => Ok(Self::Vec3  (value.read()?)),
+            DragVectorKind::Angle => Ok(Self::Angle (value.read()?)),
+        }
+    }
 }
 
-impl RichTextProperty {
-    const FIELDS: &'static [&'static str] = &[
-        "size", "style", "color", "background_color", "line_height", "extra_letter_spacing",
-    ];
-
-    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
-        match tag {
-            "size"                 => Ok(Self::Size               (value.read()?)),
-            "extra_letter_spacing" => Ok(Self::ExtraLetterSpacing (value.read()?)),
-            "line_height"          => Ok(Self::LineHeight         (value.read()?)),
-            "style"                => Ok(Self::Style              (value.read()?)),
-            "background_color"     => Ok(Self::BackgroundColor    (value.read::<Binding<Color>>()?.map_value(|c| c.0))),
-            "color"                => Ok(Self::Color              (value.read::<Binding<Color>>()?.map_value(|c| c.0))),
-            _ => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+impl ToUiconf for DragVectorValue {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        match self {
+            Self::Vec2(value)  => value.write_uiconf(writer),
+            Self::Vec3(value)  => value.write_uiconf(writer),
+            Self::Angle(value) => value.write_uiconf(writer),
         }
     }
 }
 
-//
-// RichTextStyle
-//
-
-#[derive(EnumString, EnumVariantNames, Debug, Clone, Copy)]
-#[strum(serialize_all = "snake_case")]
-pub enum RichTextStyle {
-    Small,
-    Body,
-    Monospace,
-    Button,
-    Heading,
-    Code,
-    Strong,
-    Weak,
-    Strikethrough,
-    Underline,
-    Italics,
-    Raised,
-}
+impl ToUiconf for DragVector {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        if self.id.is_none() && self.enabled.is_none() && self.transition.is_none() && self.visible.is_none() && self.groups.is_empty() && self.size_limits.is_empty() && self.props.is_empty() && self.response.0.is_empty() {
+            self.value.write_uiconf(writer);
+            return;
+        }
 
-impl ReadUiconf for RichTextStyle {
-    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
-        let name = value.read_string()?;
-        Self::from_str(&name).map_err(|_| {
-            Error::unknown_variant(value, &name, Self::VARIANTS)
-        })
+        writer.object(|writer| {
+            if let Some(id) = &self.id {
+                writer.field("id", id);
+            }
+            if let Some(enabled) = &self.enabled {
+                writer.field("enabled", enabled);
+            }
+            if let Some(transition) = &self.transition {
+                writer.field("transition", transition);
+            }
+            writer.field("value", &self.value);
+            if let Some(visible) = &self.visible {
+                writer.field("visible", visible);
+            }
+            if !self.groups.is_empty() {
+                writer.field("groups", &self.groups);
+            }
+            self.size_limits.write_map_values(writer);
+            for prop in self.props.iter() {
+                prop.write_map_value(writer);
+            }
+            self.response.write_map_values(writer);
+        });
     }
 }
 
 //
-// Button
+// DragVectorProperty
 //
 
-#[derive(Debug)]
-pub struct Button {
-    pub text: RichText,
-    pub small: bool,
-    pub visible: Option<Binding<bool>>,
-    pub props: Vec<ButtonProperty>,
-    pub response: Response,
+// `min`, `max` and `speed` of `drag_angle` are in degrees
+#[derive(Debug, Clone)]
+pub enum DragVectorProperty {
+    Speed(f64),
+    Min(f64),
+    Max(f64),
+    Decimals(u32),
+    // replaces default `x`, `y`, `z` component labels
+    Labels(Vec<String>),
 }
 
-impl Button {
-    const FIELDS: &'static [&'static str] = const_concat!(
-        &["text", "small", "visible"],
-        ButtonProperty::FIELDS,
-        ResponseProperty::FIELDS,
-    );
+impl DragVectorProperty {
+    const FIELDS: &'static [&'static str] = &["speed", "min", "max", "decimals", "labels"];
 
-    pub fn new(text: RichText) -> Self {
-        Self {
-            text,
-            small: false,
-            visible: None,
-            props: vec![],
-            response: Response(vec![]),
+    fn read_map_value(tag: &str, value: &Reader, kind: DragVectorKind) -> Result<Self, Error> {
+        match tag {
+            "speed"    => Ok(Self::Speed    (value.read()?)),
+            "min"      => Ok(Self::Min      (value.read()?)),
+            "max"      => Ok(Self::Max      (value.read()?)),
+            "decimals" => Ok(Self::Decimals (value.read()?)),
+            "labels"   => {
+                let labels = value.read::<Vec<String>>()?;
+                let expected = match kind {
+                    DragVectorKind::Vec2  => 2,
+                    DragVectorKind::Vec3  => 3,
+                    DragVectorKind::Angle => return Err(Error::custom(value, "`drag_angle` has no component labels")),
+                };
+                if labels.len() != expected {
+                    return Err(Error::custom(value, format!("expected {} labels, found {}", expected, labels.len())));
+                }
+                Ok(Self::Labels(labels))
+            }
+            _          => Err(Error::unknown_field(value, tag, Self::FIELDS)),
         }
     }
 
-    fn show(&self, data: &mut dyn Reflect, ui: &mut egui::Ui) {
-        if let Some(visible) = &self.visible {
-            if let Ok(visible) = visible.resolve(data) {
-                if !visible { return; }
-            }
+    fn write_map_value(&self, writer: &mut Writer) {
+        match self {
+            Self::Speed(speed)       => writer.field("speed", speed),
+            Self::Min(min)           => writer.field("min", min),
+            Self::Max(max)           => writer.field("max", max),
+            Self::Decimals(decimals) => writer.field("decimals", decimals),
+            Self::Labels(labels)     => writer.field("labels", labels),
         }
+    }
+}
 
-        let text = self.text.resolve(data).ok().unwrap_or_default();
-        let mut button = egui::Button::new(text);
+//
+// Transition
+//
 
-        if self.small {
-            button = button.small();
-        }
+// `transition = { duration = 0.3 fade = yes slide = { 0 -20 } }`, widget fades and slides in and out
+// when its `visible` binding changes; `transition = 0.3` is a shortcut for a fade
+#[derive(Debug, Clone)]
+pub struct Transition {
+    pub duration: f32,
+    pub fade: bool,
+    // where hidden widget is moved to, relative to its position
+    pub slide: Option<egui::Vec2>,
+}
 
-        for prop in self.props.iter() {
-            use ButtonProperty as P;
-            button = match prop {
-                P::ShortcutText(text) => {
-                    if let Ok(text) = text.resolve(data) {
-                        button.shortcut_text(text)
-                    } else {
-                        button
-                    }
-                },
-                P::Wrap(wrap) => button.wrap(*wrap),
-                P::Fill(color) => {
-                    if let Ok(color) = color.resolve(data) {
-                        button.fill(color_bevy_to_egui(color))
-                    } else {
-                        button
-                    }
-                }
-                P::Stroke(stroke) => {
-                    if let Ok(stroke) = stroke.resolve(data) {
-                        button.stroke(stroke)
-                    } else {
-                        button
-                    }
-                }
-                P::Sense(sense)       => button.sense(sense.0),
-                P::Frame(frame)       => button.frame(*frame),
-                P::MinSize(size)      => button.min_size(*size),
-                P::Rounding(rounding) => button.rounding(*rounding),
-                P::Selected(selected) => button.selected(*selected),
-            };
+impl Transition {
+    const FIELDS: &'static [&'static str] = &["duration", "fade", "slide"];
+
+    fn show(&self, ui: &mut egui::Ui, id: egui::Id, visible: bool, add_contents: impl FnOnce(&mut egui::Ui)) {
+        let shown = ui.ctx().animate_bool_with_time(id.with("uiconf_transition"), visible, self.duration);
+
+        if shown == 0. { return; }
+        if shown == 1. {
+            add_contents(ui);
+            return;
         }
 
-        self.response.process(data, ui.add(button));
+        // content is painted moved by `offset`, but space is reserved at its usual position
+        let offset = self.slide.unwrap_or_default() * (1. - shown);
+        let mut child = ui.child_ui(ui.available_rect_before_wrap().translate(offset), *ui.layout());
+        if self.fade {
+            fade_visuals(child.visuals_mut(), shown);
+        }
+        add_contents(&mut child);
+        ui.allocate_rect(child.min_rect().translate(-offset), egui::Sense::hover());
     }
 }
 
-impl ReadUiconf for Button {
+impl ReadUiconf for Transition {
     fn read_uiconf(value: &Reader) -> Result<Self, Error> {
         if value.is_scalar() {
-            return Ok(Self::new(value.read()?));
+            return Ok(Transition { duration: value.read()?, fade: true, slide: None });
         }
 
-        let mut text = None;
-        let mut visible = None;
-        let mut small = false;
-        let mut props = vec![];
-        let mut response = vec![];
+        let mut duration = None;
+        let mut fade = None;
+        let mut slide = None;
 
         for (key, value) in value.read_object()? {
             match &*key {
-                "text" => {
-                    if text.is_some() { return Err(Error::duplicate_field(&value, "text")); }
-                    text = Some(value.read()?);
-                }
-                "visible" => {
-                    if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
-                    visible = Some(value.read()?);
-                }
-                "small" => {
-                    small = value.read()?;
-                }
+                "duration" => { duration = Some(value.read()?); }
+                "fade"     => { fade     = Some(value.read()?); }
+                "slide"    => { slide    = Some(value.read::<Size<{ SIZE_ANY_DISALLOWED }>>()?.0); }
                 str => {
-                    if ButtonProperty::FIELDS.contains(&str) {
-                        props.push(ButtonProperty::read_map_value(&key, &value)?);
-                    } else if ResponseProperty::FIELDS.contains(&str) {
-                        response.push(ResponseProperty::read_map_value(&key, &value)?);
-                    } else {
-                        return Err(Error::unknown_field(&value, &key, Button::FIELDS));
-                    }
+                    value.skip_unknown_field(str, Transition::FIELDS)?;
                 }
             }
         }
 
-        let text = text.ok_or_else(|| Error::missing_field(value, "text"))?;
+        let duration = duration.ok_or_else(|| Error::missing_field(value, "duration"))?;
 
-        Ok(Button { text, visible, small, props, response: Response(response) })
+        // fade is on by default, unless the widget slides
+        Ok(Transition { duration, fade: fade.unwrap_or(slide.is_none()), slide })
+    }
+}
+
+impl ToUiconf for Transition {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        if self.fade && self.slide.is_none() {
+            self.duration.write_uiconf(writer);
+            return;
+        }
+
+        writer.object(|writer| {
+            writer.field("duration", &self.duration);
+            if self.fade == self.slide.is_some() {
+                writer.field("fade", &self.fade);
+            }
+            if let Some(slide) = self.slide {
+                writer.field("slide", &Size::<{ SIZE_ANY_DISALLOWED }>(slide));
+            }
+        });
     }
 }
 
 //
-// ButtonProperty
+// SizeLimits
 //
 
-#[derive(Debug)]
-pub enum ButtonProperty {
-    ShortcutText(RichText),
-    Wrap(bool),
-    Fill(Binding<bevy::prelude::Color>),
-    Stroke(Stroke),
-    Sense(Sense),
-    Frame(bool),
-    MinSize(egui::Vec2),
-    Rounding(egui::Rounding),
-    Selected(bool),
+// `min_width = 100 max_width = 300`, space any widget is laid out in, e.g. so that a long label wraps
+#[derive(Debug, Clone, Default)]
+pub struct SizeLimits {
+    pub min_width: Option<f32>,
+    pub max_width: Option<f32>,
+    pub min_height: Option<f32>,
+    pub max_height: Option<f32>,
 }
 
-impl ButtonProperty {
-    const FIELDS: &'static [&'static str] = &[
-        "shortcut_text", "wrap", "fill", "stroke", "sense", "frame", "min_size", "rounding", "selected",
-    ];
+impl SizeLimits {
+    const FIELDS: &'static [&'static str] = &["min_width", "max_width", "min_height", "max_height"];
 
-    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
-        match tag {
-            "shortcut_text" => Ok(Self::ShortcutText (value.read()?)),
-            "wrap"          => Ok(Self::Wrap         (value.read()?)),
-            "fill"          => Ok(Self::Fill         (value.read::<Binding<Color>>()?.map_value(|c| c.0))),
-            "stroke"        => Ok(Self::Stroke       (value.read()?)),
-            "sense"         => Ok(Self::Sense        (value.read()?)),
-            "frame"         => Ok(Self::Frame        (value.read()?)),
-            "min_size"      => Ok(Self::MinSize      (value.read::<Size<{ SIZE_ANY_IS_ZERO }>>()?.0)),
-            "rounding"      => Ok(Self::Rounding     (value.read::<Rounding>()?.0)),
-            "selected"      => Ok(Self::Selected     (value.read()?)),
-            _               => Err(Error::unknown_field(value, tag, Self::FIELDS)),
-        }
+    fn is_empty(&self) -> bool {
+        self.min_width.is_none() && self.max_width.is_none() && self.min_height.is_none() && self.max_height.is_none()
+    }
+
+    // widget gets a child ui that is at most `max` size, and occupies at least `min` size
+    fn show<R>(&self, ui: &mut egui::Ui, add_contents: impl FnOnce(&mut egui::Ui) -> R) -> R {
+        let available = ui.available_size_before_wrap();
+        let max_size = egui::vec2(
+            self.max_width.map_or(available.x, |width| width.min(available.x)),
+            self.max_height.map_or(available.y, |height| height.min(available.y)),
+        );
+        let min_size = egui::vec2(self.min_width.unwrap_or(0.), self.min_height.unwrap_or(0.));
+
+        ui.allocate_ui_with_layout(max_size, *ui.layout(), |ui| {
+            ui.set_min_size(min_size);
+            add_contents(ui)
+        }).inner
+    }
+
+    fn read_map_value(&mut self, tag: &str, value: &Reader) -> Result<(), Error> {
+        let field = match tag {
+            "min_width"  => &mut self.min_width,
+            "max_width"  => &mut self.max_width,
+            "min_height" => &mut self.min_height,
+            "max_height" => &mut self.max_height,
+            _            => return Err(Error::unknown_field(value, tag, Self::FIELDS)),
+        };
+        if field.is_some() { return Err(Error::duplicate_field(value, tag)); }
+        *field = Some(value.read()?);
+        Ok(())
+    }
+
+    fn write_map_values(&self, writer: &mut Writer) {
+        if let Some(min_width) = &self.min_width { writer.field("min_width", min_width); }
+        if let Some(max_width) = &self.max_width { writer.field("max_width", max_width); }
+        if let Some(min_height) = &self.min_height { writer.field("min_height", min_height); }
+        if let Some(max_height) = &self.max_height { writer.field("max_height", max_height); }
     }
 }
 
 //
-// Label
+// AspectRatio
 //
 
-#[derive(Debug)]
-pub struct Label {
-    pub text: RichText,
-    pub visible: Option<Binding<bool>>,
-    pub props: Vec<LabelProperty>,
-    pub response: Response,
+// `aspect_ratio = 16:9` or `aspect_ratio = 1.5`, container takes the biggest rect of this ratio
+// that fits into available space, e.g. for minimaps and video thumbnails
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AspectRatio {
+    pub width: f32,
+    pub height: f32,
 }
 
-impl Label {
-    const FIELDS: &'static [&'static str] = const_concat!(
-        &["text", "visible"],
-        LabelProperty::FIELDS,
-        ResponseProperty::FIELDS,
-    );
+impl AspectRatio {
+    // content is clipped to the rect, so that it doesn't break the ratio
+    fn show<R>(&self, ui: &mut egui::Ui, add_contents: impl FnOnce(&mut egui::Ui) -> R) -> R {
+        let ratio = self.width / self.height;
+        let available = ui.available_size_before_wrap();
+        // height is often unbounded in scroll areas and auto-sized windows, then width decides
+        let width = if available.y.is_finite() { available.x.min(available.y * ratio) } else { available.x };
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(width, width / ratio), egui::Sense::hover());
+
+        let id = ui.next_auto_id().with("uiconf_aspect_ratio");
+        let mut child = ui.child_ui_with_id_source(rect, *ui.layout(), id);
+        child.set_clip_rect(rect.intersect(ui.clip_rect()));
+        add_contents(&mut child)
+    }
+}
 
-    pub fn new(text: RichText) -> Self {
-        Self {
-            text,
-            visible: None,
-            props: vec![],
-            response: Response(vec![]),
+impl ReadUiconf for AspectRatio {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let string = value.read_string()?;
+        let invalid = || Error::invalid_value(value, &string, "ratio like `16:9` or `1.5`");
+
+        let (width, height) = match string.split_once(':') {
+            Some((width, height)) => (width.trim().parse().map_err(|_| invalid())?, height.trim().parse().map_err(|_| invalid())?),
+            None => (string.trim().parse().map_err(|_| invalid())?, 1.),
+        };
+        if !(width > 0. && height > 0.) {
+            return Err(invalid());
         }
+
+        Ok(AspectRatio { width, height })
     }
+}
 
-    fn show(&self, data: &mut dyn Reflect, ui: &mut egui::Ui) {
-        if let Some(visible) = &self.visible {
-            if let Ok(visible) = visible.resolve(data) {
-                if !visible { return; }
-            }
+impl ToUiconf for AspectRatio {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        if self.height == 1. {
+            writer.scalar(self.width);
+        } else {
+            writer.scalar(format!("{}:{}", self.width, self.height));
         }
+    }
+}
 
-        let text = self.text.resolve(data).ok().unwrap_or_default();
-        let mut label = egui::Label::new(text);
+//
+// Animate
+//
 
-        for prop in self.props.iter() {
-            use LabelProperty as P;
-            label = match prop {
-                P::Wrap(wrap)         => label.wrap(*wrap),
-                P::Truncate(truncate) => label.truncate(*truncate),
-                P::Sense(sense)       => label.sense(sense.0),
-            };
-        }
+// `animate = { duration = 0.3 easing = ease_out }`, changes of text size, colors (including alpha),
+// fills, strokes and margins of a widget are tweened instead of applied instantly
+#[derive(Debug, Clone)]
+pub struct Animate {
+    pub duration: f32,
+    pub easing: Easing,
+}
 
-        self.response.process(data, ui.add(label));
-    }
+impl Animate {
+    const FIELDS: &'static [&'static str] = &["duration", "easing"];
 }
 
-impl ReadUiconf for Label {
+impl ReadUiconf for Animate {
     fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        // `animate = 0.3` is a shortcut for `animate = { duration = 0.3 }`
         if value.is_scalar() {
-            return Ok(Self::new(value.read()?));
+            return Ok(Animate { duration: value.read()?, easing: Easing::default() });
         }
 
-        let mut text = None;
-        let mut visible = None;
-        let mut props = vec![];
-        let mut response = vec![];
+        let mut duration = None;
+        let mut easing = None;
 
         for (key, value) in value.read_object()? {
-            if key == "text" {
-                if text.is_some() { return Err(Error::duplicate_field(&value, "text")); }
-                text = Some(value.read()?);
-            } else if key == "visible" {
-                if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
-                visible = Some(value.read()?);
-            } else if LabelProperty::FIELDS.contains(&&*key) {
-                props.push(LabelProperty::read_map_value(&key, &value)?);
-            } else if ResponseProperty::FIELDS.contains(&&*key) {
-                response.push(ResponseProperty::read_map_value(&key, &value)?);
-            } else {
-                return Err(Error::unknown_field(&value, &key, Label::FIELDS));
+            match &*key {
+                "duration" => { duration = Some(value.read()?); }
+                "easing"   => { easing   = Some(value.read()?); }
+                str => {
+                    value.skip_unknown_field(str, Animate::FIELDS)?;
+                }
             }
         }
 
-        let text = text.ok_or_else(|| Error::missing_field(value, "text"))?;
+        let duration = duration.ok_or_else(|| Error::missing_field(value, "duration"))?;
 
-        Ok(Label { text, visible, props, response: Response(response) })
+        Ok(Animate { duration, easing: easing.unwrap_or_default() })
     }
 }
 
-//
-// LabelProperty
-//
+impl ToUiconf for Animate {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        if self.easing == Easing::default() {
+            self.duration.write_uiconf(writer);
+            return;
+        }
 
-#[derive(Debug, Clone)]
-pub enum LabelProperty {
-    Wrap(bool),
-    Truncate(bool),
-    Sense(Sense),
+        writer.object(|writer| {
+            writer.field("duration", &self.duration);
+            writer.field("easing", &self.easing);
+        });
+    }
 }
 
-impl LabelProperty {
-    const FIELDS: &'static [&'static str] = &["wrap", "truncate", "sense"];
+#[derive(EnumString, EnumVariantNames, Display, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[strum(serialize_all = "snake_case")]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    #[default]
+    EaseOut,
+    EaseInOut,
+}
 
-    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
-        match tag {
-            "wrap"     => Ok(Self::Wrap     (value.read()?)),
-            "truncate" => Ok(Self::Truncate (value.read()?)),
-            "sense"    => Ok(Self::Sense    (value.read()?)),
-            _          => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+impl Easing {
+    // cubic curves, `t` is in 0..=1
+    fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear    => t,
+            Easing::EaseIn    => t * t * t,
+            Easing::EaseOut   => 1. - (1. - t).powi(3),
+            Easing::EaseInOut => if t < 0.5 { 4. * t * t * t } else { 1. - (-2. * t + 2.).powi(3) / 2. },
         }
     }
 }
 
-//
-// Separator
-//
-
-#[derive(Debug)]
-pub struct Separator {
-    pub visible: Option<Binding<bool>>,
-    pub props: Vec<SeparatorProperty>,
-    pub response: Response,
+impl ReadUiconf for Easing {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let name = value.read_string()?;
+        Self::from_str(&name).map_err(|_| {
+            Error::unknown_variant(value, &name, Self::VARIANTS)
+        })
+    }
 }
 
-impl Separator {
-    const FIELDS: &'static [&'static str] = const_concat!(
-        &["visible"],
-        SeparatorProperty::FIELDS,
-        ResponseProperty::FIELDS,
-    );
-
-    fn show(&self, data: &mut dyn Reflect, ui: &mut egui::Ui) {
-        if let Some(visible) = &self.visible {
-            if let Ok(visible) = visible.resolve(data) {
-                if !visible { return; }
-            }
-        }
+impl ToUiconf for Easing {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.scalar(self);
+    }
+}
 
-        let mut separator = egui::Separator::default();
+// Animation state of a single widget, values are identified by `id` of the widget and a property name.
+#[derive(Clone, Copy)]
+pub struct Tween<'a> {
+    animate: &'a Animate,
+    ctx: &'a egui::Context,
+    id: egui::Id,
+}
 
-        for prop in self.props.iter() {
-            use SeparatorProperty as P;
-            separator = match prop {
-                P::Vertical(vertical) => if *vertical {
-                    separator.vertical()
-                } else {
-                    separator.horizontal()
-                }
-                P::Spacing(spacing)   => separator.spacing(*spacing),
-                P::Grow(grow)         => separator.grow(*grow),
-                P::Shrink(shrink)     => separator.shrink(*shrink),
-            };
-        }
+impl<'a> Tween<'a> {
+    pub fn new(animate: &'a Animate, ctx: &'a egui::Context, id: egui::Id) -> Self {
+        Self { animate, ctx, id: id.with("uiconf_tween") }
+    }
 
-        self.response.process(data, ui.add(separator));
+    // used for parts of the widget that have their own copy of the same properties, e.g. text spans
+    fn with(&self, key: impl std::hash::Hash) -> Self {
+        Self { id: self.id.with(key), ..*self }
     }
-}
 
-impl ReadUiconf for Separator {
-    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
-        let mut visible = None;
-        let mut props = vec![];
-        let mut response = vec![];
+    fn value(&self, id: egui::Id, target: f32) -> f32 {
+        let linear = self.ctx.animate_value_with_time(id, target, self.animate.duration);
 
-        for (key, value) in value.read_object()? {
-            if key == "visible" {
-                if visible.is_some() { return Err(Error::duplicate_field(&value, "visible")); }
-                visible = Some(value.read()?);
-            } else if SeparatorProperty::FIELDS.contains(&&*key) {
-                props.push(SeparatorProperty::read_map_value(&key, &value)?);
-            } else if ResponseProperty::FIELDS.contains(&&*key) {
-                response.push(ResponseProperty::read_map_value(&key, &value)?);
-            } else {
-                return Err(Error::unknown_field(&value, &key, Separator::FIELDS));
+        // egui interpolates linearly, so easing is applied on top of it,
+        // knowing which value the current animation has started from
+        let from = self.ctx.data_mut(|d| {
+            let (from, to) = d.get_temp_mut_or_insert_with(id, || (target, target));
+            if *to != target {
+                (*from, *to) = (linear, target);
             }
-        }
+            *from
+        });
 
-        Ok(Separator { visible, props, response: Response(response) })
+        if from == target { return target; }
+        let t = ((linear - from) / (target - from)).clamp(0., 1.);
+        from + (target - from) * self.animate.easing.apply(t)
     }
-}
 
-//
-// SeparatorProperty
-//
-
-#[derive(Debug, Clone)]
-pub enum SeparatorProperty {
-    Vertical(bool),
-    Spacing(f32),
-    Grow(f32),
-    Shrink(f32),
-}
+    pub fn f32(&self, key: &str, target: f32) -> f32 {
+        self.value(self.id.with(key), target)
+    }
 
-impl SeparatorProperty {
-    const FIELDS: &'static [&'static str] = &["vertical", "spacing", "grow", "shrink"];
+    pub fn color(&self, key: &str, target: egui::Color32) -> egui::Color32 {
+        let id = self.id.with(key);
+        let [r, g, b, a] = target.to_array().map(|c| c as f32);
+        let [r, g, b, a] = [(0, r), (1, g), (2, b), (3, a)].map(|(idx, c)| self.value(id.with(idx), c).round() as u8);
+        egui::Color32::from_rgba_premultiplied(r, g, b, a)
+    }
 
-    fn read_map_value(tag: &str, value: &Reader) -> Result<Self, Error> {
-        match tag {
-            "vertical" => Ok(Self::Vertical   (value.read()?)),
-            "spacing"  => Ok(Self::Spacing    (value.read()?)),
-            "grow"     => Ok(Self::Grow       (value.read()?)),
-            "shrink"   => Ok(Self::Shrink     (value.read()?)),
-            _          => Err(Error::unknown_field(value, tag, Self::FIELDS)),
+    pub fn margin(&self, key: &str, target: egui::Margin) -> egui::Margin {
+        egui::Margin {
+            left:   self.f32(&format!("{}_left", key), target.left),
+            right:  self.f32(&format!("{}_right", key), target.right),
+            top:    self.f32(&format!("{}_top", key), target.top),
+            bottom: self.f32(&format!("{}_bottom", key), target.bottom),
         }
     }
 }
@@ -1348,6 +9130,12 @@ impl ReadUiconf for Alignment {
     }
 }
 
+impl ToUiconf for Alignment {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.scalar(self);
+    }
+}
+
 //
 // Color
 //
@@ -1358,6 +9146,15 @@ pub struct Color(bevy::prelude::Color);
 impl ReadUiconf for Color {
     fn read_uiconf(value: &Reader) -> Result<Self, Error> {
         if value.is_scalar() {
+            let name = value.read_string()?;
+            if let Some(hex) = name.strip_prefix('#') {
+                return parse_hex_color(hex)
+                    .map(Self)
+                    .ok_or_else(|| Error::invalid_value(value, &name, "\"#rrggbb\" or \"#rrggbbaa\""));
+            }
+            if let Some(color) = value.palette_color(&name) {
+                return Ok(Self(color));
+            }
             let value: ColorName = value.read()?;
             return Ok(value.into());
         }
@@ -1380,6 +9177,35 @@ impl ReadUiconf for Color {
     }
 }
 
+// `#` starts a comment in config files, so hex colors need to be quoted: `color = "#ff8800"`
+fn parse_hex_color(hex: &str) -> Option<bevy::prelude::Color> {
+    if !matches!(hex.len(), 6 | 8) || !hex.is_ascii() { return None; }
+
+    let channel = |idx: usize| u8::from_str_radix(hex.get(idx * 2..idx * 2 + 2)?, 16).ok();
+    let a = if hex.len() == 8 { channel(3)? } else { u8::MAX };
+    Some(bevy::prelude::Color::rgba_u8(channel(0)?, channel(1)?, channel(2)?, a))
+}
+
+impl ReadUiconf for bevy::prelude::Color {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        Ok(value.read::<Color>()?.0)
+    }
+}
+
+impl ToUiconf for bevy::prelude::Color {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        let [r, g, b, a] = self.as_rgba_u8();
+        writer.array(|writer| {
+            writer.item(&r);
+            writer.item(&g);
+            writer.item(&b);
+            if a != u8::MAX {
+                writer.item(&a);
+            }
+        });
+    }
+}
+
 //
 // ColorName
 //
@@ -1469,7 +9295,7 @@ pub struct Stroke {
 impl ResolveBinding for Stroke {
     type Item = egui::Stroke;
 
-    fn resolve(&self, data: &dyn Reflect) -> anyhow::Result<Self::Item> {
+    fn resolve(&self, data: &dyn DataSource) -> anyhow::Result<Self::Item> {
         let width = self.width.resolve(data).unwrap_or_default();
         let color = self.color.resolve(data).unwrap_or_default();
         Ok(egui::Stroke::new(width, color_bevy_to_egui(color)))
@@ -1489,7 +9315,7 @@ impl ReadUiconf for Stroke {
 
         let mut seq = value.read_array()?;
         let width = seq.next().ok_or_else(|| Error::invalid_length(value, 0, EXPECTED))?.read()?;
-        let color = seq.next().ok_or_else(|| Error::invalid_length(value, 1, EXPECTED))?.read::<Binding<Color>>()?.map_value(|x| x.0);
+        let color = seq.next().ok_or_else(|| Error::invalid_length(value, 1, EXPECTED))?.read()?;
         if seq.next().is_some() {
             return Err(Error::invalid_length(value, 3, EXPECTED));
         }
@@ -1497,6 +9323,15 @@ impl ReadUiconf for Stroke {
     }
 }
 
+impl ToUiconf for Stroke {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.array(|writer| {
+            writer.item(&self.width);
+            writer.item(&self.color);
+        });
+    }
+}
+
 //
 // Rounding
 //
@@ -1537,6 +9372,109 @@ impl ReadUiconf for Rounding {
     }
 }
 
+impl ToUiconf for Rounding {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        let egui::Rounding { nw, ne, se, sw } = self.0;
+        if nw == ne && nw == se && nw == sw {
+            writer.scalar(nw);
+        } else {
+            writer.array(|writer| {
+                writer.item(&nw);
+                writer.item(&ne);
+                writer.item(&se);
+                writer.item(&sw);
+            });
+        }
+    }
+}
+
+//
+// Margin
+//
+
+#[derive(Debug, Clone, Copy)]
+pub struct Margin(pub egui::Margin);
+
+impl ReadUiconf for Margin {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        const EXPECTED: &str = "{ top right bottom left }";
+
+        if let Ok(str) = value.read_string() {
+            if str == "none" {
+                return Ok(Margin(egui::Margin::ZERO));
+            } else {
+                return Ok(Margin(egui::Margin::same(value.read()?)));
+            }
+        }
+
+        let mut seq = value.read_array()?;
+
+        // same semantics as in CSS
+        let top    = seq.next().ok_or_else(|| Error::invalid_length(value, 0, EXPECTED))?.read::<f32>()?;
+        let right  = seq.next().map(|v| v.read::<f32>()).transpose()?.unwrap_or(top);
+        let bottom = seq.next().map(|v| v.read::<f32>()).transpose()?.unwrap_or(top);
+        let left   = seq.next().map(|v| v.read::<f32>()).transpose()?.unwrap_or(right);
+
+        if seq.next().is_some() {
+            return Err(Error::invalid_length(value, 5, EXPECTED));
+        }
+
+        Ok(Margin(egui::Margin { left, right, top, bottom }))
+    }
+}
+
+impl ToUiconf for Margin {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        let egui::Margin { left, right, top, bottom } = self.0;
+        if left == right && left == top && left == bottom {
+            writer.scalar(left);
+        } else {
+            writer.array(|writer| {
+                writer.item(&top);
+                writer.item(&right);
+                writer.item(&bottom);
+                writer.item(&left);
+            });
+        }
+    }
+}
+
+//
+// Shadow
+//
+
+#[derive(Debug, Clone, Copy)]
+pub struct Shadow(pub egui::epaint::Shadow);
+
+impl ReadUiconf for Shadow {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        const EXPECTED: &str = "{ extrusion color } or none";
+
+        if let Ok(str) = value.read_string() {
+            if str == "none" {
+                return Ok(Shadow(egui::epaint::Shadow::NONE));
+            }
+        }
+
+        let mut seq = value.read_array()?;
+        let extrusion = seq.next().ok_or_else(|| Error::invalid_length(value, 0, EXPECTED))?.read::<f32>()?;
+        let color = seq.next().ok_or_else(|| Error::invalid_length(value, 1, EXPECTED))?.read::<bevy::prelude::Color>()?;
+        if seq.next().is_some() {
+            return Err(Error::invalid_length(value, 3, EXPECTED));
+        }
+        Ok(Shadow(egui::epaint::Shadow { extrusion, color: color_bevy_to_egui(color) }))
+    }
+}
+
+impl ToUiconf for Shadow {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.array(|writer| {
+            writer.item(&self.0.extrusion);
+            writer.item(&color_egui_to_bevy(self.0.color));
+        });
+    }
+}
+
 //
 // Sense
 //
@@ -1601,6 +9539,21 @@ impl ReadUiconf for Sense {
     }
 }
 
+impl ToUiconf for Sense {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        if !self.0.click && !self.0.drag && !self.0.focusable {
+            writer.scalar("hover");
+            return;
+        }
+
+        writer.array(|writer| {
+            if self.0.click { writer.item("click"); }
+            if self.0.drag { writer.item("drag"); }
+            if self.0.focusable { writer.item("focusable"); }
+        });
+    }
+}
+
 //
 // Size
 //
@@ -1636,6 +9589,188 @@ impl<const ANY: u8> ReadUiconf for Size<ANY> {
     }
 }
 
+impl<const ANY: u8> ToUiconf for Size<ANY> {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.array(|writer| {
+            for value in [self.0.x, self.0.y] {
+                if ANY == SIZE_ANY_IS_INF && value == f32::INFINITY {
+                    writer.item(&AnyOrF32(None));
+                } else {
+                    writer.item(&value);
+                }
+            }
+        });
+    }
+}
+
+//
+// Rect
+//
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rect(pub egui::Rect);
+
+impl ReadUiconf for Rect {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        const EXPECTED: &str = "{ left top right bottom }";
+        let mut seq = value.read_array()?;
+        let left   = seq.next().ok_or_else(|| Error::invalid_length(value, 0, EXPECTED))?.read::<f32>()?;
+        let top    = seq.next().ok_or_else(|| Error::invalid_length(value, 1, EXPECTED))?.read::<f32>()?;
+        let right  = seq.next().ok_or_else(|| Error::invalid_length(value, 2, EXPECTED))?.read::<f32>()?;
+        let bottom = seq.next().ok_or_else(|| Error::invalid_length(value, 3, EXPECTED))?.read::<f32>()?;
+        if seq.next().is_some() {
+            return Err(Error::invalid_length(value, 5, EXPECTED));
+        }
+        Ok(Rect(egui::Rect::from_min_max(egui::pos2(left, top), egui::pos2(right, bottom))))
+    }
+}
+
+impl ToUiconf for Rect {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.array(|writer| {
+            writer.item(&self.0.min.x);
+            writer.item(&self.0.min.y);
+            writer.item(&self.0.max.x);
+            writer.item(&self.0.max.y);
+        });
+    }
+}
+
+//
+// Vec2
+//
+
+impl ReadUiconf for bevy::math::Vec2 {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        let size = value.read::<Size<{ SIZE_ANY_DISALLOWED }>>()?.0;
+        Ok(bevy::math::Vec2::new(size.x, size.y))
+    }
+}
+
+impl ToUiconf for bevy::math::Vec2 {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        Size::<{ SIZE_ANY_DISALLOWED }>(egui::vec2(self.x, self.y)).write_uiconf(writer);
+    }
+}
+
+//
+// Condition
+//
+
+// Either a bool binding, or a comparison like `{ @state == Paused }`
+#[derive(Debug)]
+pub enum Condition {
+    Binding(Binding<bool>),
+    Compare {
+        binding: BindingRef<dyn std::any::Any + Send + Sync>,
+        op: CompareOp,
+        value: String,
+    },
+}
+
+impl Condition {
+    // enums are compared by variant name, strings and bools by value, numbers numerically
+    fn compare(field: &dyn Reflect, op: CompareOp, expected: &str) -> anyhow::Result<bool> {
+        let ordering = if let bevy::reflect::ReflectRef::Enum(value) = field.reflect_ref() {
+            value.variant_name().cmp(expected)
+        } else if let Some(value) = field.downcast_ref::<String>() {
+            value.as_str().cmp(expected)
+        } else if let Some(value) = field.downcast_ref::<bool>() {
+            let expected = match expected {
+                "yes" | "true" => true,
+                "no" | "false" => false,
+                _ => anyhow::bail!("cannot compare bool with `{}`", expected),
+            };
+            value.cmp(&expected)
+        } else if let Some(value) = reflect_as_f64(field) {
+            let expected = expected.parse::<f64>().map_err(|_| anyhow::anyhow!("cannot compare number with `{}`", expected))?;
+            value.partial_cmp(&expected).ok_or_else(|| anyhow::anyhow!("cannot compare NaN"))?
+        } else {
+            anyhow::bail!(
+                "cannot compare {}",
+                field.get_represented_type_info().map(|info| info.type_path()).unwrap_or("<unknown>"),
+            );
+        };
+
+        Ok(match op {
+            CompareOp::Equal        => ordering.is_eq(),
+            CompareOp::NotEqual     => ordering.is_ne(),
+            CompareOp::Less         => ordering.is_lt(),
+            CompareOp::LessEqual    => ordering.is_le(),
+            CompareOp::Greater      => ordering.is_gt(),
+            CompareOp::GreaterEqual => ordering.is_ge(),
+        })
+    }
+}
+
+impl ResolveBinding for Condition {
+    type Item = bool;
+
+    fn resolve(&self, data: &dyn DataSource) -> anyhow::Result<Self::Item> {
+        match self {
+            Condition::Binding(binding) => binding.resolve(data),
+            Condition::Compare { binding, op, value } => {
+                let field = binding.resolve_reflect(data)?;
+                Condition::compare(field, *op, value)
+            }
+        }
+    }
+}
+
+impl ReadUiconf for Condition {
+    fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        if value.is_scalar() {
+            return Ok(Condition::Binding(value.read()?));
+        }
+
+        let (key, op, expected) = value.read_comparison()?;
+        let op = match op {
+            jomini::text::Operator::Exact            => CompareOp::Equal,
+            jomini::text::Operator::NotEqual         => CompareOp::NotEqual,
+            jomini::text::Operator::LessThan         => CompareOp::Less,
+            jomini::text::Operator::LessThanEqual    => CompareOp::LessEqual,
+            jomini::text::Operator::GreaterThan      => CompareOp::Greater,
+            jomini::text::Operator::GreaterThanEqual => CompareOp::GreaterEqual,
+            op => return Err(Error::unexpected_operator(value, op)),
+        };
+
+        Ok(Condition::Compare {
+            binding: BindingRef::parse(&expected, &key)?,
+            op,
+            value: expected.read_string()?,
+        })
+    }
+}
+
+impl ToUiconf for Condition {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        match self {
+            Condition::Binding(binding) => binding.write_uiconf(writer),
+            Condition::Compare { binding, op, value } => writer.array(|writer| {
+                writer.item(binding);
+                writer.item(op);
+                writer.item(value);
+            }),
+        }
+    }
+}
+
+#[derive(Display, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    #[strum(serialize = "==")] Equal,
+    #[strum(serialize = "!=")] NotEqual,
+    #[strum(serialize = "<")]  Less,
+    #[strum(serialize = "<=")] LessEqual,
+    #[strum(serialize = ">")]  Greater,
+    #[strum(serialize = ">=")] GreaterEqual,
+}
+
+impl ToUiconf for CompareOp {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.scalar(self);
+    }
+}
+
 //
 // AnyOrF32
 //
@@ -1653,6 +9788,15 @@ impl ReadUiconf for AnyOrF32 {
     }
 }
 
+impl ToUiconf for AnyOrF32 {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        match self.0 {
+            Some(value) => writer.scalar(value),
+            None => writer.scalar("any"),
+        }
+    }
+}
+
 //
 // Empty
 //
@@ -1663,6 +9807,8 @@ pub struct Empty;
 
 impl ReadUiconf for Empty {
     fn read_uiconf(value: &Reader) -> Result<Self, Error> {
+        if value.is_flag() { return Ok(Empty); }
+
         match value.token() {
             TextToken::Array { .. } => Ok(Empty),
             TextToken::Object { .. } => Ok(Empty),
@@ -1671,6 +9817,22 @@ impl ReadUiconf for Empty {
     }
 }
 
+impl ToUiconf for Empty {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|_| {});
+    }
+}
+
+// flags that used to be written as `auto_sized = {}` also accept `yes`/`no` and bare `auto_sized`
+fn read_flag(value: &Reader) -> Result<bool, Error> {
+    if value.is_scalar() || value.is_flag() {
+        value.read()
+    } else {
+        value.read::<Empty>()?;
+        Ok(true)
+    }
+}
+
 //
 // Conversions
 //
@@ -1686,3 +9848,207 @@ fn color_bevy_to_egui(color: bevy::prelude::Color) -> egui::Color32 {
     let a = (color.a() * 255.) as u8;
     egui::Color32::from_rgba_premultiplied(r, g, b, a)
 }
+
+// egui can't change opacity of painted shapes, so all colors from the style are faded instead,
+// colors set explicitly on widgets (e.g. text `color`) aren't affected
+fn fade_visuals(visuals: &mut egui::Visuals, opacity: f32) {
+    let fade = |color: &mut egui::Color32| *color = color.gamma_multiply(opacity);
+
+    for widget in [
+        &mut visuals.widgets.noninteractive,
+        &mut visuals.widgets.inactive,
+        &mut visuals.widgets.hovered,
+        &mut visuals.widgets.active,
+        &mut visuals.widgets.open,
+    ] {
+        fade(&mut widget.bg_fill);
+        fade(&mut widget.weak_bg_fill);
+        fade(&mut widget.bg_stroke.color);
+        fade(&mut widget.fg_stroke.color);
+    }
+
+    if let Some(color) = &mut visuals.override_text_color { fade(color); }
+    fade(&mut visuals.selection.bg_fill);
+    fade(&mut visuals.selection.stroke.color);
+    fade(&mut visuals.hyperlink_color);
+    fade(&mut visuals.faint_bg_color);
+    fade(&mut visuals.extreme_bg_color);
+    fade(&mut visuals.code_bg_color);
+    fade(&mut visuals.warn_fg_color);
+    fade(&mut visuals.error_fg_color);
+    fade(&mut visuals.window_fill);
+    fade(&mut visuals.window_stroke.color);
+    fade(&mut visuals.window_shadow.color);
+    fade(&mut visuals.panel_fill);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::format::Format;
+
+    fn read(source: &str) -> Result<Root, Error> {
+        Root::read(source.as_bytes(), "test.gui", LoadingMode::Strict, &UiconfWidgetRegistry::default())
+    }
+
+    #[test]
+    fn error_location() {
+        let source = "window = {\n    title = \"Menu\"\n    unknown_field = 1\n}\n";
+        let Err(Error::UnknownField { field, at, .. }) = read(source) else {
+            panic!("expected unknown field error");
+        };
+        assert_eq!(field, "unknown_field");
+        assert_eq!((at.file.as_str(), at.line, at.column), ("test.gui", 3, 5));
+        assert_eq!(at.snippet, "    unknown_field = 1");
+    }
+
+    #[test]
+    fn syntax_error_location() {
+        let source = "window = {\n    title = \"Menu\"\n}\n}\n";
+        let Err(Error::SyntaxError { at, .. }) = read(source) else {
+            panic!("expected syntax error");
+        };
+        assert_eq!((at.line, at.column), (4, 1));
+    }
+
+    #[test]
+    fn class_merge() {
+        let source = r#"
+            classes = {
+                base = { resizable = no collapsible = no }
+                open = { class = base collapsible = yes }
+            }
+            window = {
+                title = "Menu"
+                class = open
+                resizable = yes
+            }
+        "#;
+        let written = read(source).unwrap().write();
+        assert!(written.contains("resizable = yes"), "{}", written);
+        assert!(written.contains("collapsible = yes"), "{}", written);
+        assert!(!written.contains("= no"), "{}", written);
+    }
+
+    #[test]
+    fn class_precedence() {
+        let source = r#"
+            classes = {
+                a = { resizable = no vscroll = yes }
+                b = { resizable = yes }
+            }
+            window = {
+                title = "Menu"
+                class = { a b }
+                vscroll = no
+            }
+        "#;
+        let root = read(source).unwrap();
+        let mut props = root.window.props.iter().filter_map(|prop| match prop {
+            WindowProperty::Resizable(Binding::Value(resizable)) => Some(("resizable", *resizable)),
+            WindowProperty::VScroll(vscroll) => Some(("vscroll", *vscroll)),
+            _ => None,
+        }).collect::<Vec<_>>();
+        props.sort();
+        assert_eq!(props, [("resizable", true), ("vscroll", false)]);
+    }
+
+    #[test]
+    fn justify_gaps() {
+        assert_eq!(Justify::SpaceBetween.gaps(90., 4), (0., 30.));
+        assert_eq!(Justify::SpaceBetween.gaps(90., 1), (0., 0.));
+        assert_eq!(Justify::SpaceAround.gaps(80., 4), (10., 20.));
+        assert_eq!(Justify::SpaceEvenly.gaps(100., 4), (20., 20.));
+        assert_eq!(Justify::SpaceEvenly.gaps(100., 0), (0., 0.));
+    }
+
+    #[test]
+    fn write_round_trip() {
+        let source = r#"
+            window = {
+                title = "Menu"
+                default_size = { 300 200 }
+                resizable = no
+                label = "Hello"
+                button = {
+                    id = start
+                    text = "Start"
+                    small = yes
+                }
+            }
+        "#;
+        let written = read(source).unwrap().write();
+        assert_eq!(read(&written).unwrap().write(), written);
+    }
+
+    #[test]
+    fn bool_shortcuts() {
+        let read_props = |props: &str| {
+            let root = read(&format!("window = {{ title = \"Menu\" {} }}", props)).unwrap();
+            root.window.props.iter().map(|prop| match prop {
+                WindowProperty::AutoSized(auto_sized) => *auto_sized,
+                WindowProperty::Resizable(Binding::Value(resizable)) => *resizable,
+                _ => panic!("unexpected property"),
+            }).collect::<Vec<_>>()
+        };
+
+        assert_eq!(read_props("auto_sized = {} resizable = yes"), [true, true]);
+        assert_eq!(read_props("auto_sized = false resizable = true"), [false, true]);
+        assert_eq!(read_props("auto_sized resizable"), [true, true]);
+    }
+
+    #[test]
+    fn flags_after_content() {
+        let root = read("window = { grid = { label = \"Name\" striped } }").unwrap();
+        let ContentWidget::Grid(grid) = &root.window.content.0[0] else { panic!("expected grid"); };
+        assert!(grid.striped);
+        assert_eq!(grid.content.0.len(), 1);
+
+        let Err(Error::MisplacedFlag { flag, .. }) = read("window = { grid = { striped label = \"Name\" } }") else {
+            panic!("expected misplaced flag error");
+        };
+        assert_eq!(flag, "striped");
+    }
+
+    #[test]
+    fn place_coords() {
+        let root = read("window = { place = { pos = { 50% 20 } size = { any 25.5% } label = \"Hi\" } }").unwrap();
+        let ContentWidget::Place(place) = &root.window.content.0[0] else { panic!("expected place"); };
+        assert_eq!(place.pos, [PlaceCoord::Percent(50.), PlaceCoord::Points(20.)]);
+        assert_eq!(place.size, [PlaceCoord::Any, PlaceCoord::Percent(25.5)]);
+        assert_eq!(place.pos[0].resolve(300.), Some(150.));
+        assert_eq!(place.size[0].resolve(300.), None);
+
+        let Err(Error::Custom { message, .. }) = read("window = { place = { pos = { any 0 } label = \"Hi\" } }") else {
+            panic!("expected `any` to be rejected in pos");
+        };
+        assert_eq!(message, "`any` is only allowed in `size`");
+    }
+
+    #[test]
+    fn columns_require_column() {
+        let root = read("window = { columns = { column = { label = \"A\" } column = { label = \"B\" } } }").unwrap();
+        let ContentWidget::Columns(columns) = &root.window.content.0[0] else { panic!("expected columns"); };
+        assert_eq!(columns.columns.len(), 2);
+
+        let Err(Error::MissingField { field, .. }) = read("window = { columns = { min_width = 100 } }") else {
+            panic!("expected missing field error");
+        };
+        assert_eq!(field, "column");
+    }
+
+    #[test]
+    fn converted_error_location() {
+        let json = "{\n  \"window\": {\n    \"title\": \"Menu\",\n    \"unknown_field\": 1\n  }\n}\n";
+        let ron = "(\n  window: (\n    title: \"Menu\",\n    unknown_field: 1,\n  ),\n)\n";
+
+        for (format, file, source) in [(Format::Json, "test.gui.json", json), (Format::Ron, "test.gui.ron", ron)] {
+            let converted = format.to_gui(source.as_bytes(), file).unwrap();
+            let result = Root::read_mapped(&converted.data, converted.source_map.as_ref(), file, LoadingMode::Strict, &UiconfWidgetRegistry::default());
+            let Err(Error::UnknownField { at, .. }) = result else {
+                panic!("expected unknown field error in {}", file);
+            };
+            assert_eq!((at.file.as_str(), at.line, at.column), (file, 4, 5));
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::egui;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NavAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    // clicks the focused widget
+    Activate,
+}
+
+// Moves egui focus between interactive widgets of the topmost window with arrow keys or gamepad,
+// and clicks the focused widget. Insert it as a resource to enable:
+//
+//     app.insert_resource(UiconfNavigation::default());
+//
+// Focus moves to the closest widget in that direction, when nothing is focused the first widget is.
+#[derive(Resource, Debug, Clone)]
+pub struct UiconfNavigation {
+    pub keys: Vec<(KeyCode, NavAction)>,
+    pub gamepad_buttons: Vec<(GamepadButtonType, NavAction)>,
+    // left stick moves focus once when tilted further than this, set above 1 to disable
+    pub stick_threshold: f32,
+    // draw selection stroke around the focused widget, egui only shows focus of some widgets
+    pub highlight: bool,
+}
+
+impl Default for UiconfNavigation {
+    fn default() -> Self {
+        // Enter and Space already click focused widgets in egui
+        Self {
+            keys: vec![
+                (KeyCode::Up, NavAction::Up),
+                (KeyCode::Down, NavAction::Down),
+                (KeyCode::Left, NavAction::Left),
+                (KeyCode::Right, NavAction::Right),
+            ],
+            gamepad_buttons: vec![
+                (GamepadButtonType::DPadUp, NavAction::Up),
+                (GamepadButtonType::DPadDown, NavAction::Down),
+                (GamepadButtonType::DPadLeft, NavAction::Left),
+                (GamepadButtonType::DPadRight, NavAction::Right),
+                (GamepadButtonType::South, NavAction::Activate),
+            ],
+            stick_threshold: 0.5,
+            highlight: true,
+        }
+    }
+}
+
+// Stored in egui context, widgets shown in this frame are collected here and used in the next one.
+#[derive(Clone, Default)]
+struct NavState {
+    widgets: Arc<Mutex<Vec<(egui::Id, egui::Rect, egui::LayerId)>>>,
+    activate: bool,
+    highlight: bool,
+}
+
+impl NavState {
+    fn egui_id() -> egui::Id {
+        egui::Id::new("bevy_uiconf_egui::navigation")
+    }
+}
+
+// called for every widget with response, does nothing unless navigation is enabled
+pub(crate) fn register(response: &mut egui::Response) {
+    if !response.enabled || !(response.sense.click || response.sense.focusable) { return; }
+
+    let Some(state) = response.ctx.data(|d| d.get_temp::<NavState>(NavState::egui_id())) else { return; };
+    state.widgets.lock().unwrap().push((response.id, response.rect, response.layer_id));
+
+    if !response.has_focus() { return; }
+
+    if state.activate {
+        response.clicked[egui::PointerButton::Primary as usize] = true;
+    }
+    if state.highlight {
+        let visuals = response.ctx.style().visuals.clone();
+        response.ctx.layer_painter(response.layer_id)
+            .rect_stroke(response.rect.expand(2.), visuals.widgets.hovered.rounding, visuals.selection.stroke);
+    }
+}
+
+pub fn navigate(
+    navigation: Option<Res<UiconfNavigation>>,
+    keys: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut stick: Local<Option<NavAction>>,
+    mut egui_contexts: Query<&mut bevy_egui::EguiContext>,
+) {
+    let Some(navigation) = navigation else {
+        for mut ctx in egui_contexts.iter_mut() {
+            ctx.get_mut().data_mut(|d| d.remove::<NavState>(NavState::egui_id()));
+        }
+        return;
+    };
+
+    let mut key_actions = vec![];
+    for (key, action) in navigation.keys.iter() {
+        if keys.just_pressed(*key) { key_actions.push(*action); }
+    }
+    let mut actions = vec![];
+    for gamepad in gamepads.iter() {
+        for (button, action) in navigation.gamepad_buttons.iter() {
+            if buttons.just_pressed(GamepadButton::new(gamepad, *button)) { actions.push(*action); }
+        }
+    }
+
+    // stick only moves focus when it's tilted, and needs to return to center before moving again
+    let tilt = gamepads.iter().find_map(|gamepad| {
+        let x = axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX)).unwrap_or(0.);
+        let y = axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY)).unwrap_or(0.);
+        if x.abs().max(y.abs()) < navigation.stick_threshold {
+            None
+        } else if x.abs() > y.abs() {
+            Some(if x > 0. { NavAction::Right } else { NavAction::Left })
+        } else {
+            Some(if y > 0. { NavAction::Up } else { NavAction::Down })
+        }
+    });
+    if tilt != *stick {
+        actions.extend(tilt);
+        *stick = tilt;
+    }
+
+    for mut ctx in egui_contexts.iter_mut() {
+        let ctx = ctx.get_mut();
+        let previous = ctx.data(|d| d.get_temp::<NavState>(NavState::egui_id())).unwrap_or_default();
+
+        // arrow keys move the cursor while a text field is focused
+        let mut actions = actions.clone();
+        if !ctx.wants_keyboard_input() {
+            actions.extend(key_actions.iter().copied());
+        }
+
+        for action in actions.iter() {
+            let direction = match action {
+                NavAction::Up       => egui::vec2(0., -1.),
+                NavAction::Down     => egui::vec2(0., 1.),
+                NavAction::Left     => egui::vec2(-1., 0.),
+                NavAction::Right    => egui::vec2(1., 0.),
+                NavAction::Activate => continue,
+            };
+            if let Some(id) = next_focus(ctx, &previous.widgets.lock().unwrap(), direction) {
+                ctx.memory_mut(|mem| mem.request_focus(id));
+            }
+        }
+
+        ctx.data_mut(|d| d.insert_temp(NavState::egui_id(), NavState {
+            widgets: Default::default(),
+            activate: actions.contains(&NavAction::Activate),
+            highlight: navigation.highlight,
+        }));
+    }
+}
+
+// closest widget in the direction, widgets straight ahead are preferred over ones to the side
+fn next_focus(ctx: &egui::Context, widgets: &[(egui::Id, egui::Rect, egui::LayerId)], direction: egui::Vec2) -> Option<egui::Id> {
+    let focused = ctx.memory(|mem| mem.focus()).and_then(|focus| widgets.iter().find(|(id, ..)| *id == focus));
+
+    let Some(&(focused, from, layer)) = focused else {
+        // nothing is focused yet, so the first widget of the topmost window is
+        let layer = ctx.memory(|mem| {
+            mem.layer_ids().filter(|layer| widgets.iter().any(|(_, _, widget)| widget == layer)).last()
+        })?;
+        return widgets.iter().find(|(_, _, widget)| *widget == layer).map(|(id, ..)| *id);
+    };
+
+    widgets.iter()
+        .filter(|(id, _, widget)| *id != focused && *widget == layer)
+        .filter_map(|(id, rect, _)| {
+            let delta = rect.center() - from.center();
+            let along = delta.dot(direction);
+            if along <= 0. { return None; }
+            let across = (delta - direction * along).length();
+            Some((along + across * 2., *id))
+        })
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, id)| id)
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use bevy::app::AppExit;
+use bevy::asset::io::file::{FileAssetReader, FileAssetWriter};
+use bevy::asset::io::{AssetReader, AssetReaderError, AssetWriter};
+use bevy::asset::{AsyncReadExt, AsyncWriteExt};
+use bevy::prelude::*;
+use bevy::tasks::IoTaskPool;
+
+#[cfg(feature = "egui_dock")]
+use crate::dock::UiconfDock;
+use crate::shared::EguiShared;
+
+// Saves position, size and collapsed state of windows to a file, and restores them on startup:
+//
+//     app.add_plugins(UiconfPlugin { persistence: Some(UiconfPersistence::new("ui_layout.ron")), ..default() });
+//
+// Windows are identified by asset path and explicit `id`, so their layout is kept when the file changes.
+// Windows placed by code (e.g. nameplates) aren't saved, and layouts of windows that weren't shown
+// during the session are dropped from the file.
+// With `egui_dock` feature, arrangement of dock tabs is saved too, see `UiconfDock`.
+#[derive(Debug, Clone)]
+pub struct UiconfPersistence {
+    // relative to the directory assets are loaded from, see `FileAssetReader::get_base_path`
+    pub path: PathBuf,
+    // changes are written at most this often, and once more when app exits
+    pub save_interval: f32,
+}
+
+impl UiconfPersistence {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), save_interval: 5. }
+    }
+
+    // `ui_layout.dock.ron` next to `ui_layout.ron`, see `UiconfDock`
+    #[cfg(feature = "egui_dock")]
+    pub fn dock_path(&self) -> PathBuf {
+        self.path.with_extension("dock.ron")
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct WindowLayout {
+    pub pos: [f32; 2],
+    // size of window content, unknown if window was collapsed all the time
+    pub size: Option<[f32; 2]>,
+    pub collapsed: bool,
+}
+
+// Layouts of all windows shown so far, including ones restored from file.
+#[derive(Resource, Clone)]
+pub struct UiconfLayouts {
+    inner: Arc<Mutex<LayoutsInner>>,
+    persistence: UiconfPersistence,
+}
+
+#[derive(Default)]
+struct LayoutsInner {
+    // read from file, used until the window is shown
+    saved: HashMap<String, WindowLayout>,
+    // windows shown this session, only these are written back
+    windows: HashMap<String, WindowLayout>,
+    changed: bool,
+}
+
+impl UiconfLayouts {
+    // Missing or broken file is the same as no saved layout. It's read once on startup,
+    // before any window is shown, so blocking here doesn't stall a frame.
+    pub(crate) fn load(persistence: UiconfPersistence) -> Self {
+        let saved = read_file(&persistence.path).map_or_else(HashMap::new, |text| {
+            ron::from_str(&text).unwrap_or_else(|err| {
+                bevy::log::warn!("failed to read window layout from {}: {}", persistence.path.display(), err);
+                HashMap::new()
+            })
+        });
+
+        Self {
+            inner: Arc::new(Mutex::new(LayoutsInner { saved, ..default() })),
+            persistence,
+        }
+    }
+
+    // `key` is asset path, followed by `#id` if window has explicit `id`
+    pub fn window(&self, key: &str) -> Option<WindowLayout> {
+        let inner = self.inner.lock().unwrap();
+        inner.windows.get(key).or_else(|| inner.saved.get(key)).copied()
+    }
+
+    pub(crate) fn record(&self, key: &str, layout: WindowLayout) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.windows.get(key) != Some(&layout) {
+            inner.windows.insert(key.to_owned(), layout);
+            inner.changed = true;
+        }
+    }
+
+    // forgets all saved layouts, windows that are already shown keep their place
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.saved.clear();
+        inner.windows.clear();
+        inner.changed = true;
+    }
+
+    // written in background, so that a slow disk doesn't stall the frame, except when app is exiting
+    fn save(&self, in_background: bool) {
+        let mut inner = self.inner.lock().unwrap();
+        if !inner.changed { return; }
+        inner.changed = false;
+
+        let text = match ron::to_string(&inner.windows) {
+            Ok(text) => text,
+            Err(err) => {
+                bevy::log::warn!("failed to serialize window layout: {}", err);
+                return;
+            }
+        };
+
+        write_file(self.persistence.path.clone(), text, in_background);
+    }
+
+    // arrangement of dock tabs is saved separately, only if it changed
+    #[cfg(feature = "egui_dock")]
+    fn save_dock(&self, dock: &UiconfDock, in_background: bool) {
+        if let Some(text) = dock.changed_tabs() {
+            write_file(self.persistence.dock_path(), text, in_background);
+        }
+    }
+}
+
+// `None` if the file is missing or can't be read
+pub(crate) fn read_file(path: &Path) -> Option<String> {
+    let read = async {
+        let mut text = String::new();
+        let reader = FileAssetReader::new("");
+        let mut reader = reader.read(path).await?;
+        reader.read_to_string(&mut text).await?;
+        Ok::<_, AssetReaderError>(text)
+    };
+    match bevy::tasks::block_on(read) {
+        Ok(text) => Some(text),
+        Err(AssetReaderError::NotFound(_)) => None,
+        Err(err) => {
+            bevy::log::warn!("failed to read {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+fn write_file(path: PathBuf, text: String, in_background: bool) {
+    let write = async move {
+        let write = async {
+            let writer = FileAssetWriter::new("");
+            let mut writer = writer.write(&path).await?;
+            writer.write_all(text.as_bytes()).await?;
+            writer.close().await?;
+            Ok::<_, bevy::asset::io::AssetWriterError>(())
+        };
+        if let Err(err) = write.await {
+            bevy::log::warn!("failed to write {}: {}", path.display(), err);
+        }
+    };
+    if in_background {
+        IoTaskPool::get().spawn(write).detach();
+    } else {
+        bevy::tasks::block_on(write);
+    }
+}
+
+impl EguiShared for UiconfLayouts {
+    const ID: &'static str = "bevy_uiconf_egui::layouts";
+}
+
+pub fn save_layouts(
+    layouts: Option<Res<UiconfLayouts>>,
+    #[cfg(feature = "egui_dock")] dock: Res<UiconfDock>,
+    time: Res<Time>,
+    mut exit: EventReader<AppExit>,
+    mut since_save: Local<f32>,
+) {
+    let Some(layouts) = layouts else { return; };
+
+    if exit.read().count() > 0 {
+        layouts.save(false);
+        #[cfg(feature = "egui_dock")]
+        layouts.save_dock(&dock, false);
+        return;
+    }
+
+    *since_save += time.delta_seconds();
+    if *since_save >= layouts.persistence.save_interval {
+        *since_save = 0.;
+        layouts.save(true);
+        #[cfg(feature = "egui_dock")]
+        layouts.save_dock(&dock, true);
+    }
+}
@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::egui;
+
+// Whether uiconf windows took pointer or keyboard input in this frame, so that gameplay systems
+// don't react to clicks that were meant for the UI:
+//
+//     app.add_systems(Update, pick_units.run_if(uiconf_pointer_free));
+//     app.add_systems(Update, move_camera.run_if(uiconf_keyboard_free));
+//
+// Updated in `PostUpdate`, after all windows are shown, so systems in `Update` see the state of
+// the previous frame. Other egui windows of the app aren't taken into account.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UiconfPointerState {
+    // pointer is over a uiconf window or a docked panel, in any egui context
+    pub over_window: bool,
+    // text field or another widget has keyboard focus, egui doesn't tell which window it's in,
+    // so it's only checked in contexts uiconf windows are shown in
+    pub keyboard_captured: bool,
+    // text field of a uiconf window has keyboard focus, e.g. to suspend hotkeys while player is typing
+    pub text_input_focused: bool,
+}
+
+// Areas occupied by uiconf windows in this frame, recorded as they're shown.
+#[derive(Clone, Default)]
+pub(crate) struct ShownAreas(Vec<(egui::LayerId, egui::Rect)>);
+
+impl ShownAreas {
+    fn egui_id() -> egui::Id {
+        egui::Id::new("bevy_uiconf_egui::shown_areas")
+    }
+
+    pub(crate) fn record(ctx: &egui::Context, layer: egui::LayerId, rect: egui::Rect) {
+        ctx.data_mut(|d| d.get_temp_mut_or_default::<Self>(Self::egui_id()).0.push((layer, rect)));
+    }
+
+    fn take(ctx: &egui::Context) -> Self {
+        ctx.data_mut(|d| std::mem::take(d.get_temp_mut_or_default::<Self>(Self::egui_id())))
+    }
+
+    // pointer has to be over the area itself, not over another window on top of it
+    fn contain(&self, ctx: &egui::Context, pos: egui::Pos2) -> bool {
+        let layer_at = ctx.layer_id_at(pos);
+        self.0.iter().any(|(layer, rect)| rect.contains(pos) && layer_at == Some(*layer))
+    }
+}
+
+// Text field of a uiconf window that has keyboard focus in this frame, recorded as it's shown.
+#[derive(Clone, Copy)]
+pub(crate) struct FocusedTextInput(egui::Rect);
+
+impl FocusedTextInput {
+    fn egui_id() -> egui::Id {
+        egui::Id::new("bevy_uiconf_egui::focused_text_input")
+    }
+
+    pub(crate) fn record(response: &egui::Response) {
+        if response.has_focus() {
+            response.ctx.data_mut(|d| d.insert_temp(Self::egui_id(), Self(response.rect)));
+        }
+    }
+
+    fn take(ctx: &egui::Context) -> Option<Self> {
+        ctx.data_mut(|d| {
+            let focused = d.get_temp::<Self>(Self::egui_id());
+            d.remove::<Self>(Self::egui_id());
+            focused
+        })
+    }
+}
+
+// IME is turned on for bevy windows while a uiconf text field is focused, so that CJK input works,
+// and turned off after that, unless it was enabled by the app itself
+pub fn update_pointer_state(
+    mut state: ResMut<UiconfPointerState>,
+    mut egui_contexts: Query<(Entity, &mut bevy_egui::EguiContext, Option<&mut Window>)>,
+    mut ime_enabled: Local<HashSet<Entity>>,
+) {
+    let mut new_state = UiconfPointerState::default();
+
+    for (entity, mut ctx, window) in egui_contexts.iter_mut() {
+        let ctx = ctx.get_mut();
+        let focused = FocusedTextInput::take(ctx);
+        new_state.text_input_focused |= focused.is_some();
+
+        if let Some(mut window) = window {
+            match focused {
+                Some(FocusedTextInput(rect)) => {
+                    // candidate box is placed under the text cursor, in logical pixels of the window
+                    let x = ctx.output(|output| output.text_cursor_pos).map_or(rect.left(), |pos| pos.x);
+                    let scale = ctx.pixels_per_point() / window.scale_factor() as f32;
+                    let position = Vec2::new(x, rect.bottom()) * scale;
+                    if !window.ime_enabled {
+                        window.ime_enabled = true;
+                        ime_enabled.insert(entity);
+                    }
+                    if window.ime_position != position {
+                        window.ime_position = position;
+                    }
+                }
+                None => {
+                    if ime_enabled.remove(&entity) {
+                        window.ime_enabled = false;
+                    }
+                }
+            }
+        }
+
+        let areas = ShownAreas::take(ctx);
+        if areas.0.is_empty() { continue; }
+
+        if let Some(pos) = ctx.pointer_latest_pos() {
+            new_state.over_window |= areas.contain(ctx, pos);
+        }
+        new_state.keyboard_captured |= ctx.wants_keyboard_input();
+    }
+
+    // written only when changed, so that `state.is_changed()` is meaningful
+    state.set_if_neq(new_state);
+}
+
+// run condition for systems that handle clicks in the game world
+pub fn uiconf_pointer_free(state: Res<UiconfPointerState>) -> bool {
+    !state.over_window
+}
+
+// run condition for systems that handle keyboard, e.g. camera movement with WASD
+pub fn uiconf_keyboard_free(state: Res<UiconfPointerState>) -> bool {
+    !state.keyboard_captured
+}
+
+// run condition for hotkeys that shouldn't fire while player is typing into uiconf windows
+pub fn uiconf_not_typing(state: Res<UiconfPointerState>) -> bool {
+    !state.text_input_focused
+}
@@ -1,90 +1,184 @@
+use std::any::TypeId;
 use std::sync::atomic::AtomicBool;
+use std::sync::RwLock;
 
 use anyhow::{anyhow, Context};
-use bevy::reflect::{Reflect, ReflectMut, ReflectRef, List};
+use bevy::reflect::{Enum, List, Reflect, ReflectMut, ReflectRef, Struct};
 use jomini::TextToken;
 use smol_str::SmolStr;
 
-use super::data_model::{ResolveBinding, ResolveBindingRef};
+use super::data_model::{DataSource, ResolveBinding, ResolveBindingRef};
 use super::error::Error;
 use super::{reader, ReadUiconf};
+use crate::diagnostics::{self, DiagnosticKind};
+use crate::model::Condition;
+use crate::writer::writer::Writer;
+use crate::writer::ToUiconf;
 
+// Returned when binding points to `Option` field that is `None`,
+// widgets may show a fallback or skip rendering entirely.
+#[derive(Debug, thiserror::Error)]
+#[error("value is None")]
+pub struct NoneValue;
+
+pub(crate) fn is_option(value: &dyn Enum) -> bool {
+    value.reflect_type_path().starts_with("core::option::Option<")
+}
 
 #[derive(Debug)]
 pub struct BindingRef<T: ?Sized> {
     name: SmolStr,
     warned: AtomicBool,
+    // struct type this binding was last resolved against, and index of the field in it
+    cache: RwLock<Option<(TypeId, usize)>>,
     _marker: std::marker::PhantomData<T>,
 }
 
 impl<T: ?Sized> BindingRef<T> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     fn change_type<U>(self) -> BindingRef<U> {
         BindingRef {
             name: self.name,
             warned: self.warned,
+            cache: self.cache,
             _marker: std::marker::PhantomData,
         }
     }
+
+    // Looking up fields by index is cheaper than by name, so index is remembered
+    // for each struct type. Dynamic structs share the same type, so they aren't cached.
+    fn field_index(&self, data: &dyn Reflect, value: &dyn Struct, name: &str) -> Option<usize> {
+        let type_id = data.as_any().type_id();
+        let cacheable = !data.is_dynamic();
+
+        if cacheable {
+            if let Some((cached_type, index)) = *self.cache.read().unwrap() {
+                if cached_type == type_id { return Some(index); }
+            }
+        }
+
+        let index = (0..value.field_len()).find(|&idx| value.name_at(idx) == Some(name))?;
+        if cacheable {
+            *self.cache.write().unwrap() = Some((type_id, index));
+        }
+        Some(index)
+    }
+
+    fn field<'data>(&self, data: &'data dyn DataSource) -> anyhow::Result<&'data dyn Reflect> {
+        let (data, name) = data.source(&self.name).context("namespace not found")?;
+        let ReflectRef::Struct(value) = data.reflect_ref() else {
+            return Err(anyhow!("expected struct"));
+        };
+        let index = self.field_index(data, value, name).context("key not found")?;
+        Ok(value.field_at(index).unwrap())
+    }
+
+    // each binding is only reported once, so that broken bindings don't flood the log every frame
+    fn report(&self, kind: DiagnosticKind, err: &anyhow::Error) {
+        if !self.warned.fetch_or(true, std::sync::atomic::Ordering::Relaxed) {
+            diagnostics::report(kind, format!("failed to resolve binding @{}: {}", self.name, err));
+        }
+    }
+
+    // should only be called after `field` succeeded on the same data
+    fn field_mut<'data>(&self, data: &'data mut dyn DataSource) -> &'data mut dyn Reflect {
+        let (data, name) = data.source_mut(&self.name).unwrap();
+        let index = self.cache.read().unwrap()
+            .filter(|(cached_type, _)| !data.is_dynamic() && *cached_type == data.as_any().type_id())
+            .map(|(_, index)| index);
+        let ReflectMut::Struct(value) = data.reflect_mut() else { unreachable!() };
+        match index {
+            Some(index) => value.field_at_mut(index).unwrap(),
+            None => value.field_mut(name).unwrap(),
+        }
+    }
 }
 
-impl<T: ?Sized> ReadUiconf for BindingRef<T> {
+impl<T: ?Sized + 'static> ReadUiconf for BindingRef<T> {
     fn read_uiconf(value: &reader::Reader) -> Result<Self, Error> {
         let TextToken::Unquoted(scalar) = value.token() else {
             return Err(Error::invalid_type(value, value.token_type(), "unquoted scalar"));
         };
 
-        let string = scalar.to_string();
+        BindingRef::parse(value, &scalar.to_string())
+    }
+}
+
+impl<T: ?Sized + 'static> BindingRef<T> {
+    // `@name` found anywhere in the file, `value` is used for error reporting
+    pub fn parse(value: &reader::Reader, string: &str) -> Result<Self, Error> {
         if let Some(reference) = string.strip_prefix('@') {
+            value.record_binding(reference, std::any::TypeId::of::<T>(), std::any::type_name::<T>());
             Ok(BindingRef {
                 name: reference.into(),
                 warned: AtomicBool::new(false),
+                cache: RwLock::new(None),
                 _marker: std::marker::PhantomData,
             })
         } else {
             Err(Error::invalid_value(
                 value,
-                &string,
+                string,
                 "@ref",
             ))
         }
     }
 }
 
+impl<T: ?Sized> ToUiconf for BindingRef<T> {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.scalar(format_args!("@{}", self.name));
+    }
+}
+
 impl<T: ?Sized> BindingRef<T> {
-    pub fn resolve_list_ref<'data>(
+    // field of any type, used when binding type is not known in advance
+    pub fn resolve_reflect<'data>(
         &'data self,
-        data: &'data dyn Reflect,
-    ) -> anyhow::Result<&'data dyn List> {
-        (|| -> anyhow::Result<&'data dyn List> {
-            let ReflectRef::Struct(value) = data.reflect_ref() else {
-                return Err(anyhow!("expected struct"));
-            };
-            let value = value.field(&self.name).context("key not found")?;
-
-            let ReflectRef::List(value) = value.reflect_ref() else {
-                return Err(anyhow!(
-                    "expected list, found {}",
-                    value.get_represented_type_info().map(|info| info.type_path()).unwrap_or("<unknown>")
-                ));
-            };
-            Ok(value)
-        })().map_err(|err| {
-            if !self.warned.fetch_or(true, std::sync::atomic::Ordering::Relaxed) {
-                bevy::log::warn!("failed to resolve binding @{}: {}", self.name, err);
-            }
+        data: &'data dyn DataSource,
+    ) -> anyhow::Result<&'data dyn Reflect> {
+        self.field(data).map_err(|err| {
+            self.report(DiagnosticKind::UnresolvedBinding, &err);
             err
         })
     }
 
+    pub fn resolve_reflect_mut<'data>(
+        &'data self,
+        data: &'data mut dyn DataSource,
+    ) -> anyhow::Result<&'data mut dyn Reflect> {
+        let _ = self.resolve_reflect(data)?;
+        Ok(self.field_mut(data))
+    }
+
+    pub fn resolve_list_ref<'data>(
+        &'data self,
+        data: &'data dyn DataSource,
+    ) -> anyhow::Result<&'data dyn List> {
+        let value = self.resolve_reflect(data)?;
+
+        let ReflectRef::List(list) = value.reflect_ref() else {
+            let err = anyhow!(
+                "expected list, found {}",
+                value.get_represented_type_info().map(|info| info.type_path()).unwrap_or("<unknown>")
+            );
+            self.report(DiagnosticKind::TypeMismatch, &err);
+            return Err(err);
+        };
+        Ok(list)
+    }
+
     pub fn resolve_list_mut<'data>(
         &'data self,
-        data: &'data mut dyn Reflect,
+        data: &'data mut dyn DataSource,
     ) -> anyhow::Result<&'data mut dyn List> {
         let _ = self.resolve_list_ref(data)?;
 
         // all errors should've been catched by `resolve_ref` above
-        let ReflectMut::Struct(value) = data.reflect_mut() else { unreachable!() };
-        let value = value.field_mut(&self.name).unwrap();
+        let value = self.field_mut(data);
 
         let ReflectMut::List(value) = value.reflect_mut() else { unreachable!() };
         Ok(value)
@@ -94,13 +188,18 @@ impl<T: ?Sized> BindingRef<T> {
 impl<T: Reflect> BindingRef<T> {
     pub fn resolve_ref<'data>(
         &'data self,
-        data: &'data dyn Reflect,
+        data: &'data dyn DataSource,
     ) -> anyhow::Result<&T> {
+        let mut value = self.resolve_reflect(data)?;
+
         (|| -> anyhow::Result<&'data T> {
-            let ReflectRef::Struct(value) = data.reflect_ref() else {
-                return Err(anyhow!("expected struct"));
-            };
-            let value = value.field(&self.name).context("key not found")?;
+            // `Option<T>` resolves into `T` if it's `Some`
+            if let ReflectRef::Enum(option) = value.reflect_ref() {
+                if !value.is::<T>() && is_option(option) {
+                    value = option.field_at(0).ok_or(NoneValue)?;
+                }
+            }
+
             value.downcast_ref::<T>().ok_or_else(||
                 anyhow!(
                     "expected type {}, found {}",
@@ -112,8 +211,8 @@ impl<T: Reflect> BindingRef<T> {
                 )
             )
         })().map_err(|err| {
-            if !self.warned.fetch_or(true, std::sync::atomic::Ordering::Relaxed) {
-                bevy::log::warn!("failed to resolve binding @{}: {}", self.name, err);
+            if !err.is::<NoneValue>() {
+                self.report(DiagnosticKind::TypeMismatch, &err);
             }
             err
         })
@@ -121,13 +220,16 @@ impl<T: Reflect> BindingRef<T> {
 
     pub fn resolve_mut<'data>(
         &'data self,
-        data: &'data mut dyn Reflect,
+        data: &'data mut dyn DataSource,
     ) -> anyhow::Result<&'data mut T> {
         let _ = self.resolve_ref(data)?;
 
         // all errors should've been catched by `resolve_ref` above
-        let ReflectMut::Struct(value) = data.reflect_mut() else { unreachable!() };
-        let value = value.field_mut(&self.name).unwrap();
+        let mut value = self.field_mut(data);
+        if !value.is::<T>() {
+            let ReflectMut::Enum(option) = value.reflect_mut() else { unreachable!() };
+            value = option.field_at_mut(0).unwrap();
+        }
         Ok(value.downcast_mut::<T>().unwrap())
     }
 }
@@ -136,19 +238,34 @@ impl<T: Reflect> BindingRef<T> {
 pub enum Binding<T> {
     Ref(BindingRef<T>),
     Value(T),
+    // `color = { if = @low_hp then = red else = white }`, evaluated every time value is resolved
+    If(Box<ConditionalBinding<T>>),
 }
 
 impl<T> Binding<T> {
-    pub fn map_value<U, F: FnOnce(T) -> U>(self, f: F) -> Binding<U> {
+    pub fn map_value<U, F: FnMut(T) -> U>(self, mut f: F) -> Binding<U> {
+        self.map_value_with(&mut f)
+    }
+
+    fn map_value_with<U>(self, f: &mut dyn FnMut(T) -> U) -> Binding<U> {
         match self {
             Binding::Ref(binding) => Binding::Ref(binding.change_type()),
             Binding::Value(value) => Binding::Value(f(value)),
+            Binding::If(binding) => Binding::If(Box::new(ConditionalBinding {
+                condition: binding.condition,
+                then: binding.then.map_value_with(f),
+                otherwise: binding.otherwise.map_value_with(f),
+            })),
         }
     }
 }
 
-impl<T: ReadUiconf> ReadUiconf for Binding<T> {
+impl<T: ReadUiconf + 'static> ReadUiconf for Binding<T> {
     fn read_uiconf(value: &reader::Reader) -> Result<Self, Error> {
+        if let Some(binding) = ConditionalBinding::try_read(value)? {
+            return Ok(Binding::If(Box::new(binding)));
+        }
+
         let binding = BindingRef::read_uiconf(value);
         if let Ok(binding) = binding {
             Ok(Binding::Ref(binding))
@@ -158,10 +275,79 @@ impl<T: ReadUiconf> ReadUiconf for Binding<T> {
     }
 }
 
+impl<T: ToUiconf> ToUiconf for Binding<T> {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        match self {
+            Binding::Ref(binding) => binding.write_uiconf(writer),
+            Binding::Value(value) => value.write_uiconf(writer),
+            Binding::If(binding) => binding.write_uiconf(writer),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ConditionalBinding<T> {
+    pub condition: Condition,
+    pub then: Binding<T>,
+    pub otherwise: Binding<T>,
+}
+
+impl<T: ReadUiconf + 'static> ConditionalBinding<T> {
+    const FIELDS: &'static [&'static str] = &["if", "then", "else"];
+
+    // objects without `if` key are regular values, e.g. `stroke = { width = 1 color = red }`
+    fn try_read(value: &reader::Reader) -> Result<Option<Self>, Error> {
+        if !matches!(value.token(), TextToken::Object { .. }) { return Ok(None); }
+
+        let fields = value.read_object()?.collect::<Vec<_>>();
+        if !fields.iter().any(|(key, _)| key == "if") { return Ok(None); }
+
+        let mut condition = None;
+        let mut then = None;
+        let mut otherwise = None;
+
+        for (key, value) in fields {
+            match &*key {
+                "if" => {
+                    if condition.is_some() { return Err(Error::duplicate_field(&value, "if")); }
+                    condition = Some(value.read()?);
+                }
+                "then" => {
+                    if then.is_some() { return Err(Error::duplicate_field(&value, "then")); }
+                    then = Some(value.read()?);
+                }
+                "else" => {
+                    if otherwise.is_some() { return Err(Error::duplicate_field(&value, "else")); }
+                    otherwise = Some(value.read()?);
+                }
+                str => {
+                    value.skip_unknown_field(str, Self::FIELDS)?;
+                }
+            }
+        }
+
+        Ok(Some(ConditionalBinding {
+            condition: condition.unwrap(),
+            then: then.ok_or_else(|| Error::missing_field(value, "then"))?,
+            otherwise: otherwise.ok_or_else(|| Error::missing_field(value, "else"))?,
+        }))
+    }
+}
+
+impl<T: ToUiconf> ToUiconf for ConditionalBinding<T> {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.object(|writer| {
+            writer.field("if", &self.condition);
+            writer.field("then", &self.then);
+            writer.field("else", &self.otherwise);
+        });
+    }
+}
+
 impl<T: Reflect + Copy> ResolveBinding for Binding<T> {
     type Item = T;
 
-    fn resolve(&self, data: &dyn Reflect) -> anyhow::Result<Self::Item> {
+    fn resolve(&self, data: &dyn DataSource) -> anyhow::Result<Self::Item> {
         self.resolve_ref(data).copied()
     }
 }
@@ -169,10 +355,17 @@ impl<T: Reflect + Copy> ResolveBinding for Binding<T> {
 impl<T: Reflect> ResolveBindingRef for Binding<T> {
     type Item = T;
 
-    fn resolve_ref<'data>(&'data self, data: &'data dyn Reflect) -> anyhow::Result<&'data Self::Item> {
+    fn resolve_ref<'data>(&'data self, data: &'data dyn DataSource) -> anyhow::Result<&'data Self::Item> {
         match self {
             Binding::Ref(binding) => binding.resolve_ref(data),
             Binding::Value(value) => Ok(value),
+            Binding::If(binding) => {
+                if binding.condition.resolve(data)? {
+                    binding.then.resolve_ref(data)
+                } else {
+                    binding.otherwise.resolve_ref(data)
+                }
+            }
         }
     }
 }
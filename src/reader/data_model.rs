@@ -1,20 +1,127 @@
 use bevy::reflect::prelude::*;
+use bevy::reflect::{GetTypeRegistration, TypeRegistry};
 
+// Anything bindings can be resolved against: a reflected struct, or `UiconfContext`.
+pub trait DataSource {
+    // struct containing field `name`, and name of the field within that struct
+    fn source<'a>(&self, name: &'a str) -> Option<(&dyn Reflect, &'a str)>;
+    fn source_mut<'a>(&mut self, name: &'a str) -> Option<(&mut dyn Reflect, &'a str)>;
+    // address of the underlying data, used in ids of widgets repeated for each list item
+    fn data_ptr(&self) -> *const ();
+}
+
+impl<T: Reflect> DataSource for T {
+    fn source<'a>(&self, name: &'a str) -> Option<(&dyn Reflect, &'a str)> {
+        Some((self, name))
+    }
+
+    fn source_mut<'a>(&mut self, name: &'a str) -> Option<(&mut dyn Reflect, &'a str)> {
+        Some((self, name))
+    }
+
+    fn data_ptr(&self) -> *const () {
+        self as *const T as *const ()
+    }
+}
+
+// Any numeric field as `f64`, used where exact type of the field doesn't matter.
+pub fn reflect_as_f64(field: &dyn Reflect) -> Option<f64> {
+    macro_rules! try_as {
+        ($($t:ty),*) => { $( if let Some(v) = field.downcast_ref::<$t>() { return Some(*v as f64); } )* };
+    }
+    try_as!(f32, f64, u8, i8, u16, i16, u32, i32, u64, i64, usize, isize);
+    None
+}
+
+// Writes into any numeric field, rounding for integer ones, returns `false` if field isn't numeric.
+pub fn reflect_set_f64(field: &mut dyn Reflect, value: f64) -> bool {
+    macro_rules! try_set {
+        ($value:expr; $($t:ty),*) => { $( if let Some(v) = field.downcast_mut::<$t>() { *v = $value as $t; return true; } )* };
+    }
+    try_set!(value; f32, f64);
+    try_set!(value.round(); u8, i8, u16, i16, u32, i32, u64, i64, usize, isize);
+    false
+}
+
+// Value that is only available as `dyn Reflect`, e.g. list item inside of `each`.
+pub struct ReflectSource<'r>(pub &'r mut dyn Reflect);
+
+impl DataSource for ReflectSource<'_> {
+    fn source<'a>(&self, name: &'a str) -> Option<(&dyn Reflect, &'a str)> {
+        Some((&*self.0, name))
+    }
+
+    fn source_mut<'a>(&mut self, name: &'a str) -> Option<(&mut dyn Reflect, &'a str)> {
+        Some((&mut *self.0, name))
+    }
+
+    fn data_ptr(&self) -> *const () {
+        &*self.0 as *const dyn Reflect as *const ()
+    }
+}
+
+// Several data models under their own namespaces, so state doesn't have to live
+// in a single resource:
+//
+//     let mut context = UiconfContext::new()
+//         .with("settings", settings.as_reflect_mut())
+//         .with("player", player.as_reflect_mut());
+//     window.show_with_context(&mut context, egui_contexts.ctx_mut());
+//
+// Bindings are prefixed with namespace, e.g. `@settings.volume` or `@player.hp`.
+#[derive(Default)]
+pub struct UiconfContext<'r> {
+    sources: Vec<(&'r str, &'r mut dyn Reflect)>,
+}
+
+impl<'r> UiconfContext<'r> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, namespace: &'r str, source: &'r mut dyn Reflect) -> Self {
+        self.sources.push((namespace, source));
+        self
+    }
+}
+
+impl DataSource for UiconfContext<'_> {
+    fn source<'a>(&self, name: &'a str) -> Option<(&dyn Reflect, &'a str)> {
+        let (namespace, name) = name.split_once('.')?;
+        let (_, source) = self.sources.iter().find(|(ns, _)| *ns == namespace)?;
+        Some((&**source, name))
+    }
+
+    fn source_mut<'a>(&mut self, name: &'a str) -> Option<(&mut dyn Reflect, &'a str)> {
+        let (namespace, name) = name.split_once('.')?;
+        let (_, source) = self.sources.iter_mut().find(|(ns, _)| *ns == namespace)?;
+        Some((&mut **source, name))
+    }
+
+    // context itself is usually created every frame, so first source is used instead
+    fn data_ptr(&self) -> *const () {
+        self.sources.first().map_or(std::ptr::null(), |(_, source)| &**source as *const dyn Reflect as *const ())
+    }
+}
+
+// Resolves into an owned value, implemented for `Copy` types only.
 pub trait ResolveBinding {
     type Item;
 
     fn resolve(
         &self,
-        data: &dyn Reflect,
+        data: &dyn DataSource,
     ) -> anyhow::Result<Self::Item>;
 }
 
+// Resolves into a reference to the data model, so strings and lists
+// can be read every frame without being cloned.
 pub trait ResolveBindingRef {
-    type Item;
+    type Item: ?Sized;
 
     fn resolve_ref<'data>(
         &'data self,
-        data: &'data dyn Reflect,
+        data: &'data dyn DataSource,
     ) -> anyhow::Result<&'data Self::Item>;
 }
 
@@ -37,3 +144,14 @@ impl Trigger {
         self.0 += 1;
     }
 }
+
+// Data model with a known set of fields, implemented with `#[derive(UiconfModel)]`.
+pub trait UiconfModel: Reflect + GetTypeRegistration {
+    // names of all fields that can be bound with `@name`
+    const FIELDS: &'static [&'static str];
+    // names of fields of type `Trigger`
+    const TRIGGERS: &'static [&'static str];
+
+    // registers this type and types of all its fields
+    fn register_types(registry: &mut TypeRegistry);
+}
@@ -1,41 +1,60 @@
 use thiserror::Error;
 
+pub use super::location::Location;
 use super::reader::Reader;
 
 #[derive(Error, Debug)]
 pub enum Error {
-    #[error("invalid type {actual}, expected {expected} (at {at})")]
-    InvalidType { actual: String, expected: String, at: String },
-    #[error("invalid value {actual}, expected {expected} (at {at})")]
-    InvalidValue { actual: String, expected: String, at: String },
-    #[error("invalid length {actual}, expected {expected} (at {at})")]
-    InvalidLength { actual: usize, expected: String, at: String },
-    #[error("unknown variant {actual}, expected one of {expected} (at {at})")]
-    UnknownVariant { actual: String, expected: String, at: String },
-    #[error("unknown field `{field}`, expected one of {expected} (at {at})")]
-    UnknownField { field: String, expected: String, at: String },
-    #[error("duplicate field `{field}` (at {at})")]
-    DuplicateField { field: String, at: String },
-    #[error("missing field `{field}` (at {at})")]
-    MissingField { field: String, at: String },
-    #[error("unexpected operator `{op}` (at {at})")]
-    UnexpectedOperator { op: String, at: String },
-    #[error("unexpected remainder `{remainder}` (at {at})")]
-    UnexpectedRemainder { remainder: String, at: String },
-    #[error("failed to deserialize: {error} (at {at})")]
+    #[error("invalid type {actual}, expected {expected} (at {at}){}", .at.snippet())]
+    InvalidType { actual: String, expected: String, at: Location },
+    #[error("invalid value {actual}, expected {expected} (at {at}){}", .at.snippet())]
+    InvalidValue { actual: String, expected: String, at: Location },
+    #[error("invalid length {actual}, expected {expected} (at {at}){}", .at.snippet())]
+    InvalidLength { actual: usize, expected: String, at: Location },
+    #[error("unknown variant {actual}, expected one of {expected} (at {at}){}", .at.snippet())]
+    UnknownVariant { actual: String, expected: String, at: Location },
+    #[error("unknown field `{field}`, expected one of {expected} (at {at}){}", .at.snippet())]
+    UnknownField { field: String, expected: String, at: Location },
+    #[error("duplicate field `{field}` (at {at}){}", .at.snippet())]
+    DuplicateField { field: String, at: Location },
+    #[error("missing field `{field}` (at {at}){}", .at.snippet())]
+    MissingField { field: String, at: Location },
+    #[error("unexpected operator `{op}` (at {at}){}", .at.snippet())]
+    UnexpectedOperator { op: String, at: Location },
+    #[error("unexpected remainder `{remainder}` (at {at}){}", .at.snippet())]
+    UnexpectedRemainder { remainder: String, at: Location },
+    #[error("flag `{flag}` should follow all other fields (at {at}){}", .at.snippet())]
+    MisplacedFlag { flag: String, at: Location },
+    #[error("syntax error: {error} (at {at}){}", .at.snippet())]
+    SyntaxError {
+        error: jomini::Error,
+        at: Location,
+    },
+    #[error("failed to deserialize: {error} (at {at}){}", .at.snippet())]
     DeserializeError {
         error: jomini::DeserializeError,
-        at: String,
+        at: Location,
     },
-    #[error("failed to parse: {error} (at {at})")]
+    #[error("failed to parse: {error} (at {at}){}", .at.snippet())]
     ScalarError {
         error: jomini::ScalarError,
-        at: String,
+        at: Location,
+    },
+    #[error("failed to read {file}: {error}")]
+    Io {
+        error: std::io::Error,
+        file: String,
+    },
+    #[error("failed to parse {file} as {format}: {message}")]
+    InvalidFormat {
+        format: &'static str,
+        message: String,
+        file: String,
     },
-    #[error("{message} (at {at})")]
+    #[error("{message} (at {at}){}", .at.snippet())]
     Custom {
         message: String,
-        at: String,
+        at: Location,
     },
 }
 
@@ -44,7 +63,7 @@ impl Error {
         Error::InvalidType {
             actual: actual.to_owned(),
             expected: expected.to_owned(),
-            at: reader.path(),
+            at: reader.location(),
         }
     }
 
@@ -52,7 +71,7 @@ impl Error {
         Error::InvalidValue {
             actual: actual.to_owned(),
             expected: expected.to_owned(),
-            at: reader.path(),
+            at: reader.location(),
         }
     }
 
@@ -60,7 +79,7 @@ impl Error {
         Error::InvalidLength {
             actual,
             expected: expected.to_owned(),
-            at: reader.path(),
+            at: reader.location(),
         }
     }
 
@@ -72,7 +91,7 @@ impl Error {
                 .map(|s| format!("`{}`", s))
                 .collect::<Vec<_>>()
                 .join(", "),
-            at: reader.path(),
+            at: reader.location(),
         }
     }
 
@@ -84,56 +103,63 @@ impl Error {
                 .map(|s| format!("`{}`", s))
                 .collect::<Vec<_>>()
                 .join(", "),
-            at: reader.path(),
+            at: reader.location(),
         }
     }
 
     pub fn duplicate_field(reader: &Reader, field: &str) -> Self {
         Error::DuplicateField {
             field: field.to_owned(),
-            at: reader.path(),
+            at: reader.location(),
         }
     }
 
     pub fn missing_field(reader: &Reader, field: &str) -> Self {
         Error::MissingField {
             field: field.to_owned(),
-            at: reader.path(),
+            at: reader.location(),
         }
     }
 
     pub fn unexpected_operator(reader: &Reader, op: jomini::text::Operator) -> Self {
         Error::UnexpectedOperator {
             op: op.to_string(),
-            at: reader.path(),
+            at: reader.location(),
         }
     }
 
     pub fn unexpected_remainder(reader: &Reader, remainder: &str) -> Self {
         Error::UnexpectedRemainder {
             remainder: remainder.to_owned(),
-            at: reader.path(),
+            at: reader.location(),
+        }
+    }
+
+    pub fn misplaced_flag(reader: &Reader, flag: &str) -> Self {
+        Error::MisplacedFlag {
+            flag: flag.to_owned(),
+            at: reader.location(),
         }
     }
 
     pub fn deserialize_error(reader: &Reader, error: jomini::DeserializeError) -> Self {
         Error::DeserializeError {
             error,
-            at: reader.path(),
+            at: reader.location(),
         }
     }
 
     pub fn scalar_error(reader: &Reader, error: jomini::ScalarError) -> Self {
         Error::ScalarError {
             error,
-            at: reader.path(),
+            at: reader.location(),
         }
     }
 
     pub fn custom<T: std::fmt::Display>(reader: &Reader, msg: T) -> Self {
         Error::Custom {
             message: msg.to_string(),
-            at: reader.path(),
+            at: reader.location(),
         }
     }
 }
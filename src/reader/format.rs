@@ -0,0 +1,303 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::path::Path;
+
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+
+use super::error::Error;
+
+// Input formats supported by the loader, selected by file extension.
+//
+// RON and JSON files describe the same structure as .gui files:
+//
+//     { "window": { "title": "Hello", "button": "Ok", "button": "Cancel" } }
+//     (window: (title: "Hello", button: "Ok", button: "Cancel"))
+//
+// Keys may repeat, and their order is preserved, same as in .gui files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Gui,
+    Ron,
+    Json,
+}
+
+impl Format {
+    pub const EXTENSIONS: &'static [&'static str] = &["gui", "gui.ron", "gui.json"];
+
+    pub fn from_path(path: &Path) -> Self {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if name.ends_with(".ron") {
+            Self::Ron
+        } else if name.ends_with(".json") {
+            Self::Json
+        } else {
+            Self::Gui
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Gui  => "gui",
+            Self::Ron  => "ron",
+            Self::Json => "json",
+        }
+    }
+
+    // Converts file contents into .gui text, so that all formats share the same reader.
+    // Source map of the converted text leads errors back to the original file.
+    pub fn to_gui<'a>(self, data: &'a [u8], file: &str) -> Result<Converted<'a>, Error> {
+        let node = match self {
+            Self::Gui  => return Ok(Converted { data: Cow::Borrowed(data), source_map: None }),
+            Self::Ron  => ron::de::from_bytes::<Node>(data).map_err(|err| err.to_string()),
+            Self::Json => serde_json::from_slice::<Node>(data).map_err(|err| err.to_string()),
+        };
+
+        let node = node.map_err(|message| Error::InvalidFormat {
+            format: self.name(),
+            message,
+            file: file.to_owned(),
+        })?;
+
+        let Node::Object(fields) = node else {
+            return Err(Error::InvalidFormat {
+                format: self.name(),
+                message: "expected object at the top level".to_owned(),
+                file: file.to_owned(),
+            });
+        };
+
+        let mut emitter = Emitter { output: String::new(), source_map: SourceMap::new(data), cursor: 0 };
+        emitter.fields(&fields);
+        Ok(Converted {
+            data: Cow::Owned(emitter.output.into_bytes()),
+            source_map: Some(emitter.source_map),
+        })
+    }
+}
+
+pub struct Converted<'a> {
+    pub data: Cow<'a, [u8]>,
+    // `None` for .gui files, which are read as is
+    pub source_map: Option<SourceMap<'a>>,
+}
+
+// Offsets of keys and scalars in converted text, paired with offsets of the same values
+// in the original file.
+#[derive(Debug)]
+pub struct SourceMap<'a> {
+    pub original: &'a [u8],
+    // sorted by offset in converted text
+    offsets: Vec<(usize, usize)>,
+}
+
+impl<'a> SourceMap<'a> {
+    fn new(original: &'a [u8]) -> Self {
+        Self { original, offsets: vec![] }
+    }
+
+    // values that weren't found in the original file are reported at the closest value before them
+    pub fn original_offset(&self, offset: usize) -> Option<usize> {
+        let idx = self.offsets.partition_point(|(converted, _)| *converted <= offset);
+        Some(self.offsets.get(idx.checked_sub(1)?)?.1)
+    }
+}
+
+// Writes the tree as .gui text, looking up every key and scalar in the original file along the way.
+// Both are found in the same order they were deserialized in, so the search only goes forward.
+struct Emitter<'a> {
+    output: String,
+    source_map: SourceMap<'a>,
+    // end of the last value found in the original file
+    cursor: usize,
+}
+
+impl Emitter<'_> {
+    fn fields(&mut self, fields: &[(String, Node)]) {
+        for (key, value) in fields.iter() {
+            self.map(key);
+            self.output.push_str(key);
+            self.output.push_str(" = ");
+            self.node(value);
+            self.output.push('\n');
+        }
+    }
+
+    fn node(&mut self, node: &Node) {
+        match node {
+            Node::Scalar(value) => {
+                self.map(value);
+                // unquoted strings are required for bindings and keywords
+                let is_token = !value.is_empty() && value.chars().all(|c| {
+                    c.is_alphanumeric() || matches!(c, '_' | '@' | '.' | '-' | '|' | ':')
+                });
+                if is_token {
+                    self.output.push_str(value);
+                } else {
+                    self.output.push('"');
+                    for c in value.chars() {
+                        if c == '"' || c == '\\' {
+                            self.output.push('\\');
+                        }
+                        self.output.push(c);
+                    }
+                    self.output.push('"');
+                }
+            }
+            Node::Object(fields) => {
+                self.output.push_str("{\n");
+                self.fields(fields);
+                self.output.push('}');
+            }
+            Node::Array(items) => {
+                self.output.push('{');
+                for item in items.iter() {
+                    self.output.push(' ');
+                    self.node(item);
+                }
+                self.output.push_str(" }");
+            }
+        }
+    }
+
+    // Either quoted or bare, whichever comes first. Values that were converted (e.g. `true` to `yes`)
+    // or written differently (e.g. `1.0` to `1`) may not be found, then the cursor stays where it is.
+    fn map(&mut self, value: &str) {
+        let original = self.source_map.original;
+        let rest = &original[self.cursor..];
+        let bare = match value {
+            "yes" => "true",
+            "no"  => "false",
+            value => value,
+        };
+
+        let quoted = format!("\"{}\"", value);
+        let found_quoted = find(rest, quoted.as_bytes(), |_, _| true).map(|idx| (idx, quoted.len()));
+        let found_bare = find(rest, bare.as_bytes(), |before, after| {
+            let is_word = |c: Option<&u8>| c.is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, b'_' | b'.'));
+            !is_word(before) && !is_word(after)
+        }).map(|idx| (idx, bare.len()));
+
+        let found = match (found_quoted, found_bare) {
+            (Some(quoted), Some(bare)) => Some(if quoted.0 <= bare.0 { quoted } else { bare }),
+            (quoted, bare) => quoted.or(bare),
+        };
+        if let Some((idx, len)) = found {
+            self.source_map.offsets.push((self.output.len(), self.cursor + idx));
+            self.cursor += idx + len;
+        }
+    }
+}
+
+// first occurrence of `needle` accepted by `boundary`, which is given bytes around it
+fn find(haystack: &[u8], needle: &[u8], boundary: impl Fn(Option<&u8>, Option<&u8>) -> bool) -> Option<usize> {
+    if needle.is_empty() { return None; }
+    (0..=haystack.len().checked_sub(needle.len())?).find(|&idx| {
+        haystack[idx..].starts_with(needle)
+            && boundary(idx.checked_sub(1).and_then(|idx| haystack.get(idx)), haystack.get(idx + needle.len()))
+    })
+}
+
+// Intermediate tree built from RON or JSON, written out as .gui text by `Emitter`.
+#[derive(Debug)]
+enum Node {
+    Scalar(String),
+    Object(Vec<(String, Node)>),
+    Array(Vec<Node>),
+}
+
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(NodeVisitor)
+    }
+}
+
+struct NodeVisitor;
+
+impl<'de> Visitor<'de> for NodeVisitor {
+    type Value = Node;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("string, number, boolean, array or object")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Node, E> {
+        Ok(Node::Scalar(if v { "yes" } else { "no" }.to_owned()))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Node, E> {
+        Ok(Node::Scalar(v.to_string()))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Node, E> {
+        Ok(Node::Scalar(v.to_string()))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Node, E> {
+        Ok(Node::Scalar(v.to_string()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Node, E> {
+        Ok(Node::Scalar(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Node, E> {
+        Ok(Node::Scalar(v))
+    }
+
+    // `()` and `null` are used for fields without value, e.g. `end_row = {}`
+    fn visit_unit<E>(self) -> Result<Node, E> {
+        Ok(Node::Object(vec![]))
+    }
+
+    fn visit_none<E>(self) -> Result<Node, E> {
+        Ok(Node::Object(vec![]))
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Node, D::Error> {
+        Node::deserialize(deserializer)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Node, D::Error> {
+        Node::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Node, A::Error> {
+        let mut items = vec![];
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Node::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Node, A::Error> {
+        let mut fields = vec![];
+        while let Some((Key(key), value)) = map.next_entry::<Key, Node>()? {
+            fields.push((key, value));
+        }
+        Ok(Node::Object(fields))
+    }
+}
+
+// RON only accepts identifiers as struct field names when deserialized as `str`
+struct Key(String);
+
+impl<'de> Deserialize<'de> for Key {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(KeyVisitor)
+    }
+}
+
+struct KeyVisitor;
+
+impl<'de> Visitor<'de> for KeyVisitor {
+    type Value = Key;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("field name")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Key, E> {
+        Ok(Key(v.to_owned()))
+    }
+}
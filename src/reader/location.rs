@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+// Position of a value in the source file.
+#[derive(Debug, Clone, Default)]
+pub struct Location {
+    pub path: String,
+    pub file: String,
+    // 1-based line and column
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+}
+
+impl Location {
+    pub fn new(path: String, file: String, source: &[u8], offset: Option<usize>) -> Self {
+        let Some(offset) = offset.filter(|offset| *offset <= source.len()) else {
+            return Location { path, file, ..Default::default() };
+        };
+
+        let line_start = source[..offset].iter().rposition(|c| *c == b'\n').map(|idx| idx + 1).unwrap_or(0);
+        let line_end = source[offset..].iter().position(|c| *c == b'\n').map(|idx| idx + offset).unwrap_or(source.len());
+        let line = source[..line_start].iter().filter(|c| **c == b'\n').count() + 1;
+        let prefix = String::from_utf8_lossy(&source[line_start..offset]);
+        let column = prefix.chars().count() + 1;
+        let snippet = String::from_utf8_lossy(&source[line_start..line_end]).trim_end().to_owned();
+
+        Location { path, file, line, column, snippet }
+    }
+
+    // rendered below the error message, similar to rustc diagnostics
+    pub fn snippet(&self) -> String {
+        if self.line == 0 { return String::new(); }
+
+        let line = self.line.to_string();
+        let padding = " ".repeat(line.len());
+        let marker = self.snippet
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+
+        format!(
+            "\n{padding} |\n{line} | {}\n{padding} | {marker}^",
+            self.snippet.replace('\r', ""),
+        )
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "{}, ", self.path)?;
+        }
+        if self.line == 0 {
+            write!(f, "{}", self.file)
+        } else {
+            write!(f, "{}:{}:{}", self.file, self.line, self.column)
+        }
+    }
+}
@@ -1,7 +1,10 @@
 pub mod binding;
 pub mod data_model;
 pub mod error;
+pub mod format;
+pub mod location;
 pub mod reader;
+pub mod schema;
 
 use error::Error;
 
@@ -15,9 +18,16 @@ impl ReadUiconf for String {
     }
 }
 
+// `yes`/`no` as in Paradox files, `true`/`false` for everyone else, or a bare flag meaning `yes`
 impl ReadUiconf for bool {
     fn read_uiconf(value: &reader::Reader) -> Result<Self, Error> {
-        value.read_scalar()?.to_bool().map_err(|err| Error::scalar_error(value, err))
+        if value.is_flag() { return Ok(true); }
+
+        match &*value.read_scalar()?.to_string() {
+            "yes" | "true" => Ok(true),
+            "no" | "false" => Ok(false),
+            other => Err(Error::invalid_value(value, other, "yes or no")),
+        }
     }
 }
 
@@ -1,43 +1,355 @@
+use std::any::TypeId;
 use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 
-use jomini::text::ValueReader;
+use jomini::text::{Operator, ValueReader};
 use jomini::{Scalar, TextToken, Utf8Encoding};
 use smol_str::SmolStr;
 
 use super::ReadUiconf;
-use super::error::Error;
+use super::error::{Error, Location};
+use super::format::SourceMap;
+use super::schema::BindingRecord;
+use crate::model::Icon;
+use crate::registry::UiconfWidgetRegistry;
+
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadingMode {
+    // fail to load the asset if it contains unknown fields
+    #[default]
+    Strict,
+    // log a warning and skip unknown fields
+    Lenient,
+}
+
+// Data shared between all readers of the same file.
+#[derive(Default)]
+pub struct ReadContext<'data, 'tokens> {
+    source: &'data [u8],
+    // set when source was converted from another format, errors are reported in the original file
+    source_map: Option<&'data SourceMap<'data>>,
+    file: String,
+    mode: LoadingMode,
+    classes: HashMap<SmolStr, (ValueReader<'data, 'tokens, Utf8Encoding>, ReaderPath)>,
+    text_styles: Vec<SmolStr>,
+    font_families: Vec<SmolStr>,
+    bindings: RefCell<Vec<BindingRecord>>,
+    binding_scope: RefCell<Vec<SmolStr>>,
+    assets: RefCell<Vec<String>>,
+    palette: RefCell<HashMap<SmolStr, bevy::prelude::Color>>,
+    icons: RefCell<HashMap<SmolStr, Icon>>,
+    widgets: UiconfWidgetRegistry,
+}
+
+impl<'d, 't> ReadContext<'d, 't> {
+    pub fn new(source: &'d [u8], file: impl Into<String>, mode: LoadingMode, widgets: UiconfWidgetRegistry) -> Self {
+        Self {
+            source,
+            source_map: None,
+            file: file.into(),
+            mode,
+            classes: HashMap::new(),
+            text_styles: vec![],
+            font_families: vec![],
+            bindings: RefCell::default(),
+            binding_scope: RefCell::default(),
+            assets: RefCell::default(),
+            palette: RefCell::default(),
+            icons: RefCell::default(),
+            widgets,
+        }
+    }
+
+    pub fn with_source_map(mut self, source_map: Option<&'d SourceMap<'d>>) -> Self {
+        self.source_map = source_map;
+        self
+    }
+
+    // byte offset in the converted source leads to the original file
+    fn location(&self, path: String, offset: Option<usize>) -> Location {
+        match self.source_map {
+            Some(map) => Location::new(path, self.file.clone(), map.original, offset.and_then(|offset| map.original_offset(offset))),
+            None      => Location::new(path, self.file.clone(), self.source, offset),
+        }
+    }
+
+    pub fn take_bindings(&self) -> Vec<BindingRecord> {
+        std::mem::take(&mut *self.bindings.borrow_mut())
+    }
+
+    pub fn take_assets(&self) -> Vec<String> {
+        std::mem::take(&mut *self.assets.borrow_mut())
+    }
+
+    fn offset_of(&self, scalar: Scalar<'d>) -> Option<usize> {
+        let offset = (scalar.as_bytes().as_ptr() as usize).checked_sub(self.source.as_ptr() as usize)?;
+        (offset <= self.source.len()).then_some(offset)
+    }
+
+    // Classes can only reference classes defined above them, this way cycles are impossible.
+    pub fn read_classes(&mut self, value: &Reader<'d, 't>) -> Result<(), Error> {
+        for (key, value) in value.read_object()? {
+            if self.classes.contains_key(&*key) {
+                return Err(Error::duplicate_field(&value, &key));
+            }
+
+            let object = value.reader.read_object().map_err(|err| Error::deserialize_error(&value, err))?;
+            for (field, _, class) in object.fields() {
+                if field.read_str() == "class" {
+                    let class = Reader::new(class, value.path.clone(), value.context.clone());
+                    for name in class.read_class_names()? {
+                        if !self.classes.contains_key(&*name) {
+                            return Err(Error::custom(&class, format!(
+                                "class `{}` should be defined above `{}`",
+                                name, key,
+                            )));
+                        }
+                    }
+                }
+            }
+
+            self.classes.insert(key.into(), (value.reader.clone(), value.path.clone()));
+        }
+
+        Ok(())
+    }
+
+    // `palette = { accent = "#ff8800" danger = red }`, colors can reference colors defined above them
+    pub fn read_palette(&self, value: &Reader<'d, 't>) -> Result<(), Error> {
+        for (key, value) in value.read_object()? {
+            if self.palette.borrow().contains_key(&*key) {
+                return Err(Error::duplicate_field(&value, &key));
+            }
+            let color = value.read::<bevy::prelude::Color>()?;
+            self.palette.borrow_mut().insert(key.into(), color);
+        }
+        Ok(())
+    }
+
+    // `icons = { texture = "ui/icons.png"  sword = { 0 0 16 16 } }`, regions are `{ x y width height }` in pixels;
+    // there can be several of these sections, one per texture
+    pub fn read_icons(&self, value: &Reader<'d, 't>) -> Result<(), Error> {
+        let mut texture = None;
+        let mut regions = vec![];
+
+        for (key, value) in value.read_object()? {
+            if key == "texture" {
+                if texture.is_some() { return Err(Error::duplicate_field(&value, "texture")); }
+                let path = value.read_string()?;
+                value.record_asset(&path);
+                texture = Some(path);
+            } else {
+                if self.icons.borrow().contains_key(&*key) || regions.iter().any(|(name, _)| *name == key) {
+                    return Err(Error::duplicate_field(&value, &key));
+                }
+                regions.push((key, Icon::read_region(&value)?));
+            }
+        }
+
+        let texture = texture.ok_or_else(|| Error::missing_field(value, "texture"))?;
+        for (name, region) in regions {
+            self.icons.borrow_mut().insert(name.into(), Icon { texture: texture.clone(), region });
+        }
+        Ok(())
+    }
+
+    // Names are collected before anything else is read, so text can reference styles defined below it.
+    pub fn read_text_style_names(&mut self, value: &Reader<'d, 't>) -> Result<(), Error> {
+        for (key, _) in value.read_object()? {
+            self.text_styles.push(key.into());
+        }
+        Ok(())
+    }
+
+    // Same for font families, egui panics when text uses a family that isn't declared.
+    pub fn read_font_family_names(&mut self, value: &Reader<'d, 't>) -> Result<(), Error> {
+        for (key, _) in value.read_object()? {
+            self.font_families.push(key.into());
+        }
+        Ok(())
+    }
+}
+
+// Path from the root to a value, stored as a linked list shared between siblings,
+// so that reading children doesn't copy the whole path.
+#[derive(Debug, Clone, Default)]
+pub struct ReaderPath(Option<Rc<PathSegment>>);
+
+#[derive(Debug)]
+struct PathSegment {
+    parent: ReaderPath,
+    key: SmolStr,
+    index: u32,
+}
+
+impl ReaderPath {
+    pub fn root() -> Self {
+        Self(None)
+    }
+
+    pub fn join(&self, key: impl Into<SmolStr>, index: u32) -> Self {
+        Self(Some(Rc::new(PathSegment { parent: self.clone(), key: key.into(), index })))
+    }
+
+    // from the root to the last segment
+    fn segments(&self) -> Vec<&PathSegment> {
+        let mut result = vec![];
+        let mut current = self;
+        while let Some(segment) = &current.0 {
+            result.push(&**segment);
+            current = &segment.parent;
+        }
+        result.reverse();
+        result
+    }
+}
+
+impl std::hash::Hash for ReaderPath {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for segment in self.segments() {
+            segment.key.hash(state);
+            segment.index.hash(state);
+        }
+    }
+}
+
+impl std::fmt::Display for ReaderPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, segment) in self.segments().into_iter().enumerate() {
+            if idx > 0 { f.write_str(".")?; }
+            f.write_str(&segment.key)?;
+        }
+        Ok(())
+    }
+}
 
 pub struct Reader<'data, 'tokens> {
     reader: ValueReader<'data, 'tokens, Utf8Encoding>,
-    path: Vec<(SmolStr, u32)>,
+    path: ReaderPath,
+    // byte offset in the source file, used for error reporting
+    offset: Option<usize>,
+    // bare `resizable` without a value, read as `resizable = yes`
+    flag: bool,
+    context: Rc<ReadContext<'data, 'tokens>>,
 }
 
 impl<'d, 't> Reader<'d, 't> {
-    pub fn new(value: ValueReader<'d, 't, Utf8Encoding>, path: Vec<(SmolStr, u32)>) -> Self {
-        Self { reader: value, path }
+    pub fn new(
+        value: ValueReader<'d, 't, Utf8Encoding>,
+        path: ReaderPath,
+        context: Rc<ReadContext<'d, 't>>,
+    ) -> Self {
+        let offset = match value.token() {
+            TextToken::Quoted(scalar) | TextToken::Unquoted(scalar) => context.offset_of(*scalar),
+            _ => None,
+        };
+        Self { reader: value, path, offset, flag: false, context }
+    }
+
+    // point errors at the key instead of the value
+    pub fn with_key_offset(mut self, key: Scalar<'d>) -> Self {
+        self.offset = self.context.offset_of(key).or(self.offset);
+        self
     }
 
     pub fn token(&self) -> &TextToken<'d> {
         self.reader.token()
     }
 
+    pub fn is_flag(&self) -> bool {
+        self.flag
+    }
+
+    // only built when needed, e.g. for error reporting
     pub fn path(&self) -> String {
-        self.path.iter().map(|(s, _)| s.as_str()).collect::<Vec<_>>().join(".")
+        self.path.to_string()
+    }
+
+    pub fn location(&self) -> Location {
+        self.context.location(self.path(), self.offset)
     }
 
     pub fn get_id(&self) -> crate::egui::Id {
         crate::egui::Id::new(&self.path)
     }
 
+    // in lenient mode unknown fields are reported and skipped
+    pub fn skip_unknown_field(&self, field: &str, expected: &'static [&'static str]) -> Result<(), Error> {
+        let error = Error::unknown_field(self, field, expected);
+        match self.context.mode {
+            LoadingMode::Strict => Err(error),
+            LoadingMode::Lenient => {
+                bevy::log::warn!("{}", error);
+                Ok(())
+            }
+        }
+    }
+
+    // custom widgets registered by user, see `UiconfWidgetRegistry`
+    pub fn widgets(&self) -> &UiconfWidgetRegistry {
+        &self.context.widgets
+    }
+
+    pub fn palette_color(&self, name: &str) -> Option<bevy::prelude::Color> {
+        self.context.palette.borrow().get(name).copied()
+    }
+
+    pub fn icon(&self, name: &str) -> Option<Icon> {
+        self.context.icons.borrow().get(name).cloned()
+    }
+
+    pub fn is_text_style_defined(&self, name: &str) -> bool {
+        self.context.text_styles.iter().any(|style| style == name)
+    }
+
+    pub fn is_font_family_declared(&self, name: &str) -> bool {
+        self.context.font_families.iter().any(|family| family == name)
+    }
+
+    // bindings are collected to be validated against data model later
+    pub fn record_binding(&self, name: &str, type_id: TypeId, type_name: &'static str) {
+        let record = BindingRecord {
+            name: name.into(),
+            scope: self.context.binding_scope.borrow().clone(),
+            type_id,
+            type_name,
+            location: self.location(),
+        };
+        self.context.bindings.borrow_mut().push(record);
+    }
+
+    // paths of other assets used at runtime (e.g. sounds), loaded as dependencies of the window
+    pub fn record_asset(&self, path: &str) {
+        let mut assets = self.context.assets.borrow_mut();
+        if !assets.iter().any(|asset| asset == path) {
+            assets.push(path.to_owned());
+        }
+    }
+
+    // bindings inside of `each` block are resolved relative to list items
+    pub fn push_binding_scope(&self, list: &str) {
+        self.context.binding_scope.borrow_mut().push(list.into());
+    }
+
+    pub fn pop_binding_scope(&self) {
+        self.context.binding_scope.borrow_mut().pop();
+    }
+
     pub fn read<T: ReadUiconf>(&self) -> Result<T, Error> {
         T::read_uiconf(self)
     }
 
     pub fn is_scalar(&self) -> bool {
-        matches!(self.reader.token(), TextToken::Quoted(_) | TextToken::Unquoted(_))
+        !self.flag && matches!(self.reader.token(), TextToken::Quoted(_) | TextToken::Unquoted(_))
     }
 
     pub fn read_scalar(&self) -> Result<Scalar<'d>, Error> {
+        if self.flag {
+            return Err(Error::invalid_type(self, self.token_type(), "scalar"));
+        }
+
         match self.token() {
             TextToken::Quoted(scalar) => Ok(*scalar),
             TextToken::Unquoted(scalar) => Ok(*scalar),
@@ -58,27 +370,125 @@ impl<'d, 't> Reader<'d, 't> {
             _ => return Err(Error::invalid_type(self, self.token_type(), "object")),
         };
 
+        // bare flags are either the only thing in `{ resizable movable }`,
+        // or follow all other fields in `{ title = "Hi" resizable }`
+        let (fields, flags) = if matches!(self.token(), TextToken::Array { .. }) {
+            let array = self.reader.read_array().map_err(|err| Error::deserialize_error(self, err))?;
+            (vec![], array.values().collect::<Vec<_>>())
+        } else {
+            let object = self.reader.read_object().map_err(|err| Error::deserialize_error(self, err))?;
+            let mut fields = object.fields();
+            for (_, op, _) in fields.by_ref() {
+                if let Some(op) = op {
+                    return Err(Error::unexpected_operator(self, op));
+                }
+            }
+            let flags = fields.remainder().values().collect::<Vec<_>>();
+            (object.fields().collect::<Vec<_>>(), flags)
+        };
+
+        // fields after a flag, e.g. `{ resizable title = "Hi" }`, end up among the flags
+        // with an operator token between key and value
+        if flags.iter().any(|flag| matches!(flag.token(), TextToken::Operator(_))) {
+            let Some(flag) = flags.iter().find(|flag| matches!(flag.token(), TextToken::Unquoted(_))) else {
+                return Err(Error::misplaced_flag(self, ""));
+            };
+            let name = flag.read_str().unwrap_or(Cow::Borrowed(""));
+            let at = Reader::new(flag.clone(), self.path.join(&*name, 0), self.context.clone());
+            return Err(Error::misplaced_flag(&at, &name));
+        }
+
+        for flag in flags.iter() {
+            if !matches!(flag.token(), TextToken::Unquoted(_)) {
+                let remainder = flag.read_str().unwrap_or(Cow::Borrowed(""));
+                return Err(Error::unexpected_remainder(self, &remainder));
+            }
+        }
+
+        let keys = fields.iter().map(|(key, _, _)| key.read_str())
+            .chain(flags.iter().map(|flag| flag.read_str().unwrap_or(Cow::Borrowed(""))))
+            .collect::<Vec<_>>();
+        let mut result = vec![];
+
+        for (idx, (key, _, value)) in fields.into_iter().enumerate() {
+            let path = self.path.join(key.read_str(), idx as u32);
+            let value = Reader::new(value, path, self.context.clone()).with_key_offset(key.read_scalar());
+
+            if keys[idx] != "class" {
+                result.push((key.read_str(), value));
+                continue;
+            }
+
+            // `class = name` is replaced with properties of that class,
+            // explicitly specified properties (or properties of later classes) take precedence
+            let mut classes = vec![];
+            for name in value.read_class_names()? {
+                let Some((class, path)) = self.context.classes.get(&*name) else {
+                    return Err(Error::custom(&value, format!("unknown class `{}`", name)));
+                };
+                let class = Reader::new(class.clone(), path.clone(), self.context.clone());
+                classes.push(class.read_object()?.collect::<Vec<_>>());
+            }
+
+            let class_keys = classes.iter().map(|fields| {
+                fields.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>()
+            }).collect::<Vec<_>>();
+
+            for (class_idx, fields) in classes.into_iter().enumerate() {
+                for (key, value) in fields {
+                    let overridden = keys.contains(&key) ||
+                        class_keys[class_idx + 1..].iter().any(|keys| keys.contains(&key));
+
+                    if !overridden {
+                        result.push((key, value));
+                    }
+                }
+            }
+        }
+
+        let fields_len = keys.len() - flags.len();
+        for (idx, flag) in flags.into_iter().enumerate() {
+            let key = keys[fields_len + idx].clone();
+            let path = self.path.join(&*key, (fields_len + idx) as u32);
+            let mut value = Reader::new(flag, path, self.context.clone());
+            value.flag = true;
+            result.push((key, value));
+        }
+
+        Ok(result.into_iter())
+    }
+
+    // `{ key op value }` with a single comparison inside, e.g. `{ @state == Paused }`
+    pub fn read_comparison(&self) -> Result<(Cow<'d, str>, Operator, Reader<'d, 't>), Error> {
+        match self.token() {
+            TextToken::Object { .. } => (),
+            _ => return Err(Error::invalid_type(self, self.token_type(), "comparison")),
+        };
+
         let object = self.reader.read_object().map_err(|err| Error::deserialize_error(self, err))?;
         let mut fields = object.fields();
-        for (_, op, _) in fields.by_ref() {
-            if let Some(op) = op {
-                return Err(Error::unexpected_operator(self, op));
-            }
+        let Some((key, op, value)) = fields.next() else {
+            return Err(Error::custom(self, "expected comparison, found empty object"));
+        };
+        let Some(op) = op else {
+            return Err(Error::custom(self, "expected comparison operator, found `=`"));
+        };
+        if fields.next().is_some() {
+            return Err(Error::custom(self, "expected a single comparison"));
         }
-        if let Some(remainder) = fields.remainder().values().next() {
-            let remainder = if let Ok(str) = remainder.read_str() {
-                str
-            } else {
-                Cow::Borrowed("")
-            };
-            return Err(Error::unexpected_remainder(self, &remainder));
+
+        let path = self.path.join(key.read_str(), 0);
+        let value = Reader::new(value, path, self.context.clone()).with_key_offset(key.read_scalar());
+        Ok((key.read_str(), op, value))
+    }
+
+    // either `class = name` or `class = { name1 name2 }`
+    fn read_class_names(&self) -> Result<Vec<String>, Error> {
+        if self.is_scalar() {
+            Ok(vec![self.read_string()?])
+        } else {
+            self.read()
         }
-        let path = self.path.clone();
-        Ok(object.fields().enumerate().map(move |(idx, (key, _, value))| {
-            let mut path = path.clone();
-            path.push((key.read_str().into(), idx as u32));
-            (key.read_str(), Reader::new(value, path))
-        }))
     }
 
     pub fn read_array(&self) -> Result<impl Iterator<Item = Reader<'d, 't>>, Error> {
@@ -90,16 +500,21 @@ impl<'d, 't> Reader<'d, 't> {
 
         let array = self.reader.read_array().map_err(|err| Error::deserialize_error(self, err))?;
         let path = self.path.clone();
+        let offset = self.offset;
+        let context = self.context.clone();
         let mut index = 0;
         Ok(array.values().enumerate().map(move |(idx, value)| {
-            let mut path = path.clone();
-            path.push((index.to_string().into(), idx as u32));
+            let path = path.join(index.to_string(), idx as u32);
             index += 1;
-            Reader::new(value, path)
+            let mut reader = Reader::new(value, path, context.clone());
+            reader.offset = reader.offset.or(offset);
+            reader
         }))
     }
 
     pub fn token_type(&self) -> &'static str {
+        if self.flag { return "flag without value"; }
+
         match self.token() {
             TextToken::Array { .. }          => "array",
             TextToken::Object { .. }         => "object",
@@ -0,0 +1,161 @@
+use std::any::{Any, TypeId};
+
+use bevy::reflect::{Reflect, TypeInfo, TypeRegistry, VariantInfo};
+use smol_str::SmolStr;
+
+use super::error::{Error, Location};
+
+// Every `@binding` found in the file, recorded while reading.
+#[derive(Debug, Clone)]
+pub struct BindingRecord {
+    pub name: SmolStr,
+    // list fields of enclosing `each` blocks, from outermost to innermost
+    pub scope: Vec<SmolStr>,
+    pub type_id: TypeId,
+    pub type_name: &'static str,
+    pub location: Location,
+}
+
+impl BindingRecord {
+    // `BindingRef<dyn Reflect>` is used for lists iterated by `each`
+    fn expects_list(&self) -> bool {
+        self.type_id == TypeId::of::<dyn Reflect>()
+    }
+
+    // `BindingRef<dyn Any + Send + Sync>` is used in comparisons, which work with any type
+    fn expects_any(&self) -> bool {
+        self.type_id == TypeId::of::<dyn Any + Send + Sync>()
+    }
+}
+
+// What bindings are resolved against at runtime: data model passed to `show`,
+// or namespaces of `UiconfContext` passed to `show_with_context`.
+#[derive(Debug, Clone, Copy)]
+pub enum Schema<'a> {
+    Model(TypeId),
+    // namespace and type of its data model
+    Context(&'a [(&'a str, TypeId)]),
+}
+
+// Checks all bindings against `schema`, returns all errors found.
+pub fn validate_bindings(bindings: &[BindingRecord], registry: &TypeRegistry, schema: Schema) -> Vec<Error> {
+    bindings
+        .iter()
+        .filter_map(|binding| {
+            validate_binding(binding, registry, schema).err().map(|message| Error::Custom {
+                message: format!("binding @{} is invalid: {}", binding.name, message),
+                at: binding.location.clone(),
+            })
+        })
+        .collect()
+}
+
+fn validate_binding(binding: &BindingRecord, registry: &TypeRegistry, schema: Schema) -> Result<(), String> {
+    // lists of enclosing `each` blocks, then the binding itself, each resolved relative to the previous one
+    let mut names = binding.scope.iter().chain([&binding.name]).map(|name| name.as_str()).collect::<Vec<_>>();
+
+    // only the outermost name has a namespace, same as with `UiconfContext`, list items are bound directly
+    let mut current = match schema {
+        Schema::Model(type_id) => type_id,
+        Schema::Context(namespaces) => {
+            let expected = namespaces.iter().map(|(namespace, _)| format!("`{}`", namespace)).collect::<Vec<_>>().join(", ");
+            let Some((namespace, name)) = names[0].split_once('.') else {
+                return Err(format!("@{} has no namespace, expected one of {}", names[0], expected));
+            };
+            let Some((_, type_id)) = namespaces.iter().find(|(ns, _)| *ns == namespace) else {
+                return Err(format!("unknown namespace `{}`, expected one of {}", namespace, expected));
+            };
+            names[0] = name;
+            *type_id
+        }
+    };
+
+    let name = names.pop().unwrap();
+    for list in names {
+        let field = struct_field(registry, current, list)?;
+        let Some(TypeInfo::List(info)) = registry.get_type_info(field) else {
+            return Err(format!("@{} is not a list", list));
+        };
+        current = info.item_type_id();
+    }
+
+    let field = struct_field(registry, current, name)?;
+
+    if binding.expects_any() {
+        // field exists, that's enough
+    } else if binding.expects_list() {
+        if !matches!(registry.get_type_info(field), Some(TypeInfo::List(_))) {
+            return Err(format!("expected list, found {}", type_path(registry, field)));
+        }
+    } else if field != binding.type_id && option_inner(registry, field) != Some(binding.type_id) {
+        return Err(format!("expected type {}, found {}", binding.type_name, type_path(registry, field)));
+    }
+
+    Ok(())
+}
+
+fn struct_field(registry: &TypeRegistry, type_id: TypeId, name: &str) -> Result<TypeId, String> {
+    let Some(TypeInfo::Struct(info)) = registry.get_type_info(type_id) else {
+        return Err(format!("expected struct, found {}", type_path(registry, type_id)));
+    };
+    let field = info.field(name).ok_or_else(|| format!("field `{}` not found in {}", name, info.type_path()))?;
+    Ok(field.type_id())
+}
+
+// `Option<T>` fields can be bound to anything that expects `T`
+fn option_inner(registry: &TypeRegistry, type_id: TypeId) -> Option<TypeId> {
+    let Some(TypeInfo::Enum(info)) = registry.get_type_info(type_id) else { return None; };
+    if !info.type_path().starts_with("core::option::Option<") { return None; }
+    let Some(VariantInfo::Tuple(some)) = info.variant("Some") else { return None; };
+    some.field_at(0).map(|field| field.type_id())
+}
+
+fn type_path(registry: &TypeRegistry, type_id: TypeId) -> &'static str {
+    registry.get_type_info(type_id).map(|info| info.type_path()).unwrap_or("<unregistered type>")
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::model::Root;
+    use crate::reader::reader::LoadingMode;
+    use crate::registry::UiconfWidgetRegistry;
+
+    #[derive(Reflect, Default)]
+    struct Settings {
+        title: String,
+    }
+
+    #[derive(Reflect, Default)]
+    struct Player {
+        items: Vec<Item>,
+    }
+
+    #[derive(Reflect, Default)]
+    struct Item {
+        name: String,
+    }
+
+    fn validate(content: &str) -> Vec<Error> {
+        let source = format!("window = {{ title = \"HUD\" {} }}", content);
+        let root = Root::read(source.as_bytes(), "hud.gui", LoadingMode::Strict, &UiconfWidgetRegistry::default()).unwrap();
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<Settings>();
+        registry.register::<Player>();
+        let namespaces = [("settings", TypeId::of::<Settings>()), ("player", TypeId::of::<Player>())];
+        validate_bindings(&root.bindings, &registry, Schema::Context(&namespaces))
+    }
+
+    #[test]
+    fn context_namespaces() {
+        assert!(validate("label = { text = @settings.title }").is_empty());
+        assert!(validate("each = { in = @player.items label = { text = @name } }").is_empty());
+        assert_eq!(validate("label = { text = @title }").len(), 1);
+        assert_eq!(validate("label = { text = @audio.title }").len(), 1);
+        assert_eq!(validate("label = { text = @player.title }").len(), 1);
+        assert_eq!(validate("each = { in = @player.items label = { text = @settings.title } }").len(), 1);
+    }
+}
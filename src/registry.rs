@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, RwLock};
+
+use bevy::prelude::*;
+
+use crate::egui;
+use crate::reader::binding::Binding;
+use crate::reader::data_model::DataSource;
+use crate::reader::error::Error;
+use crate::reader::reader::Reader;
+use crate::reader::ReadUiconf;
+use crate::writer::ToUiconf;
+
+// Widget provided by another crate, read from `tag = { ... }` wherever built-in widgets are allowed.
+// `ToUiconf` is used when the file is written back in canonical form.
+pub trait CustomWidget: ToUiconf + Debug + Send + Sync + 'static {
+    fn show(&self, data: &mut dyn DataSource, ui: &mut egui::Ui);
+
+    // `enabled` property read by the widget, it's disabled the same way as built-in widgets
+    fn enabled(&self) -> Option<&Binding<bool>> {
+        None
+    }
+}
+
+// Extra property of a built-in widget, e.g. `button = { text = "Sword" rarity_color = @rarity }`.
+// Only the methods matching the widget it's registered for are called.
+pub trait CustomProperty: ToUiconf + Debug + Send + Sync + 'static {
+    // called before the widget is added, after all built-in properties are applied
+    fn apply_button<'a>(&self, _data: &dyn DataSource, button: egui::Button<'a>) -> egui::Button<'a> {
+        button
+    }
+
+    fn apply_label(&self, _data: &dyn DataSource, label: egui::Label) -> egui::Label {
+        label
+    }
+
+    // called after the widget is added, before `clicked` and other response events are processed
+    fn process_response(&self, _data: &mut dyn DataSource, _response: &egui::Response) {}
+}
+
+type ReadWidget = for<'d, 't> fn(&Reader<'d, 't>) -> Result<Box<dyn CustomWidget>, Error>;
+type ReadProperty = for<'d, 't> fn(&Reader<'d, 't>) -> Result<Arc<dyn CustomProperty>, Error>;
+
+fn read_widget<W: CustomWidget + ReadUiconf>(value: &Reader) -> Result<Box<dyn CustomWidget>, Error> {
+    Ok(Box::new(W::read_uiconf(value)?))
+}
+
+fn read_property<P: CustomProperty + ReadUiconf>(value: &Reader) -> Result<Arc<dyn CustomProperty>, Error> {
+    Ok(Arc::new(P::read_uiconf(value)?))
+}
+
+// Custom widgets and properties available to all assets, register them before assets are loaded:
+//
+//     app.register_uiconf_widget::<Knob>("knob");
+//     app.register_uiconf_property::<RarityColor>("button", "rarity_color");
+//
+// Built-in widgets and properties take precedence, so a custom widget can't be named `button`,
+// and custom properties are only supported on `button` and `label`.
+#[derive(Resource, Clone, Default)]
+pub struct UiconfWidgetRegistry {
+    // shared with asset loader, which is created before user gets a chance to register anything
+    widgets: Arc<RwLock<HashMap<String, ReadWidget>>>,
+    // keyed by widget tag and property name
+    properties: Arc<RwLock<HashMap<(String, String), ReadProperty>>>,
+}
+
+impl UiconfWidgetRegistry {
+    // replaces existing widget with the same tag
+    pub fn register<W: CustomWidget + ReadUiconf>(&self, tag: impl Into<String>) {
+        self.widgets.write().unwrap().insert(tag.into(), read_widget::<W>);
+    }
+
+    pub fn contains(&self, tag: &str) -> bool {
+        self.widgets.read().unwrap().contains_key(tag)
+    }
+
+    // returns `None` if no widget is registered under this tag
+    pub fn read(&self, tag: &str, value: &Reader) -> Option<Result<Box<dyn CustomWidget>, Error>> {
+        let read = *self.widgets.read().unwrap().get(tag)?;
+        Some(read(value))
+    }
+
+    // replaces existing property with the same name on the same widget
+    pub fn register_property<P: CustomProperty + ReadUiconf>(&self, widget: impl Into<String>, name: impl Into<String>) {
+        self.properties.write().unwrap().insert((widget.into(), name.into()), read_property::<P>);
+    }
+
+    // returns `None` if no property is registered under this name for `widget`
+    pub fn read_property(&self, widget: &str, name: &str, value: &Reader) -> Option<Result<Arc<dyn CustomProperty>, Error>> {
+        let read = *self.properties.read().unwrap().get(&(widget.to_owned(), name.to_owned()))?;
+        Some(read(value))
+    }
+}
@@ -0,0 +1,202 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::egui;
+
+// Typed name of a widget, matched against its `id` in config file.
+// `#[derive(LabelToId)]` on enums converts variant names to snake case, `StartButton` => `start_button`.
+pub trait LabelToId {
+    fn to_id(&self) -> Cow<'static, str>;
+}
+
+impl LabelToId for str {
+    fn to_id(&self) -> Cow<'static, str> {
+        Cow::Owned(self.to_owned())
+    }
+}
+
+impl LabelToId for String {
+    fn to_id(&self) -> Cow<'static, str> {
+        Cow::Owned(self.clone())
+    }
+}
+
+impl<T: LabelToId + ?Sized> LabelToId for &T {
+    fn to_id(&self) -> Cow<'static, str> {
+        (**self).to_id()
+    }
+}
+
+// State of a widget with explicit `id` in the last frame it was shown:
+//
+//     if window.response(ctx, MenuLabel::StartButton).is_some_and(|response| response.clicked) { ... }
+//
+// Containers don't have a response of their own, so only `hovered` and `rect` are set for them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WidgetResponse {
+    pub clicked: bool,
+    pub secondary_clicked: bool,
+    pub double_clicked: bool,
+    pub hovered: bool,
+    pub changed: bool,
+    // in egui points, multiply by `pixels_per_point` to get physical pixels
+    pub rect: egui::Rect,
+    // area where the widget can be seen, e.g. viewport of enclosing scroll area
+    pub clip_rect: egui::Rect,
+}
+
+impl WidgetResponse {
+    fn egui_id() -> egui::Id {
+        egui::Id::new("bevy_uiconf_egui::responses")
+    }
+
+    // responses are collected while the window is shown, and then moved under its id by `store`
+    pub(crate) fn record(ui: &egui::Ui, id: &str, response: &egui::Response) {
+        let response = WidgetResponse {
+            clicked: response.clicked(),
+            secondary_clicked: response.secondary_clicked(),
+            double_clicked: response.double_clicked(),
+            hovered: response.hovered(),
+            changed: response.changed(),
+            rect: response.rect,
+            clip_rect: ui.clip_rect(),
+        };
+        ui.ctx().data_mut(|d| {
+            d.get_temp_mut_or_default::<HashMap<String, WidgetResponse>>(Self::egui_id()).insert(id.to_owned(), response);
+        });
+    }
+
+    // widgets that weren't shown this frame (e.g. hidden with `visible`) are forgotten
+    pub(crate) fn store(ctx: &egui::Context, window_id: egui::Id) {
+        ctx.data_mut(|d| {
+            let responses = std::mem::take(d.get_temp_mut_or_default::<HashMap<String, WidgetResponse>>(Self::egui_id()));
+            d.insert_temp(window_id.with("uiconf_responses"), responses);
+        });
+    }
+
+    // part of the widget that isn't clipped, `None` if it's scrolled out of view
+    pub fn visible_rect(&self) -> Option<egui::Rect> {
+        let rect = self.rect.intersect(self.clip_rect);
+        rect.is_positive().then_some(rect)
+    }
+
+    pub(crate) fn get(ctx: &egui::Context, window_id: egui::Id, id: &str) -> Option<Self> {
+        ctx.data(|d| {
+            d.get_temp::<HashMap<String, WidgetResponse>>(window_id.with("uiconf_responses"))?.get(id).copied()
+        })
+    }
+}
+
+// State egui keeps for a widget under its id. Ids are recorded while the window is shown,
+// so that state of widgets that moved or were removed can be found after the asset is reloaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WidgetState {
+    Collapsing,
+    Scroll,
+    TextEdit,
+}
+
+#[derive(Debug, Clone, Default)]
+struct WindowStates {
+    shown: HashMap<egui::Id, WidgetState>,
+    // ids recorded before the asset was reloaded, the ones that aren't shown again are removed
+    reloaded: Option<HashMap<egui::Id, WidgetState>>,
+}
+
+impl WidgetState {
+    fn egui_id() -> egui::Id {
+        egui::Id::new("bevy_uiconf_egui::widget_states")
+    }
+
+    pub(crate) fn record(self, ctx: &egui::Context, id: egui::Id) {
+        ctx.data_mut(|d| {
+            d.get_temp_mut_or_default::<HashMap<egui::Id, WidgetState>>(Self::egui_id()).insert(id, self);
+        });
+    }
+
+    // called after the window is shown, removes state left behind by the previous version of the asset
+    pub(crate) fn store(ctx: &egui::Context, window_id: egui::Id) {
+        let stale = ctx.data_mut(|d| {
+            let shown = std::mem::take(d.get_temp_mut_or_default::<HashMap<egui::Id, WidgetState>>(Self::egui_id()));
+            let states = d.get_temp_mut_or_default::<WindowStates>(window_id.with("uiconf_widget_states"));
+            match states.reloaded.take() {
+                Some(old) => {
+                    states.shown = shown;
+                    old.into_iter().filter(|(id, _)| !states.shown.contains_key(id)).collect()
+                }
+                None => {
+                    states.shown.extend(shown);
+                    vec![]
+                }
+            }
+        });
+        for (id, state) in stale {
+            state.remove(ctx, id);
+        }
+    }
+
+    // Widget ids don't depend on the window id with `UiconfPlugin::keep_window_state`. Widgets that
+    // aren't shown in the first frame after reload (e.g. hidden with `visible`) lose their state too.
+    pub(crate) fn reloaded(ctx: &egui::Context, window_id: egui::Id) {
+        ctx.data_mut(|d| {
+            let states = d.get_temp_mut_or_default::<WindowStates>(window_id.with("uiconf_widget_states"));
+            let shown = std::mem::take(&mut states.shown);
+            states.reloaded.get_or_insert_with(HashMap::new).extend(shown);
+        });
+    }
+
+    // state of all widgets ever shown in the window
+    pub(crate) fn clear(ctx: &egui::Context, window_id: egui::Id) {
+        let id = window_id.with("uiconf_widget_states");
+        let Some(states) = ctx.data_mut(|d| {
+            let states = d.get_temp::<WindowStates>(id);
+            d.remove::<WindowStates>(id);
+            states
+        }) else { return; };
+        for (id, state) in states.shown.into_iter().chain(states.reloaded.into_iter().flatten()) {
+            state.remove(ctx, id);
+        }
+    }
+
+    fn remove(self, ctx: &egui::Context, id: egui::Id) {
+        match self {
+            Self::Collapsing => {
+                if let Some(state) = egui::collapsing_header::CollapsingState::load(ctx, id) {
+                    state.remove(ctx);
+                }
+            }
+            Self::Scroll   => ctx.data_mut(|d| d.remove::<egui::scroll_area::State>(id)),
+            Self::TextEdit => ctx.data_mut(|d| d.remove::<egui::text_edit::TextEditState>(id)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WidgetState;
+    use crate::egui;
+    use crate::egui::collapsing_header::CollapsingState;
+
+    #[test]
+    fn stale_state_removed_after_reload() {
+        let ctx = egui::Context::default();
+        let window_id = egui::Id::new("window");
+        let (kept, moved) = (egui::Id::new("kept"), egui::Id::new("moved"));
+        let show = |ids: &[egui::Id]| {
+            for id in ids {
+                CollapsingState::load_with_default_open(&ctx, *id, true).store(&ctx);
+                WidgetState::Collapsing.record(&ctx, *id);
+            }
+            WidgetState::store(&ctx, window_id);
+        };
+
+        show(&[kept, moved]);
+        WidgetState::reloaded(&ctx, window_id);
+        show(&[kept]);
+        assert!(CollapsingState::load(&ctx, kept).is_some());
+        assert!(CollapsingState::load(&ctx, moved).is_none());
+
+        WidgetState::clear(&ctx, window_id);
+        assert!(CollapsingState::load(&ctx, kept).is_none());
+    }
+}
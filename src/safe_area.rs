@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use crate::egui;
+use crate::shared::EguiShared;
+
+// Part of the screen that may be cut off by TV overscan or notches, in points. Windows with
+// `respect_safe_area = yes` are kept inside of it, and their `anchor` offsets are counted from it.
+// Initial value is `UiconfPlugin::safe_area`, changing the resource at runtime (e.g. from
+// a calibration screen) moves windows accordingly.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct UiconfSafeArea {
+    pub margin: egui::Margin,
+}
+
+impl UiconfSafeArea {
+    pub fn new(margin: egui::Margin) -> Self {
+        Self { margin }
+    }
+
+    // screen space that isn't taken by panels, with margins removed
+    pub fn rect(&self, ctx: &egui::Context) -> egui::Rect {
+        let rect = ctx.available_rect();
+        egui::Rect::from_min_max(rect.min + self.margin.left_top(), rect.max - self.margin.right_bottom())
+    }
+
+    // anchor offset is moved away from the edges window is anchored to
+    pub(crate) fn anchor_offset(&self, align: egui::Align2, offset: egui::Vec2) -> egui::Vec2 {
+        let x = match align.x() {
+            egui::Align::Min    => self.margin.left,
+            egui::Align::Center => 0.,
+            egui::Align::Max    => -self.margin.right,
+        };
+        let y = match align.y() {
+            egui::Align::Min    => self.margin.top,
+            egui::Align::Center => 0.,
+            egui::Align::Max    => -self.margin.bottom,
+        };
+        offset + egui::vec2(x, y)
+    }
+}
+
+impl EguiShared for UiconfSafeArea {
+    const ID: &'static str = "bevy_uiconf_egui::safe_area";
+}
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+
+use crate::egui;
+use crate::shared::EguiShared;
+
+// UI scale of all uiconf windows, multiplied by `scale` of each window, e.g. from a slider in options:
+//
+//     app.insert_resource(UiconfScale::new(settings.ui_scale));
+//
+// Text sizes and spacing from egui style are scaled, as well as text `size` set in files.
+// Unlike `EguiSettings::scale_factor`, egui windows that aren't made by uiconf keep their size.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct UiconfScale {
+    pub scale: f32,
+}
+
+impl Default for UiconfScale {
+    fn default() -> Self {
+        Self { scale: 1. }
+    }
+}
+
+impl UiconfScale {
+    pub fn new(scale: f32) -> Self {
+        Self { scale }
+    }
+
+    fn current_id() -> egui::Id {
+        egui::Id::new("bevy_uiconf_egui::scale::current")
+    }
+
+    // scale of the window that is being shown, for sizes that don't come from the style
+    pub(crate) fn current(ctx: &egui::Context) -> f32 {
+        ctx.data(|d| d.get_temp::<f32>(Self::current_id())).unwrap_or(1.)
+    }
+
+    // context style is replaced with a scaled one for the duration of `show`
+    pub(crate) fn scoped<R>(ctx: &egui::Context, scale: f32, show: impl FnOnce() -> R) -> R {
+        if scale == 1. || scale <= 0. {
+            return show();
+        }
+
+        let style = ctx.style();
+        let mut scaled = (*style).clone();
+        scale_style(&mut scaled, scale);
+        ctx.set_style(scaled);
+        ctx.data_mut(|d| d.insert_temp(Self::current_id(), scale));
+
+        let result = show();
+
+        ctx.set_style(style);
+        ctx.data_mut(|d| d.remove::<f32>(Self::current_id()));
+        result
+    }
+}
+
+fn scale_style(style: &mut egui::Style, scale: f32) {
+    for font in style.text_styles.values_mut() {
+        font.size *= scale;
+    }
+
+    let scale_margin = |margin: &mut egui::Margin| {
+        margin.left *= scale;
+        margin.right *= scale;
+        margin.top *= scale;
+        margin.bottom *= scale;
+    };
+
+    let spacing = &mut style.spacing;
+    spacing.item_spacing *= scale;
+    scale_margin(&mut spacing.window_margin);
+    spacing.button_padding *= scale;
+    scale_margin(&mut spacing.menu_margin);
+    spacing.indent *= scale;
+    spacing.interact_size *= scale;
+    spacing.slider_width *= scale;
+    spacing.combo_width *= scale;
+    spacing.text_edit_width *= scale;
+    spacing.icon_width *= scale;
+    spacing.icon_width_inner *= scale;
+    spacing.icon_spacing *= scale;
+    spacing.tooltip_width *= scale;
+    spacing.combo_height *= scale;
+    spacing.scroll.bar_width *= scale;
+    spacing.scroll.handle_min_length *= scale;
+    spacing.scroll.bar_inner_margin *= scale;
+    spacing.scroll.bar_outer_margin *= scale;
+    spacing.scroll.floating_width *= scale;
+    spacing.scroll.floating_allocated_width *= scale;
+}
+
+impl EguiShared for UiconfScale {
+    const ID: &'static str = "bevy_uiconf_egui::scale";
+}
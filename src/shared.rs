@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+use crate::egui;
+
+// Resources that widgets need while they're shown. Widgets only have access to egui context,
+// so these are copied into egui contexts of all windows by `sync_to_egui`:
+//
+//     app.add_systems(PreUpdate, sync_to_egui::<UiconfGroups>);
+//
+pub trait EguiShared: Resource + Clone {
+    // key of the copy in egui temp data
+    const ID: &'static str;
+
+    fn egui_id() -> egui::Id {
+        egui::Id::new(Self::ID)
+    }
+
+    fn get(ctx: &egui::Context) -> Option<Self> {
+        ctx.data(|d| d.get_temp::<Self>(Self::egui_id()))
+    }
+}
+
+// copied again whenever the resource changes, and into contexts of windows created later
+pub fn sync_to_egui<R: EguiShared>(resource: Option<Res<R>>, mut egui_contexts: Query<&mut bevy_egui::EguiContext>) {
+    let Some(resource) = resource else { return; };
+
+    for mut ctx in egui_contexts.iter_mut() {
+        if !resource.is_changed() && !ctx.is_added() { continue; }
+        let resource = resource.clone();
+        ctx.get_mut().data_mut(|d| d.insert_temp(R::egui_id(), resource));
+    }
+}
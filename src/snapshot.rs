@@ -0,0 +1,177 @@
+use std::path::Path;
+
+use image::RgbaImage;
+
+use crate::egui;
+use crate::uiconf_test::Harness;
+
+// Software rendering of the last frame shown by `Harness`, for visual regression tests without GPU:
+//
+//     harness.run_frames(&mut model, 3);
+//     harness.check_snapshot("tests/snapshots/menu.png");
+//
+// Rendering is not pixel-perfect compared to real renderers, but it's stable between runs.
+impl Harness {
+    pub fn snapshot(&self) -> RgbaImage {
+        let ppp = self.pixels_per_point;
+        let width = (self.screen_size.x * ppp).round() as u32;
+        let height = (self.screen_size.y * ppp).round() as u32;
+
+        let background = self.ctx.style().visuals.panel_fill;
+        let mut pixels = vec![background; (width * height) as usize];
+
+        for primitive in self.ctx.tessellate(self.shapes.clone(), ppp) {
+            let egui::epaint::Primitive::Mesh(mesh) = &primitive.primitive else { continue; };
+            let Some(texture) = self.textures.get(&mesh.texture_id) else { continue; };
+
+            let clip = egui::Rect::from_min_max(
+                (primitive.clip_rect.min.to_vec2() * ppp).to_pos2(),
+                (primitive.clip_rect.max.to_vec2() * ppp).to_pos2(),
+            ).intersect(egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(width as f32, height as f32)));
+
+            for triangle in mesh.indices.chunks_exact(3) {
+                let [a, b, c] = [0, 1, 2].map(|idx| {
+                    let mut vertex = mesh.vertices[triangle[idx] as usize];
+                    vertex.pos = (vertex.pos.to_vec2() * ppp).to_pos2();
+                    vertex
+                });
+                draw_triangle(&mut pixels, width as usize, clip, texture, [a, b, c]);
+            }
+        }
+
+        let mut image = RgbaImage::new(width, height);
+        for (pixel, color) in image.pixels_mut().zip(pixels) {
+            *pixel = image::Rgba(color.to_srgba_unmultiplied());
+        }
+        image
+    }
+
+    // compares last frame with an image saved earlier, the image is created if it doesn't exist;
+    // set `UICONF_UPDATE_SNAPSHOTS=1` to overwrite all images instead
+    pub fn check_snapshot(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let actual = self.snapshot();
+
+        if std::env::var_os("UICONF_UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).unwrap();
+            }
+            actual.save(path).unwrap_or_else(|err| panic!("can't save snapshot {}: {}", path.display(), err));
+            return;
+        }
+
+        let expected = image::open(path).unwrap_or_else(|err| panic!("can't open snapshot {}: {}", path.display(), err)).into_rgba8();
+        if expected.dimensions() == actual.dimensions() && expected.pixels().zip(actual.pixels()).all(|(a, b)| similar(a, b)) {
+            return;
+        }
+
+        let new_path = path.with_extension("new.png");
+        actual.save(&new_path).unwrap();
+        panic!("snapshot {} doesn't match, new version is saved to {}", path.display(), new_path.display());
+    }
+}
+
+// small differences are allowed, so that snapshots survive changes in float rounding
+fn similar(a: &image::Rgba<u8>, b: &image::Rgba<u8>) -> bool {
+    a.0.iter().zip(b.0.iter()).all(|(a, b)| a.abs_diff(*b) <= 2)
+}
+
+// colors are interpolated and blended in gamma space, same as egui does by default
+fn draw_triangle(
+    pixels: &mut [egui::Color32],
+    width: usize,
+    clip: egui::Rect,
+    texture: &egui::ColorImage,
+    [a, b, c]: [egui::epaint::Vertex; 3],
+) {
+    let area = edge(a.pos, b.pos, c.pos);
+    if area.abs() < f32::EPSILON { return; }
+
+    let min = a.pos.min(b.pos).min(c.pos).max(clip.min);
+    let max = a.pos.max(b.pos).max(c.pos).min(clip.max);
+    if min.x >= max.x || min.y >= max.y { return; }
+
+    for y in min.y.floor() as usize..max.y.ceil() as usize {
+        for x in min.x.floor() as usize..max.x.ceil() as usize {
+            let point = egui::pos2(x as f32 + 0.5, y as f32 + 0.5);
+            if !clip.contains(point) { continue; }
+
+            let wa = edge(b.pos, c.pos, point) / area;
+            let wb = edge(c.pos, a.pos, point) / area;
+            let wc = edge(a.pos, b.pos, point) / area;
+            if wa < 0. || wb < 0. || wc < 0. { continue; }
+
+            let color = interpolate([a.color, b.color, c.color], [wa, wb, wc]);
+            let uv = (a.uv.to_vec2() * wa + b.uv.to_vec2() * wb + c.uv.to_vec2() * wc).to_pos2();
+            let src = multiply(color, sample(texture, uv));
+
+            let dst = &mut pixels[y * width + x];
+            *dst = blend(src, *dst);
+        }
+    }
+}
+
+fn edge(a: egui::Pos2, b: egui::Pos2, p: egui::Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+fn interpolate(colors: [egui::Color32; 3], weights: [f32; 3]) -> egui::Color32 {
+    let channel = |idx: usize| {
+        let value: f32 = colors.iter().zip(weights).map(|(color, weight)| color.to_array()[idx] as f32 * weight).sum();
+        value.round().clamp(0., 255.) as u8
+    };
+    egui::Color32::from_rgba_premultiplied(channel(0), channel(1), channel(2), channel(3))
+}
+
+// nearest neighbour, textures are either font atlas or images drawn at their natural size
+fn sample(texture: &egui::ColorImage, uv: egui::Pos2) -> egui::Color32 {
+    let [width, height] = texture.size;
+    let x = ((uv.x * width as f32) as usize).min(width - 1);
+    let y = ((uv.y * height as f32) as usize).min(height - 1);
+    texture.pixels[y * width + x]
+}
+
+fn multiply(a: egui::Color32, b: egui::Color32) -> egui::Color32 {
+    let [a, b] = [a.to_array(), b.to_array()];
+    let channel = |idx: usize| ((a[idx] as u16 * b[idx] as u16 + 127) / 255) as u8;
+    egui::Color32::from_rgba_premultiplied(channel(0), channel(1), channel(2), channel(3))
+}
+
+// both colors are premultiplied
+fn blend(src: egui::Color32, dst: egui::Color32) -> egui::Color32 {
+    let [src, dst] = [src.to_array(), dst.to_array()];
+    let inverse = 255 - src[3] as u16;
+    let channel = |idx: usize| (src[idx] as u16 + (dst[idx] as u16 * inverse + 127) / 255).min(255) as u8;
+    egui::Color32::from_rgba_premultiplied(channel(0), channel(1), channel(2), channel(3))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::egui;
+    use crate::uiconf_test::Harness;
+    use crate::UiconfWindow;
+
+    #[derive(Reflect, Default)]
+    struct Empty {}
+
+    #[test]
+    fn snapshot_round_trip() {
+        let window = UiconfWindow::from_embedded(b"window = { title = \"Hello\" label = \"Hello\" }", "hello.gui").unwrap();
+        let mut harness = Harness::new(window);
+        harness.screen_size = egui::vec2(320., 240.);
+        harness.run_frames(&mut Empty::default(), 3);
+
+        let image = harness.snapshot();
+        assert_eq!(image.dimensions(), (320, 240));
+        let background = image.get_pixel(319, 239);
+        assert!(image.pixels().any(|pixel| pixel != background), "nothing was drawn");
+
+        // first call saves the image, second one compares against it
+        let path = std::env::temp_dir().join(format!("bevy_uiconf_egui_snapshot_{}.png", std::process::id()));
+        harness.check_snapshot(&path);
+        harness.check_snapshot(&path);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
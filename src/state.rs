@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+use bevy::reflect::serde::{ReflectSerializer, UntypedReflectDeserializer};
+use bevy::reflect::{ReflectFromReflect, ReflectRef};
+use serde::de::DeserializeSeed;
+
+use crate::egui;
+use crate::reader::data_model::{DataSource, Trigger};
+use crate::shared::EguiShared;
+
+// Snapshots of data model fields, taken by `clicked = save_state(@settings)` and restored by
+// `clicked = load_state(@settings)`, e.g. settings are saved when settings screen is opened,
+// and restored when "Revert" is clicked.
+//
+// Snapshots are stored as RON, keyed by type path of the field and binding name, so that models
+// with same-named fields of different types don't overwrite each other, and can be written to disk as well.
+// Types of saved fields must be registered in `AppTypeRegistry`.
+#[derive(Resource, Clone)]
+pub struct UiconfStates {
+    registry: AppTypeRegistry,
+    snapshots: Arc<Mutex<HashMap<(String, String), String>>>,
+}
+
+impl UiconfStates {
+    pub fn new(registry: AppTypeRegistry) -> Self {
+        Self { registry, snapshots: Default::default() }
+    }
+
+    // `type_path` is the type of the field, e.g. `snapshot("my_game::Settings", "settings")`
+    pub fn snapshot(&self, type_path: &str, name: &str) -> Option<String> {
+        self.snapshots.lock().unwrap().get(&(type_path.to_owned(), name.to_owned())).cloned()
+    }
+
+    pub fn set_snapshot(&self, type_path: &str, name: &str, ron: String) {
+        self.snapshots.lock().unwrap().insert((type_path.to_owned(), name.to_owned()), ron);
+    }
+
+    pub(crate) fn save(&self, name: &str, value: &dyn Reflect) {
+        let registry = self.registry.read();
+        match ron::to_string(&ReflectSerializer::new(value, &registry)) {
+            Ok(ron) => self.set_snapshot(value.reflect_type_path(), name, ron),
+            Err(err) => bevy::log::warn!("failed to save state of `@{}`: {}", name, err),
+        }
+    }
+
+    // field is left as is if nothing was saved
+    pub(crate) fn load(&self, name: &str, value: &mut dyn Reflect) {
+        let Some(ron) = self.snapshot(value.reflect_type_path(), name) else { return; };
+        let registry = self.registry.read();
+
+        let result = ron::Deserializer::from_str(&ron)
+            .map_err(|err| err.to_string())
+            .and_then(|mut deserializer| {
+                UntypedReflectDeserializer::new(&registry).deserialize(&mut deserializer).map_err(|err| err.to_string())
+            });
+
+        // `apply` panics on mismatched types, and snapshots can be set from outside with `set_snapshot`
+        let type_id = |value: &dyn Reflect| value.get_represented_type_info().map(|info| info.type_id());
+        match result {
+            Ok(snapshot) if type_id(&*snapshot).is_some() && type_id(&*snapshot) == type_id(value) => value.apply(&*snapshot),
+            Ok(snapshot) => bevy::log::warn!(
+                "failed to load state of `@{}`: snapshot is `{}`, expected `{}`",
+                name,
+                snapshot.get_represented_type_info().map_or(snapshot.reflect_type_path(), |info| info.type_path()),
+                value.reflect_type_path(),
+            ),
+            Err(err) => bevy::log::warn!("failed to load state of `@{}`: {}", name, err),
+        }
+    }
+}
+
+impl EguiShared for UiconfStates {
+    const ID: &'static str = "bevy_uiconf_egui::states";
+}
+
+// Copies of data model structs changed by a window in `staging` mode, kept between frames
+// until they are applied or reverted. Shared, because egui temp data has to be `Clone`.
+#[derive(Clone, Default)]
+pub(crate) struct StagedCopies(Arc<Mutex<Vec<StagedCopy>>>);
+
+pub(crate) struct StagedCopy {
+    // address of the original struct, and a binding that leads to it
+    key: usize,
+    name: String,
+    value: Box<dyn Reflect>,
+}
+
+impl StagedCopies {
+    pub(crate) fn get(ctx: &egui::Context, id: egui::Id) -> Self {
+        ctx.data_mut(|d| d.get_temp_mut_or_default::<Self>(id).clone())
+    }
+
+    pub(crate) fn take(&self) -> Vec<StagedCopy> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+
+    pub(crate) fn put(&self, copies: Vec<StagedCopy>) {
+        *self.0.lock().unwrap() = copies;
+    }
+}
+
+fn source_key(source: &dyn Reflect) -> usize {
+    source as *const dyn Reflect as *const () as usize
+}
+
+// Reads go to the original data until a struct is written into, writes go into a copy of that struct
+// made with `Reflect::clone_value`. Triggers are events rather than state, so they're never staged.
+pub(crate) struct StagingSource<'a> {
+    data: &'a mut dyn DataSource,
+    copies: Vec<StagedCopy>,
+    registry: Option<AppTypeRegistry>,
+}
+
+impl<'a> StagingSource<'a> {
+    pub(crate) fn new(data: &'a mut dyn DataSource, copies: Vec<StagedCopy>, ctx: &egui::Context) -> Self {
+        Self { data, copies, registry: UiconfStates::get(ctx).map(|states| states.registry) }
+    }
+
+    // copies that turned out to be the same as originals are dropped, so that
+    // widgets writing the same value each frame don't make the window dirty
+    pub(crate) fn finish(self) -> Vec<StagedCopy> {
+        let data = self.data;
+        self.copies.into_iter().filter(|copy| {
+            let Some((original, _)) = data.source(&copy.name) else { return false; };
+            !original.reflect_partial_eq(&*copy.value).unwrap_or(false)
+        }).collect()
+    }
+
+    // `clone_value` returns dynamic types for structs, which bindings can't downcast to,
+    // so concrete ones are made whenever the type is registered
+    fn clone_concrete(&self, value: &dyn Reflect) -> Box<dyn Reflect> {
+        let concrete = self.registry.as_ref().and_then(|registry| {
+            let registry = registry.read();
+            let from_reflect = registry.get_type_data::<ReflectFromReflect>(value.as_any().type_id())?;
+            from_reflect.from_reflect(value)
+        });
+        concrete.unwrap_or_else(|| value.clone_value())
+    }
+}
+
+impl DataSource for StagingSource<'_> {
+    fn source<'n>(&self, name: &'n str) -> Option<(&dyn Reflect, &'n str)> {
+        let (source, field) = self.data.source(name)?;
+        match self.copies.iter().find(|copy| copy.key == source_key(source)) {
+            Some(copy) => Some((&*copy.value, field)),
+            None       => Some((source, field)),
+        }
+    }
+
+    fn source_mut<'n>(&mut self, name: &'n str) -> Option<(&mut dyn Reflect, &'n str)> {
+        let (source, field) = self.data.source(name)?;
+        let key = source_key(source);
+        let is_trigger = match source.reflect_ref() {
+            ReflectRef::Struct(source) => source.field(field).is_some_and(|field| field.is::<Trigger>()),
+            _ => false,
+        };
+        if is_trigger {
+            return self.data.source_mut(name);
+        }
+
+        let index = match self.copies.iter().position(|copy| copy.key == key) {
+            Some(index) => index,
+            None => {
+                let value = self.clone_concrete(source);
+                self.copies.push(StagedCopy { key, name: name.to_owned(), value });
+                self.copies.len() - 1
+            }
+        };
+        Some((&mut *self.copies[index].value, field))
+    }
+
+    fn data_ptr(&self) -> *const () {
+        self.data.data_ptr()
+    }
+}
+
+// writes all staged copies into the original data
+pub(crate) fn apply_staged(data: &mut dyn DataSource, copies: Vec<StagedCopy>) {
+    for copy in copies {
+        if let Some((original, _)) = data.source_mut(&copy.name) {
+            original.apply(&*copy.value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::reflect::ReflectMut;
+
+    use super::*;
+
+    #[derive(Reflect, Default)]
+    struct Settings {
+        volume: f32,
+    }
+
+    fn set_volume(data: &mut dyn DataSource, volume: f32) {
+        let (source, field) = data.source_mut("volume").unwrap();
+        let ReflectMut::Struct(source) = source.reflect_mut() else { panic!("expected struct"); };
+        source.field_mut(field).unwrap().apply(&volume);
+    }
+
+    fn volume(data: &dyn DataSource) -> f32 {
+        let (source, field) = data.source("volume").unwrap();
+        let ReflectRef::Struct(source) = source.reflect_ref() else { panic!("expected struct"); };
+        *source.field(field).unwrap().downcast_ref::<f32>().unwrap()
+    }
+
+    #[test]
+    fn staged_apply_and_revert() {
+        let ctx = egui::Context::default();
+        let mut settings = Settings { volume: 1. };
+
+        // writes are seen by widgets, but not by the original data
+        let mut staging = StagingSource::new(&mut settings, vec![], &ctx);
+        set_volume(&mut staging, 0.5);
+        assert_eq!(volume(&staging), 0.5);
+        let copies = staging.finish();
+        assert_eq!(copies.len(), 1);
+        assert_eq!(settings.volume, 1.);
+
+        // changing the value back makes the copy the same as the original, so it's dropped
+        let mut staging = StagingSource::new(&mut settings, copies, &ctx);
+        set_volume(&mut staging, 1.);
+        assert!(staging.finish().is_empty());
+
+        let mut staging = StagingSource::new(&mut settings, vec![], &ctx);
+        set_volume(&mut staging, 0.25);
+        let copies = staging.finish();
+        apply_staged(&mut settings, copies);
+        assert_eq!(settings.volume, 0.25);
+    }
+}
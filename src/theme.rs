@@ -0,0 +1,82 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::egui;
+use crate::shared::EguiShared;
+
+// Named egui styles that windows can be switched between at runtime, e.g. light and dark themes,
+// or colors of the player's faction:
+//
+//     themes.insert("dark", egui::Style { visuals: egui::Visuals::dark(), ..default() });
+//     themes.insert("light", egui::Style { visuals: egui::Visuals::light(), ..default() });
+//     themes.set_current(Some("dark"));
+//
+// Windows use the current theme, unless they select one with `theme = @settings.theme`.
+// Text styles from files are kept, so themes don't have to define them.
+#[derive(Resource, Clone, Default)]
+pub struct UiconfThemes {
+    inner: Arc<Mutex<ThemesInner>>,
+}
+
+#[derive(Default)]
+struct ThemesInner {
+    styles: HashMap<String, Arc<egui::Style>>,
+    // `None` is the style of egui context
+    current: Option<String>,
+    // unknown themes are only reported once
+    warned: HashSet<String>,
+}
+
+impl UiconfThemes {
+    pub fn insert(&self, name: impl Into<String>, style: egui::Style) {
+        self.inner.lock().unwrap().styles.insert(name.into(), Arc::new(style));
+    }
+
+    pub fn remove(&self, name: &str) {
+        self.inner.lock().unwrap().styles.remove(name);
+    }
+
+    pub fn style(&self, name: &str) -> Option<Arc<egui::Style>> {
+        self.inner.lock().unwrap().styles.get(name).cloned()
+    }
+
+    pub fn current(&self) -> Option<String> {
+        self.inner.lock().unwrap().current.clone()
+    }
+
+    pub fn set_current(&self, name: Option<&str>) {
+        self.inner.lock().unwrap().current = name.map(ToOwned::to_owned);
+    }
+
+    // Context style is replaced with the theme for the duration of `show`, window `theme` takes precedence
+    // over the current one. Unknown themes are reported, and context style is used instead.
+    pub(crate) fn scoped<R>(ctx: &egui::Context, theme: Option<&str>, show: impl FnOnce() -> R) -> R {
+        let Some(themes) = Self::get(ctx) else { return show(); };
+        let Some(name) = theme.map(ToOwned::to_owned).or_else(|| themes.current()) else { return show(); };
+
+        let Some(themed) = themes.style(&name) else {
+            if themes.inner.lock().unwrap().warned.insert(name.clone()) {
+                crate::diagnostics::report(crate::DiagnosticKind::UnknownTheme, format!("unknown theme `{}`", name));
+            }
+            return show();
+        };
+
+        let style = ctx.style();
+        let mut themed = (*themed).clone();
+        for (name, font) in style.text_styles.iter() {
+            themed.text_styles.entry(name.clone()).or_insert_with(|| font.clone());
+        }
+        ctx.set_style(themed);
+
+        let result = show();
+
+        ctx.set_style(style);
+        result
+    }
+}
+
+impl EguiShared for UiconfThemes {
+    const ID: &'static str = "bevy_uiconf_egui::themes";
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use bevy::prelude::*;
+
+use crate::egui;
+use crate::reader::error::Error;
+use crate::response::{LabelToId, WidgetResponse};
+use crate::{UiconfCommand, UiconfDiagnostics, UiconfWindow};
+
+// Runs a window without bevy app or renderer, with simulated input:
+//
+//     let mut harness = Harness::load("assets/gui/menu.gui").unwrap();
+//     let mut model = MenuModel::default();
+//     harness.click(&mut model, MenuLabel::StartButton);
+//     assert_eq!(model.start_game.get_count(), 1);
+//
+// Widgets are found by their `id`, same as with `UiconfWindow::response`.
+pub struct Harness {
+    pub ctx: egui::Context,
+    pub window: UiconfWindow,
+    // events sent with the next frame
+    pub events: Vec<egui::Event>,
+    pub screen_size: egui::Vec2,
+    // bindings that failed to resolve and other problems, tests can assert it's empty
+    pub diagnostics: UiconfDiagnostics,
+    frame: u32,
+    commands: Vec<String>,
+    // whatever was painted in the last frame, used by `snapshot`
+    pub(crate) shapes: Vec<egui::epaint::ClippedShape>,
+    pub(crate) pixels_per_point: f32,
+    pub(crate) textures: HashMap<egui::TextureId, egui::ColorImage>,
+}
+
+impl Harness {
+    pub fn new(window: UiconfWindow) -> Self {
+        let ctx = egui::Context::default();
+        let diagnostics = UiconfDiagnostics::default();
+        diagnostics.install(&ctx);
+
+        Self {
+            ctx,
+            window,
+            events: vec![],
+            screen_size: egui::vec2(1280., 720.),
+            diagnostics,
+            frame: 0,
+            commands: vec![],
+            shapes: vec![],
+            pixels_per_point: 1.,
+            textures: HashMap::new(),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Vec<Error>> {
+        let path = path.as_ref();
+        let data = std::fs::read(path).map_err(|error| {
+            vec![Error::Io { error, file: path.display().to_string() }]
+        })?;
+        Ok(Self::new(UiconfWindow::from_embedded(&data, &path.to_string_lossy())?))
+    }
+
+    // shows the window once with all queued events, 1/60 of a second passes between frames
+    pub fn run(&mut self, data: &mut dyn Reflect) {
+        let input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, self.screen_size)),
+            time: Some(self.frame as f64 / 60.),
+            predicted_dt: 1. / 60.,
+            events: std::mem::take(&mut self.events),
+            ..Default::default()
+        };
+        self.frame += 1;
+
+        let mut ctx = self.ctx.clone();
+        let output = ctx.clone().run(input, |_| self.window.show(data, &mut ctx));
+        self.update_textures(&output.textures_delta);
+        self.shapes = output.shapes;
+        self.pixels_per_point = output.pixels_per_point;
+
+        let commands = UiconfCommand::drain(&self.ctx, Entity::PLACEHOLDER);
+        self.commands.extend(commands.into_iter().map(|command| command.name));
+    }
+
+    pub fn run_frames(&mut self, data: &mut dyn Reflect, count: usize) {
+        for _ in 0..count {
+            self.run(data);
+        }
+    }
+
+    // response of a widget in the last frame, `None` if it wasn't shown
+    pub fn response(&self, label: impl LabelToId) -> Option<WidgetResponse> {
+        self.window.response(&self.ctx, label)
+    }
+
+    // names of all commands sent by `emit(...)` since the last call
+    pub fn take_commands(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.commands)
+    }
+
+    pub fn hover(&mut self, data: &mut dyn Reflect, label: impl LabelToId) {
+        let pos = self.widget_center(data, &label);
+        self.events.push(egui::Event::PointerMoved(pos));
+        self.run(data);
+    }
+
+    // moves pointer over the widget, then presses and releases primary button in separate frames
+    pub fn click(&mut self, data: &mut dyn Reflect, label: impl LabelToId) {
+        self.hover(data, &label);
+        let pos = self.widget_center(data, &label);
+        for pressed in [true, false] {
+            self.events.push(egui::Event::PointerButton {
+                pos,
+                button: egui::PointerButton::Primary,
+                pressed,
+                modifiers: egui::Modifiers::NONE,
+            });
+            self.run(data);
+        }
+    }
+
+    // font atlas is sent once and then updated in patches, same as with a real renderer
+    fn update_textures(&mut self, delta: &egui::TexturesDelta) {
+        for (id, delta) in delta.set.iter() {
+            let image = match &delta.image {
+                egui::ImageData::Color(image) => (**image).clone(),
+                egui::ImageData::Font(image) => egui::ColorImage { size: image.size, pixels: image.srgba_pixels(None).collect() },
+            };
+
+            match (delta.pos, self.textures.get_mut(id)) {
+                (Some([x, y]), Some(texture)) => {
+                    for row in 0..image.size[1] {
+                        let start = (y + row) * texture.size[0] + x;
+                        texture.pixels[start..start + image.size[0]]
+                            .copy_from_slice(&image.pixels[row * image.size[0]..(row + 1) * image.size[0]]);
+                    }
+                }
+                _ => {
+                    self.textures.insert(*id, image);
+                }
+            }
+        }
+
+        for id in delta.free.iter() {
+            self.textures.remove(id);
+        }
+    }
+
+    // panics if widget isn't visible, so that tests fail with a clear message
+    fn widget_center(&mut self, data: &mut dyn Reflect, label: &impl LabelToId) -> egui::Pos2 {
+        // windows are invisible in the first frame, and ignore input until they're shown
+        if self.frame < 2 {
+            self.run_frames(data, 2 - self.frame as usize);
+        }
+        let Some(rect) = self.response(label).and_then(|response| response.visible_rect()) else {
+            panic!("widget `{}` is not visible", label.to_id());
+        };
+        rect.center()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::Harness;
+    use crate::reader::data_model::Trigger;
+    use crate::UiconfWindow;
+
+    #[derive(Reflect, Default)]
+    struct Menu {
+        start: Trigger,
+    }
+
+    const MENU: &str = r#"
+        window = {
+            title = "Menu"
+            button = {
+                id = start
+                text = "Start"
+                clicked = @start
+            }
+        }
+    "#;
+
+    #[test]
+    fn click_button() {
+        let window = UiconfWindow::from_embedded(MENU.as_bytes(), "menu.gui").unwrap();
+        let mut harness = Harness::new(window);
+        let mut menu = Menu::default();
+
+        harness.click(&mut menu, "start");
+        assert_eq!(menu.start.get_count(), 1);
+        assert!(harness.diagnostics.is_empty());
+    }
+}
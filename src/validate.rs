@@ -0,0 +1,87 @@
+use std::any::TypeId;
+use std::path::Path;
+
+use bevy::reflect::TypeRegistry;
+
+use crate::model::Root;
+use crate::reader::data_model::UiconfModel;
+use crate::reader::error::Error;
+use crate::reader::format::Format;
+use crate::reader::reader::LoadingMode;
+use crate::reader::schema::{validate_bindings, Schema};
+use crate::registry::UiconfWidgetRegistry;
+
+// Reads .gui file contents, and validates all bindings against data model type (if provided).
+pub fn read_validated(
+    data: &[u8],
+    file: &str,
+    format: Format,
+    mode: LoadingMode,
+    schema: Option<(&TypeRegistry, Schema)>,
+    widgets: &UiconfWidgetRegistry,
+) -> Result<Root, Vec<Error>> {
+    let converted = format.to_gui(data, file).map_err(|err| vec![err])?;
+    let root = Root::read_mapped(&converted.data, converted.source_map.as_ref(), file, mode, widgets).map_err(|err| vec![err])?;
+
+    if let Some((registry, schema)) = schema {
+        let errors = validate_bindings(&root.bindings, registry, schema);
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+    }
+
+    Ok(root)
+}
+
+// Checks .gui file without running bevy app, intended to be used in tests:
+//
+//     let mut registry = TypeRegistry::new();
+//     registry.register::<MyDataModel>();
+//     validate_file("assets/gui/menu.gui", Some((&registry, TypeId::of::<MyDataModel>()))).unwrap();
+//
+pub fn validate_file(path: impl AsRef<Path>, schema: Option<(&TypeRegistry, TypeId)>) -> Result<(), Vec<Error>> {
+    validate_file_with_widgets(path, schema, &UiconfWidgetRegistry::default())
+}
+
+// Same as `validate_file`, for files that use custom widgets.
+pub fn validate_file_with_widgets(
+    path: impl AsRef<Path>,
+    schema: Option<(&TypeRegistry, TypeId)>,
+    widgets: &UiconfWidgetRegistry,
+) -> Result<(), Vec<Error>> {
+    let schema = schema.map(|(registry, type_id)| (registry, Schema::Model(type_id)));
+    validate_path(path.as_ref(), schema, widgets)
+}
+
+// Same as `validate_file`, for windows shown with `UiconfContext`, namespaces are given with their data model types:
+//
+//     validate_context_file("assets/gui/hud.gui", &registry, &[
+//         ("settings", TypeId::of::<Settings>()),
+//         ("player", TypeId::of::<Player>()),
+//     ]).unwrap();
+//
+pub fn validate_context_file(
+    path: impl AsRef<Path>,
+    registry: &TypeRegistry,
+    namespaces: &[(&str, TypeId)],
+) -> Result<(), Vec<Error>> {
+    validate_path(path.as_ref(), Some((registry, Schema::Context(namespaces))), &UiconfWidgetRegistry::default())
+}
+
+fn validate_path(path: &Path, schema: Option<(&TypeRegistry, Schema)>, widgets: &UiconfWidgetRegistry) -> Result<(), Vec<Error>> {
+    let data = std::fs::read(path).map_err(|error| {
+        vec![Error::Io { error, file: path.display().to_string() }]
+    })?;
+
+    read_validated(&data, &path.to_string_lossy(), Format::from_path(path), LoadingMode::Strict, schema, widgets).map(|_| ())
+}
+
+// Same as `validate_file`, with schema taken from `#[derive(UiconfModel)]`:
+//
+//     validate_model_file::<MyDataModel>("assets/gui/menu.gui").unwrap();
+//
+pub fn validate_model_file<T: UiconfModel>(path: impl AsRef<Path>) -> Result<(), Vec<Error>> {
+    let mut registry = TypeRegistry::new();
+    T::register_types(&mut registry);
+    validate_file(path, Some((&registry, TypeId::of::<T>())))
+}
@@ -0,0 +1,50 @@
+pub mod writer;
+
+use writer::Writer;
+
+// Counterpart of `ReadUiconf`, writes values in canonical .gui format.
+pub trait ToUiconf {
+    fn write_uiconf(&self, writer: &mut Writer);
+}
+
+impl ToUiconf for str {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.quoted(self);
+    }
+}
+
+impl ToUiconf for String {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.quoted(self);
+    }
+}
+
+impl ToUiconf for bool {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.scalar(if *self { "yes" } else { "no" });
+    }
+}
+
+macro_rules! impl_to_uiconf_scalar {
+    ($($t:ty),*) => {
+        $(
+            impl ToUiconf for $t {
+                fn write_uiconf(&self, writer: &mut Writer) {
+                    writer.scalar(self);
+                }
+            }
+        )*
+    };
+}
+
+impl_to_uiconf_scalar!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+impl<T: ToUiconf> ToUiconf for Vec<T> {
+    fn write_uiconf(&self, writer: &mut Writer) {
+        writer.array(|writer| {
+            for item in self.iter() {
+                writer.item(item);
+            }
+        });
+    }
+}
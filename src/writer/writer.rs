@@ -0,0 +1,88 @@
+use std::fmt::{Display, Write};
+
+use super::ToUiconf;
+
+const INDENT: &str = "    ";
+
+#[derive(Default)]
+pub struct Writer {
+    output: String,
+    depth: usize,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn finish(self) -> String {
+        self.output
+    }
+
+    // `key = value` on its own line
+    pub fn field<T: ToUiconf + ?Sized>(&mut self, key: &str, value: &T) {
+        self.field_with(key, |writer| value.write_uiconf(writer));
+    }
+
+    pub fn field_with(&mut self, key: &str, f: impl FnOnce(&mut Self)) {
+        for _ in 0..self.depth {
+            self.output.push_str(INDENT);
+        }
+        self.output.push_str(key);
+        self.output.push_str(" = ");
+        f(self);
+        self.output.push('\n');
+    }
+
+    // `{ key = value ... }` spanning multiple lines
+    pub fn object(&mut self, f: impl FnOnce(&mut Self)) {
+        let start = self.output.len();
+        self.output.push_str("{\n");
+        self.depth += 1;
+        f(self);
+        self.depth -= 1;
+
+        if self.output.len() == start + 2 {
+            self.output.truncate(start);
+            self.output.push_str("{}");
+        } else {
+            for _ in 0..self.depth {
+                self.output.push_str(INDENT);
+            }
+            self.output.push('}');
+        }
+    }
+
+    // `{ value value ... }` on a single line
+    pub fn array(&mut self, f: impl FnOnce(&mut Self)) {
+        let start = self.output.len();
+        self.output.push('{');
+        f(self);
+
+        if self.output.len() == start + 1 {
+            self.output.push('}');
+        } else {
+            self.output.push_str(" }");
+        }
+    }
+
+    pub fn item<T: ToUiconf + ?Sized>(&mut self, value: &T) {
+        self.output.push(' ');
+        value.write_uiconf(self);
+    }
+
+    pub fn scalar(&mut self, value: impl Display) {
+        let _ = write!(self.output, "{}", value);
+    }
+
+    pub fn quoted(&mut self, value: &str) {
+        self.output.push('"');
+        for c in value.chars() {
+            if c == '"' || c == '\\' {
+                self.output.push('\\');
+            }
+            self.output.push(c);
+        }
+        self.output.push('"');
+    }
+}